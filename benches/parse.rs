@@ -0,0 +1,95 @@
+// Throughput of the owned parse tree against the borrowed views, over a
+// capture-like buffer of state synchronization reports.
+//
+//     cargo bench --bench parse
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use pcep_parser::borrowed::MessageView;
+use pcep_parser::builder::{ipv4_hop, LspBuilder};
+use pcep_parser::common::{IntendedAttrList, IntendedPathList, OperationalStatus};
+use pcep_parser::encoder::Encode;
+use pcep_parser::messages::message::PcepMessage;
+use pcep_parser::messages::pcrpt::{PcRpt, StateReport};
+use pcep_parser::messages::pcupdate::Path;
+use pcep_parser::objects::ero::EroObject;
+
+use std::net::Ipv4Addr;
+
+const LSP_COUNT: usize = 1000;
+
+// One synchronization PCRpt per LSP, each with a three hop ERO
+fn capture() -> Vec<u8> {
+    let mut buf = vec![];
+    for i in 0..LSP_COUNT {
+        let lsp_object = LspBuilder::new()
+            .plsp_id(i as u32 + 1)
+            .name(&format!("lsp-{i}"))
+            .operational_status(OperationalStatus::Up)
+            .delegate(true)
+            .sync(true)
+            .build();
+        let ero_object = EroObject::new(
+            (1..=3)
+                .map(|hop| ipv4_hop(Ipv4Addr::new(192, 168, 0, hop)))
+                .collect(),
+        );
+        let message = PcepMessage::PcRpt(PcRpt::new(vec![StateReport::new(
+            None,
+            lsp_object,
+            Some(Path::new(
+                IntendedPathList {
+                    paths: vec![(None, ero_object)],
+                },
+                IntendedAttrList::new(None, None, vec![]),
+            )),
+        )]));
+        message.encode(&mut buf);
+    }
+    buf
+}
+
+fn owned(input: &[u8]) -> usize {
+    let mut left = input;
+    let mut count = 0;
+    while !left.is_empty() {
+        let (remaining, message) = PcepMessage::parse_message(left).expect("[!!] Parse error");
+        count += u8::from(black_box(&message).message_type()) as usize;
+        left = remaining;
+    }
+    count
+}
+
+// Touches every object, TLV and subobject so that both sides decode
+// the same amount of the input
+fn borrowed(input: &[u8]) -> usize {
+    let mut left = input;
+    let mut count = 0;
+    while !left.is_empty() {
+        let (remaining, message) = MessageView::parse(left).expect("[!!] Parse error");
+        for object in message.objects() {
+            let object = object.expect("[!!] Parse error");
+            count += object
+                .tlvs()
+                .map(|tlv| tlv.expect("[!!] Parse error").value().len())
+                .sum::<usize>();
+            count += object
+                .subobjects()
+                .map(|s| s.expect("[!!] Parse error").body().len())
+                .sum::<usize>();
+        }
+        left = remaining;
+    }
+    count
+}
+
+fn parse_benchmark(c: &mut Criterion) {
+    let input = capture();
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_function("owned", |b| b.iter(|| owned(black_box(&input))));
+    group.bench_function("borrowed", |b| b.iter(|| borrowed(black_box(&input))));
+    group.finish();
+}
+
+criterion_group!(benches, parse_benchmark);
+criterion_main!(benches);
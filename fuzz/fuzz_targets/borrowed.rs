@@ -0,0 +1,19 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pcep_parser::borrowed::MessageView;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((_remaining, message)) = MessageView::parse(data) {
+        for object in message.objects().flatten() {
+            let _ = object.plsp_id();
+            let _ = object.srp_id();
+            let _ = object.symbolic_path_name();
+            for _tlv in object.tlvs() {}
+            for subobject in object.subobjects().flatten() {
+                let _ = subobject.ipv4_prefix();
+                let _ = subobject.sr_sid();
+            }
+        }
+    }
+});
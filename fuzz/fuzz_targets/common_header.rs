@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pcep_parser::messages::header::CommonHeader;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok((_remaining, common_header)) = CommonHeader::parse_common_header(data) {
+        let _ = common_header.to_string();
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pcep_parser::encoder::Encode;
+use pcep_parser::messages::message::PcepMessage;
+
+// Full framing: common header, message body and every object in it
+fuzz_target!(|data: &[u8]| {
+    if let Ok((_remaining, message)) = PcepMessage::parse_message(data) {
+        let _ = message.to_string();
+        let _ = message.to_bytes();
+    }
+    let _ = PcepMessage::parse_complete(data);
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pcep_parser::encoder::Encode;
+use pcep_parser::objects::open::OpenObject;
+
+// Body of an Open message, a single OPEN object
+fuzz_target!(|data: &[u8]| {
+    if let Ok((_remaining, open_object)) = OpenObject::parse_open_object(data) {
+        let _ = open_object.to_string();
+        let _ = open_object.to_bytes();
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pcep_parser::capture::pcap::PcapReader;
+use pcep_parser::capture::reassembly::Reassembler;
+
+// Capture file down to reassembled PCEP messages
+fuzz_target!(|data: &[u8]| {
+    if let Ok((_remaining, segments)) = PcapReader::read_tcp_segments(data) {
+        let _ = Reassembler::messages_from_segments(&segments);
+    }
+});
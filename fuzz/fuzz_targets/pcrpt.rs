@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pcep_parser::encoder::Encode;
+use pcep_parser::messages::pcrpt::PcRpt;
+
+// Message body, without the common header
+fuzz_target!(|data: &[u8]| {
+    if let Ok((_remaining, message)) = PcRpt::parse_report_message(data) {
+        let _ = message.to_string();
+        let _ = message.to_bytes();
+    }
+});
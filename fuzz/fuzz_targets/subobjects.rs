@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pcep_parser::encoder::Encode;
+use pcep_parser::subobjects::parser::Parser;
+
+// ERO object body
+fuzz_target!(|data: &[u8]| {
+    if let Ok((_remaining, subobjects)) = Parser::parse_subobjects(data) {
+        for subobject in subobjects {
+            let _ = subobject.to_string();
+            let _ = subobject.to_bytes();
+        }
+    }
+});
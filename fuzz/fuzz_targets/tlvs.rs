@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pcep_parser::encoder::Encode;
+use pcep_parser::tlvs::tlv_parser::Parser;
+
+// TLV section of an object body
+fuzz_target!(|data: &[u8]| {
+    if let Ok((_remaining, tlvs)) = Parser::parse_tlvs(data) {
+        for tlv in tlvs {
+            let _ = tlv.to_string();
+            let _ = tlv.to_bytes();
+        }
+    }
+});
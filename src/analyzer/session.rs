@@ -0,0 +1,808 @@
+use colored::Colorize;
+use indoc::writedoc;
+
+use crate::capture::reassembly::CapturedMessage;
+use crate::messages::message::PcepMessage;
+use crate::messages::types::MessageType;
+use crate::objects::close::CloseReason;
+use crate::objects::open::OpenObject;
+use crate::session::fsm::SessionFsm;
+use crate::session::types::{SessionAction, SessionConfig, SessionEvent, SessionState, Timer};
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddrV4};
+use std::time::Duration;
+
+const PCEP_PORT: u16 = 4189;
+
+// Session characteristics a speaker advertised in its Open
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct OpenParameters {
+    pub keepalive: u8,
+    pub deadtimer: u8,
+    pub sid: u8,
+    pub stateful: bool,
+    pub lsp_update_capability: bool,
+    pub lsp_instantiate_capability: bool,
+    pub max_sid_depth: Option<u8>,
+}
+
+impl From<&OpenObject> for OpenParameters {
+    fn from(open_object: &OpenObject) -> Self {
+        let stateful_capability = open_object.stateful_capability();
+        OpenParameters {
+            keepalive: open_object.keepalive,
+            deadtimer: open_object.deadtimer,
+            sid: open_object.sid,
+            stateful: stateful_capability.is_some(),
+            lsp_update_capability: stateful_capability
+                .map(|spc| spc.flag_lsp_update_capability)
+                .unwrap_or(false),
+            lsp_instantiate_capability: stateful_capability
+                .map(|spc| spc.flag_lsp_instantiate_capability)
+                .unwrap_or(false),
+            max_sid_depth: open_object.sr_capability().map(|srpc| srpc.max_sid_depth),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Violation {
+    // A message other than Open, Keepalive or PCErr was sent before
+    // the session was up, the peer answers with PCErr(1,1)
+    MissingOpen {
+        timestamp: Duration,
+        speaker: SocketAddrV4,
+        message_type: MessageType,
+    },
+    // The speaker stayed silent for longer than the DeadTimer it advertised,
+    // timestamp is the expiry of the peer's DeadTimer
+    DeadTimerExpired {
+        timestamp: Duration,
+        speaker: SocketAddrV4,
+        silence: Duration,
+        deadtimer: u8,
+    },
+    // A new session towards the same peer reused the previous session ID
+    DuplicateSessionId {
+        timestamp: Duration,
+        speaker: SocketAddrV4,
+        sid: u8,
+    },
+    // A message was sent that the receiver did not advertise support for
+    CapabilityMismatch {
+        timestamp: Duration,
+        speaker: SocketAddrV4,
+        message_type: MessageType,
+        capability: &'static str,
+    },
+    // Any other PCErr the session state machine of the peer answers
+    // the speaker with
+    SessionError {
+        timestamp: Duration,
+        speaker: SocketAddrV4,
+        error_type: u8,
+        error_value: u8,
+    },
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingOpen {
+                timestamp,
+                speaker,
+                message_type,
+            } => write!(
+                f,
+                "[{:.6}] {} sent {} before its Open",
+                timestamp.as_secs_f64(),
+                speaker,
+                message_type
+            ),
+            Self::DeadTimerExpired {
+                timestamp,
+                speaker,
+                silence,
+                deadtimer,
+            } => write!(
+                f,
+                "[{:.6}] {} silent for {:.3}s, DeadTimer is {}s",
+                timestamp.as_secs_f64(),
+                speaker,
+                silence.as_secs_f64(),
+                deadtimer
+            ),
+            Self::DuplicateSessionId {
+                timestamp,
+                speaker,
+                sid,
+            } => write!(
+                f,
+                "[{:.6}] {} reused session ID {} for a new session",
+                timestamp.as_secs_f64(),
+                speaker,
+                sid
+            ),
+            Self::CapabilityMismatch {
+                timestamp,
+                speaker,
+                message_type,
+                capability,
+            } => write!(
+                f,
+                "[{:.6}] {} sent {} but peer did not advertise {}",
+                timestamp.as_secs_f64(),
+                speaker,
+                message_type,
+                capability
+            ),
+            Self::SessionError {
+                timestamp,
+                speaker,
+                error_type,
+                error_value,
+            } => write!(
+                f,
+                "[{:.6}] {} is answered with PCErr {}/{}",
+                timestamp.as_secs_f64(),
+                speaker,
+                error_type,
+                error_value
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SpeakerReport {
+    pub address: SocketAddrV4,
+    pub open: Option<OpenParameters>,
+    pub message_count: usize,
+    pub first_seen: Option<Duration>,
+    pub last_seen: Option<Duration>,
+    last_keepalive: Option<Duration>,
+    pub keepalive_intervals: Vec<Duration>,
+    // State changes of the speaker's session state machine
+    pub states: Vec<(Duration, SessionState)>,
+}
+
+impl SpeakerReport {
+    fn new(address: SocketAddrV4) -> Self {
+        SpeakerReport {
+            address,
+            open: None,
+            message_count: 0,
+            first_seen: None,
+            last_seen: None,
+            last_keepalive: None,
+            keepalive_intervals: vec![],
+            states: vec![],
+        }
+    }
+
+    pub fn min_keepalive_interval(&self) -> Option<Duration> {
+        self.keepalive_intervals.iter().min().copied()
+    }
+
+    pub fn max_keepalive_interval(&self) -> Option<Duration> {
+        self.keepalive_intervals.iter().max().copied()
+    }
+
+    pub fn mean_keepalive_interval(&self) -> Option<Duration> {
+        if self.keepalive_intervals.is_empty() {
+            return None;
+        }
+        let total: Duration = self.keepalive_intervals.iter().sum();
+        Some(total / self.keepalive_intervals.len() as u32)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SessionReport {
+    pub pcc: SpeakerReport,
+    pub pce: SpeakerReport,
+    pub closed: bool,
+    pub violations: Vec<Violation>,
+}
+
+impl SessionReport {
+    fn speaker_mut(&mut self, address: SocketAddrV4) -> (&mut SpeakerReport, &SpeakerReport) {
+        if self.pcc.address == address {
+            (&mut self.pcc, &self.pce)
+        } else {
+            (&mut self.pce, &self.pcc)
+        }
+    }
+
+    // Keepalive interval each side sends at, and the DeadTimer applied to it
+    pub fn negotiated(&self) -> Option<(OpenParameters, OpenParameters)> {
+        Some((self.pcc.open?, self.pce.open?))
+    }
+}
+
+fn format_interval(interval: Option<Duration>) -> String {
+    match interval {
+        Some(interval) => format!("{:.3}s", interval.as_secs_f64()),
+        None => String::from("-"),
+    }
+}
+
+impl std::fmt::Display for SpeakerReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let open_str = match self.open {
+            Some(open) => format!(
+                "keepalive={} deadtimer={} sid={} stateful={} update={} instantiate={} msd={}",
+                open.keepalive,
+                open.deadtimer,
+                open.sid,
+                open.stateful,
+                open.lsp_update_capability,
+                open.lsp_instantiate_capability,
+                open.max_sid_depth
+                    .map(|msd| msd.to_string())
+                    .unwrap_or_else(|| String::from("-"))
+            ),
+            None => String::from("no Open seen"),
+        };
+        writeln!(f, "        address                 = {}", self.address)?;
+        writeln!(f, "        open                    = {}", open_str)?;
+        writeln!(
+            f,
+            "        messages                = {}",
+            self.message_count
+        )?;
+        writeln!(
+            f,
+            "        keepalive interval      = min {} / mean {} / max {}",
+            format_interval(self.min_keepalive_interval()),
+            format_interval(self.mean_keepalive_interval()),
+            format_interval(self.max_keepalive_interval())
+        )?;
+        let states_str: Vec<String> = self
+            .states
+            .iter()
+            .map(|(timestamp, state)| format!("{:.3}s {}", timestamp.as_secs_f64(), state))
+            .collect();
+        writeln!(
+            f,
+            "        states                  = {}",
+            states_str.join(", ")
+        )
+    }
+}
+
+impl std::fmt::Display for SessionReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut violations_str = String::new();
+        for violation in self.violations.iter() {
+            violations_str.push_str(&format!("        {}\n", violation));
+        }
+        if violations_str.is_empty() {
+            violations_str.push_str("        none\n");
+        }
+        let title = "==[PCEP Session]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {pcc_title}
+            {pcc}    {pce_title}
+            {pce}    closed                  = {closed}
+            {violations_title}
+            {violations_str}"#,
+            title = title,
+            pcc_title = "[[PCC]]".bold(),
+            pcc = self.pcc,
+            pce_title = "[[PCE]]".bold(),
+            pce = self.pce,
+            closed = self.closed,
+            violations_title = "    [[Violations]]".bold(),
+            violations_str = violations_str
+        )
+    }
+}
+
+// Session state machine of one speaker, fed with what its peer sends.
+// Its timers run on the capture timestamps.
+#[derive(Debug)]
+struct SpeakerFsm {
+    fsm: SessionFsm,
+    timers: Vec<(Timer, Duration)>,
+}
+
+impl SpeakerFsm {
+    fn new() -> Self {
+        SpeakerFsm {
+            fsm: SessionFsm::new(SessionConfig::default()),
+            timers: vec![],
+        }
+    }
+
+    fn next_expiry(&self) -> Option<(Timer, Duration)> {
+        self.timers
+            .iter()
+            .min_by_key(|(_timer, deadline)| *deadline)
+            .copied()
+    }
+
+    fn handle(&mut self, timestamp: Duration, event: SessionEvent) -> Vec<SessionAction> {
+        let actions = self.fsm.handle(event);
+        for action in actions.iter() {
+            match *action {
+                SessionAction::StartTimer(timer, seconds) => {
+                    self.timers.retain(|(running, _deadline)| *running != timer);
+                    self.timers
+                        .push((timer, timestamp + Duration::from_secs(seconds as u64)));
+                }
+                SessionAction::StopTimer(timer) => {
+                    self.timers.retain(|(running, _deadline)| *running != timer);
+                }
+                _ => {}
+            }
+        }
+        actions
+    }
+}
+
+// What made a state machine act, to attribute its PCErr
+#[derive(Debug, Clone, Copy)]
+enum Trigger {
+    Message(MessageType),
+    // Connection setup or timer expiry
+    Internal,
+    // The PCErr was seen in the capture, it is not a violation
+    OpenRejected,
+}
+
+// Follows every PCEP session seen in a stream of captured messages
+#[derive(Debug, Default)]
+pub struct SessionAnalyzer {
+    sessions: Vec<SessionReport>,
+    // PCC and PCE state machines of each session
+    fsms: Vec<(SpeakerFsm, SpeakerFsm)>,
+    active: HashMap<(SocketAddrV4, SocketAddrV4), usize>,
+    last_sid: HashMap<(Ipv4Addr, Ipv4Addr), u8>,
+}
+
+impl SessionAnalyzer {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn session_key(captured: &CapturedMessage) -> (SocketAddrV4, SocketAddrV4) {
+        if captured.source < captured.destination {
+            (captured.source, captured.destination)
+        } else {
+            (captured.destination, captured.source)
+        }
+    }
+
+    fn new_session(captured: &CapturedMessage) -> SessionReport {
+        // PCE listens on the well-known port, otherwise assume the PCC spoke first
+        let (pcc, pce) = if captured.source.port() == PCEP_PORT {
+            (captured.destination, captured.source)
+        } else {
+            (captured.source, captured.destination)
+        };
+        SessionReport {
+            pcc: SpeakerReport::new(pcc),
+            pce: SpeakerReport::new(pce),
+            closed: false,
+            violations: vec![],
+        }
+    }
+
+    fn session_index(&mut self, captured: &CapturedMessage) -> usize {
+        let key = Self::session_key(captured);
+        let reopened = matches!(captured.message, PcepMessage::Open(_))
+            && self
+                .active
+                .get(&key)
+                .map(|index| self.sessions[*index].closed)
+                .unwrap_or(false);
+        match self.active.get(&key) {
+            Some(index) if !reopened => *index,
+            _ => {
+                self.sessions.push(Self::new_session(captured));
+                self.fsms.push((SpeakerFsm::new(), SpeakerFsm::new()));
+                let index = self.sessions.len() - 1;
+                self.active.insert(key, index);
+                for to_pcc in [true, false] {
+                    self.feed(
+                        index,
+                        to_pcc,
+                        captured.timestamp,
+                        SessionEvent::ConnectionEstablished,
+                        Trigger::Internal,
+                    );
+                }
+                index
+            }
+        }
+    }
+
+    // Hand an event to the state machine of the PCC (to_pcc) or of the
+    // PCE, and report what it does about the other speaker
+    fn feed(
+        &mut self,
+        index: usize,
+        to_pcc: bool,
+        timestamp: Duration,
+        event: SessionEvent,
+        trigger: Trigger,
+    ) {
+        let (pcc_fsm, pce_fsm) = &mut self.fsms[index];
+        let speaker_fsm = if to_pcc { pcc_fsm } else { pce_fsm };
+        let actions = speaker_fsm.handle(timestamp, event);
+        let state = speaker_fsm.fsm.state();
+        let deadtimer = speaker_fsm.fsm.negotiated().remote_deadtimer;
+
+        let session = &mut self.sessions[index];
+        let (owner, peer) = if to_pcc {
+            (&mut session.pcc, &session.pce)
+        } else {
+            (&mut session.pce, &session.pcc)
+        };
+        if owner.states.last().map(|(_timestamp, last)| *last) != Some(state) {
+            owner.states.push((timestamp, state));
+        }
+        let speaker = peer.address;
+        let silence = timestamp.saturating_sub(peer.last_seen.unwrap_or(timestamp));
+        for action in actions {
+            let violation = match (action, trigger) {
+                (SessionAction::SendPcErr { .. }, Trigger::OpenRejected) => continue,
+                (
+                    SessionAction::SendPcErr {
+                        error_type: 1,
+                        error_value: 1,
+                    },
+                    Trigger::Message(message_type),
+                ) if message_type != MessageType::Open => Violation::MissingOpen {
+                    timestamp,
+                    speaker,
+                    message_type,
+                },
+                (
+                    SessionAction::SendPcErr {
+                        error_type,
+                        error_value,
+                    },
+                    _,
+                ) => Violation::SessionError {
+                    timestamp,
+                    speaker,
+                    error_type,
+                    error_value,
+                },
+                (SessionAction::SendClose(CloseReason::DeadTimerExpired), _) => {
+                    Violation::DeadTimerExpired {
+                        timestamp,
+                        speaker,
+                        silence,
+                        deadtimer,
+                    }
+                }
+                _ => continue,
+            };
+            session.violations.push(violation);
+        }
+    }
+
+    // Fire, in order, the timers of both state machines that expired
+    // before timestamp
+    fn expire_timers(&mut self, index: usize, timestamp: Duration) {
+        loop {
+            let (pcc_fsm, pce_fsm) = &mut self.fsms[index];
+            let next = [
+                (true, pcc_fsm.next_expiry()),
+                (false, pce_fsm.next_expiry()),
+            ]
+            .into_iter()
+            .filter_map(|(to_pcc, expiry)| {
+                expiry.map(|(timer, deadline)| (deadline, to_pcc, timer))
+            })
+            .filter(|(deadline, _to_pcc, _timer)| *deadline < timestamp)
+            .min_by_key(|(deadline, _to_pcc, _timer)| *deadline);
+            let (deadline, to_pcc, timer) = match next {
+                Some(next) => next,
+                None => break,
+            };
+            let speaker_fsm = if to_pcc { pcc_fsm } else { pce_fsm };
+            speaker_fsm
+                .timers
+                .retain(|(running, _deadline)| *running != timer);
+            self.feed(
+                index,
+                to_pcc,
+                deadline,
+                SessionEvent::TimerExpired(timer),
+                Trigger::Internal,
+            );
+        }
+    }
+
+    // Returns the index in sessions() of the session the message belongs to
+    pub fn process(&mut self, captured: &CapturedMessage) -> usize {
+        let index = self.session_index(captured);
+        self.expire_timers(index, captured.timestamp);
+        let mut violations = vec![];
+        let speaker = captured.source;
+        let timestamp = captured.timestamp;
+        let message_type = captured.message.message_type();
+
+        let session = &mut self.sessions[index];
+        let to_pcc = captured.destination == session.pcc.address;
+        let (report, peer) = session.speaker_mut(speaker);
+
+        if let PcepMessage::Open(ref open) = captured.message {
+            let sid = open.open_object().sid;
+            let key = (*speaker.ip(), *captured.destination.ip());
+            // Only the first Open of a session is compared with the previous
+            // session, an Open re-sent after PCErr(1,4) keeps its SID
+            let previous = self.last_sid.insert(key, sid);
+            if report.open.is_none() && previous == Some(sid) {
+                violations.push(Violation::DuplicateSessionId {
+                    timestamp,
+                    speaker,
+                    sid,
+                });
+            }
+        }
+
+        report.first_seen.get_or_insert(timestamp);
+        report.last_seen = Some(timestamp);
+        report.message_count += 1;
+
+        match captured.message {
+            PcepMessage::Open(ref open) => {
+                report.open = Some(open.open_object().into());
+            }
+            PcepMessage::Keepalive(_) => {
+                if let Some(last_keepalive) = report.last_keepalive {
+                    report
+                        .keepalive_intervals
+                        .push(timestamp.saturating_sub(last_keepalive));
+                }
+                report.last_keepalive = Some(timestamp);
+            }
+            PcepMessage::PcUpd(_)
+                if !peer.open.map(|o| o.lsp_update_capability).unwrap_or(false) =>
+            {
+                violations.push(Violation::CapabilityMismatch {
+                    timestamp,
+                    speaker,
+                    message_type,
+                    capability: "LSP-UPDATE-CAPABILITY",
+                });
+            }
+            PcepMessage::PcInitiate(_)
+                if !peer
+                    .open
+                    .map(|o| o.lsp_instantiate_capability)
+                    .unwrap_or(false) =>
+            {
+                violations.push(Violation::CapabilityMismatch {
+                    timestamp,
+                    speaker,
+                    message_type,
+                    capability: "LSP-INSTANTIATION-CAPABILITY",
+                });
+            }
+            PcepMessage::PcClose(_) => {
+                session.closed = true;
+            }
+            _ => {}
+        }
+        session.violations.extend(violations);
+
+        // A PCErr(1,4) or (1,5) is the speaker's verdict on the peer's Open
+        if let PcepMessage::PcErr(ref pcerr) = captured.message {
+            if pcerr.error_objects.iter().any(|error_object| {
+                error_object.error_type == 1
+                    && (error_object.error_value == 4 || error_object.error_value == 5)
+            }) {
+                self.feed(
+                    index,
+                    !to_pcc,
+                    timestamp,
+                    SessionEvent::OpenRejected,
+                    Trigger::OpenRejected,
+                );
+            }
+        }
+        let event = match captured.message {
+            PcepMessage::Open(ref open) => SessionEvent::Open(open),
+            PcepMessage::Keepalive(ref keepalive) => SessionEvent::Keepalive(keepalive),
+            PcepMessage::PcErr(ref pcerr) => SessionEvent::PcErr(pcerr),
+            PcepMessage::PcClose(ref close) => SessionEvent::Close(close),
+            _ => SessionEvent::MessageReceived,
+        };
+        self.feed(
+            index,
+            to_pcc,
+            timestamp,
+            event,
+            Trigger::Message(message_type),
+        );
+        index
+    }
+
+    pub fn sessions(&self) -> &[SessionReport] {
+        &self.sessions
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::builder::{ipv4_hop, LspBuilder};
+    use crate::encoder::Encode;
+    use crate::messages::pcupdate::PcepUpdate;
+
+    fn captured(seconds: u64, from_pcc: bool, input: &[u8]) -> CapturedMessage {
+        let pcc = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 50000);
+        let pce = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 2), PCEP_PORT);
+        let (_remaining, message) =
+            PcepMessage::parse_message(input).expect("[!!] Error while parsing pcep message");
+        let (source, destination) = if from_pcc { (pcc, pce) } else { (pce, pcc) };
+        CapturedMessage {
+            timestamp: Duration::from_secs(seconds),
+            source,
+            destination,
+            message,
+        }
+    }
+
+    const KEEPALIVE: &[u8] = &[0x20, 0x02, 0x00, 0x04];
+    // keepalive 30, deadtimer 120, sid 1, stateful with U flag only
+    const PCC_OPEN: &[u8] = &[
+        0x20, 0x01, 0x00, 0x14, 0x01, 0x10, 0x00, 0x10, 0x20, 0x1e, 0x78, 0x01, 0x00, 0x10, 0x00,
+        0x04, 0x00, 0x00, 0x00, 0x01,
+    ];
+    const PCE_OPEN: &[u8] = &[
+        0x20, 0x01, 0x00, 0x0c, 0x01, 0x10, 0x00, 0x08, 0x20, 0x1e, 0x78, 0x07,
+    ];
+
+    #[test]
+    fn test_session_analyzer_negotiation_and_keepalives() {
+        let mut analyzer = SessionAnalyzer::new();
+        analyzer.process(&captured(0, true, PCC_OPEN));
+        analyzer.process(&captured(0, false, PCE_OPEN));
+        analyzer.process(&captured(1, true, KEEPALIVE));
+        analyzer.process(&captured(1, false, KEEPALIVE));
+        analyzer.process(&captured(31, true, KEEPALIVE));
+        analyzer.process(&captured(62, true, KEEPALIVE));
+        let sessions = analyzer.sessions();
+        assert_eq!(sessions.len(), 1);
+        let session = &sessions[0];
+        assert!(session.violations.is_empty());
+        let (pcc_open, pce_open) = session.negotiated().expect("[!!] Opens missing");
+        assert_eq!(pcc_open.sid, 1);
+        assert!(pcc_open.lsp_update_capability);
+        assert_eq!(pce_open.sid, 7);
+        assert_eq!(
+            session.pcc.keepalive_intervals,
+            vec![Duration::from_secs(30), Duration::from_secs(31)]
+        );
+    }
+
+    #[test]
+    fn test_session_analyzer_violations() {
+        let pcep_update = PcepMessage::PcUpd(
+            PcepUpdate::builder()
+                .lsp(LspBuilder::new().plsp_id(1).delegate(true))
+                .ero(vec![ipv4_hop(Ipv4Addr::new(10, 0, 0, 3))])
+                .build(),
+        );
+        let mut analyzer = SessionAnalyzer::new();
+        analyzer.process(&captured(0, false, &pcep_update.to_bytes()));
+        let session = &analyzer.sessions()[0];
+        assert_eq!(session.violations.len(), 2);
+        assert!(matches!(
+            session.violations[0],
+            Violation::CapabilityMismatch {
+                message_type: MessageType::PCUpd,
+                ..
+            }
+        ));
+        assert!(matches!(
+            session.violations[1],
+            Violation::MissingOpen {
+                message_type: MessageType::PCUpd,
+                ..
+            }
+        ));
+        assert_eq!(
+            session.pcc.states.last(),
+            Some(&(Duration::ZERO, SessionState::Idle))
+        );
+    }
+
+    #[test]
+    fn test_session_analyzer_deadtimer_expiry() {
+        let mut analyzer = SessionAnalyzer::new();
+        analyzer.process(&captured(0, true, PCC_OPEN));
+        analyzer.process(&captured(0, false, PCE_OPEN));
+        analyzer.process(&captured(1, true, KEEPALIVE));
+        analyzer.process(&captured(1, false, KEEPALIVE));
+        analyzer.process(&captured(100, false, KEEPALIVE));
+        analyzer.process(&captured(190, false, KEEPALIVE));
+        analyzer.process(&captured(200, true, KEEPALIVE));
+        let session = &analyzer.sessions()[0];
+        assert_eq!(
+            session.violations,
+            vec![Violation::DeadTimerExpired {
+                timestamp: Duration::from_secs(121),
+                speaker: session.pcc.address,
+                silence: Duration::from_secs(120),
+                deadtimer: 120,
+            }]
+        );
+        assert_eq!(
+            session
+                .pce
+                .states
+                .iter()
+                .map(|(_timestamp, state)| *state)
+                .collect::<Vec<_>>(),
+            vec![
+                SessionState::OpenWait,
+                SessionState::KeepWait,
+                SessionState::Up,
+                SessionState::Idle
+            ]
+        );
+    }
+
+    #[test]
+    fn test_session_analyzer_duplicate_sid() {
+        let close: &[u8] = &[
+            0x20, 0x07, 0x00, 0x0c, 0x0f, 0x10, 0x00, 0x08, 0x00, 0x00, 0x00, 0x01,
+        ];
+        let mut analyzer = SessionAnalyzer::new();
+        assert_eq!(analyzer.process(&captured(0, true, PCC_OPEN)), 0);
+        assert_eq!(analyzer.process(&captured(5, true, close)), 0);
+        assert_eq!(analyzer.process(&captured(10, true, PCC_OPEN)), 1);
+        assert_eq!(analyzer.sessions().len(), 2);
+        assert!(matches!(
+            analyzer.sessions()[1].violations[0],
+            Violation::DuplicateSessionId { sid: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn test_session_analyzer_reopen_after_pcerr_keeps_sid() {
+        // PCErr(1,4), unacceptable but negotiable session characteristics,
+        // proposing keepalive 10 and deadtimer 40
+        let pcerr: &[u8] = &[
+            0x20, 0x06, 0x00, 0x14, 0x0d, 0x10, 0x00, 0x08, 0x00, 0x00, 0x01, 0x04, 0x01, 0x10,
+            0x00, 0x08, 0x20, 0x0a, 0x28, 0x07,
+        ];
+        let mut analyzer = SessionAnalyzer::new();
+        analyzer.process(&captured(0, true, PCC_OPEN));
+        analyzer.process(&captured(0, false, pcerr));
+        analyzer.process(&captured(1, true, PCC_OPEN));
+        analyzer.process(&captured(1, false, PCE_OPEN));
+        analyzer.process(&captured(2, true, KEEPALIVE));
+        analyzer.process(&captured(2, false, KEEPALIVE));
+        assert_eq!(analyzer.sessions().len(), 1);
+        let session = &analyzer.sessions()[0];
+        assert!(session.violations.is_empty());
+        assert_eq!(
+            session.pcc.states.last(),
+            Some(&(Duration::from_secs(2), SessionState::Up))
+        );
+        assert_eq!(
+            session.pce.states.last(),
+            Some(&(Duration::from_secs(2), SessionState::Up))
+        );
+        assert!(
+            analyzer.sessions()[0]
+                .violations
+                .iter()
+                .all(|violation| !matches!(violation, Violation::DuplicateSessionId { .. })),
+            "[!!] Re-sent Open reported as a duplicate SID"
+        );
+    }
+}
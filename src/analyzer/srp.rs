@@ -0,0 +1,364 @@
+use colored::Colorize;
+use indoc::writedoc;
+
+use crate::common::OperationalStatus;
+use crate::messages::message::PcepMessage;
+use crate::messages::pcerr::PcErr;
+use crate::messages::pcinitiate::{PCInitiate, PceInitiatedLspRequest};
+use crate::messages::pcrpt::PcRpt;
+use crate::messages::pcupdate::PcepUpdate;
+use crate::messages::types::MessageType;
+use crate::objects::srp::SrpObject;
+use crate::tlvs::tlv_set::LspErrorCode;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+// A PCUpd or PCInitiate request waiting for its PCRpt
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PendingRequest {
+    pub srp_id: u32,
+    pub message_type: MessageType,
+    pub plsp_id: u32,
+    pub symbolic_name: Option<String>,
+    pub sent: Duration,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RequestOutcome {
+    Reported(OperationalStatus),
+    LspError(LspErrorCode),
+    PcErr { error_type: u8, error_value: u8 },
+}
+
+impl RequestOutcome {
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, Self::Reported(_))
+    }
+}
+
+impl std::fmt::Display for RequestOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Reported(operational_status) => write!(f, "{}", operational_status),
+            Self::LspError(error_code) => write!(f, "{}", error_code),
+            Self::PcErr {
+                error_type,
+                error_value,
+            } => write!(f, "PCErr type={} value={}", error_type, error_value),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CorrelatedRequest {
+    pub request: PendingRequest,
+    pub plsp_id: u32,
+    pub acknowledged: Duration,
+    pub outcome: RequestOutcome,
+}
+
+impl CorrelatedRequest {
+    pub fn latency(&self) -> Duration {
+        self.acknowledged.saturating_sub(self.request.sent)
+    }
+}
+
+impl std::fmt::Display for CorrelatedRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "srp_id={} {} plsp_id={} latency={:.3}s outcome={}",
+            self.request.srp_id,
+            self.request.message_type,
+            self.plsp_id,
+            self.latency().as_secs_f64(),
+            self.outcome
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub min: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+// Matches SRP-IDs of PCE requests with the PCC reports acknowledging them
+#[derive(Debug, Default)]
+pub struct SrpCorrelator {
+    pending: BTreeMap<u32, PendingRequest>,
+    acknowledged: BTreeSet<u32>,
+    completed: Vec<CorrelatedRequest>,
+    unexpected: Vec<(Duration, u32)>,
+}
+
+impl SrpCorrelator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn add_pending(&mut self, request: PendingRequest) {
+        if SrpObject::is_reserved_srp_id(request.srp_id) {
+            return;
+        }
+        self.acknowledged.remove(&request.srp_id);
+        self.pending.insert(request.srp_id, request);
+    }
+
+    fn acknowledge(
+        &mut self,
+        timestamp: Duration,
+        srp_id: u32,
+        plsp_id: u32,
+        outcome: RequestOutcome,
+    ) {
+        if SrpObject::is_reserved_srp_id(srp_id) {
+            return;
+        }
+        match self.pending.remove(&srp_id) {
+            Some(request) => {
+                self.acknowledged.insert(srp_id);
+                self.completed.push(CorrelatedRequest {
+                    plsp_id: if plsp_id != 0 {
+                        plsp_id
+                    } else {
+                        request.plsp_id
+                    },
+                    request,
+                    acknowledged: timestamp,
+                    outcome,
+                });
+            }
+            // Further reports for an already acknowledged request are expected
+            None if self.acknowledged.contains(&srp_id) => {}
+            None => self.unexpected.push((timestamp, srp_id)),
+        }
+    }
+
+    fn handle_update(&mut self, timestamp: Duration, update_message: &PcepUpdate) {
+        for update_request in update_message.update_requests() {
+            self.add_pending(PendingRequest {
+                srp_id: update_request.srp_object().srp_id,
+                message_type: MessageType::PCUpd,
+                plsp_id: update_request.lsp_object().plsp_id,
+                symbolic_name: update_request
+                    .lsp_object()
+                    .symbolic_path_name()
+                    .map(String::from),
+                sent: timestamp,
+            });
+        }
+    }
+
+    fn handle_initiate(&mut self, timestamp: Duration, initiate_message: &PCInitiate) {
+        for lsp_request in initiate_message.lsp_requests() {
+            let (srp_object, lsp_object) = match lsp_request {
+                PceInitiatedLspRequest::LspInstantiation(instantiation) => {
+                    (instantiation.srp_object(), instantiation.lsp_object())
+                }
+                PceInitiatedLspRequest::LspDeletion(deletion) => {
+                    (deletion.srp_object(), deletion.lsp_object())
+                }
+            };
+            self.add_pending(PendingRequest {
+                srp_id: srp_object.srp_id,
+                message_type: MessageType::PCInitiate,
+                plsp_id: lsp_object.plsp_id,
+                symbolic_name: lsp_object.symbolic_path_name().map(String::from),
+                sent: timestamp,
+            });
+        }
+    }
+
+    fn handle_report(&mut self, timestamp: Duration, report_message: &PcRpt) {
+        for state_report in report_message.state_reports() {
+            if let Some(srp_object) = state_report.srp_object() {
+                let lsp_object = state_report.lsp_object();
+                let outcome = match lsp_object.lsp_error_code() {
+                    Some(error_code) => RequestOutcome::LspError(error_code),
+                    None => RequestOutcome::Reported(lsp_object.operational_status),
+                };
+                self.acknowledge(timestamp, srp_object.srp_id, lsp_object.plsp_id, outcome);
+            }
+        }
+    }
+
+    fn handle_error(&mut self, timestamp: Duration, error_message: &PcErr) {
+        let outcome = match error_message.error_objects.first() {
+            Some(error_object) => RequestOutcome::PcErr {
+                error_type: error_object.error_type,
+                error_value: error_object.error_value,
+            },
+            None => return,
+        };
+        for srp_object in error_message.srp_objects.iter() {
+            self.acknowledge(timestamp, srp_object.srp_id, 0, outcome);
+        }
+    }
+
+    pub fn process(&mut self, timestamp: Duration, message: &PcepMessage) {
+        match message {
+            PcepMessage::PcUpd(update_message) => self.handle_update(timestamp, update_message),
+            PcepMessage::PcInitiate(initiate_message) => {
+                self.handle_initiate(timestamp, initiate_message)
+            }
+            PcepMessage::PcRpt(report_message) => self.handle_report(timestamp, report_message),
+            PcepMessage::PcErr(error_message) => self.handle_error(timestamp, error_message),
+            _ => {}
+        }
+    }
+
+    pub fn completed(&self) -> &[CorrelatedRequest] {
+        &self.completed
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &CorrelatedRequest> {
+        self.completed.iter().filter(|c| c.outcome.is_failure())
+    }
+
+    pub fn unacknowledged(&self) -> impl Iterator<Item = &PendingRequest> {
+        self.pending.values()
+    }
+
+    // Reports carrying an SRP-ID that no request used
+    pub fn unexpected(&self) -> &[(Duration, u32)] {
+        &self.unexpected
+    }
+
+    pub fn latency_summary(&self) -> Option<LatencySummary> {
+        if self.completed.is_empty() {
+            return None;
+        }
+        let latencies: Vec<Duration> = self.completed.iter().map(|c| c.latency()).collect();
+        let total: Duration = latencies.iter().sum();
+        Some(LatencySummary {
+            count: latencies.len(),
+            min: latencies.iter().min().copied().unwrap_or_default(),
+            mean: total / latencies.len() as u32,
+            max: latencies.iter().max().copied().unwrap_or_default(),
+        })
+    }
+}
+
+impl std::fmt::Display for SrpCorrelator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut completed_str = String::new();
+        for correlated in self.completed.iter() {
+            completed_str.push_str(&format!("        {}\n", correlated));
+        }
+        let mut unacknowledged_str = String::new();
+        for request in self.pending.values() {
+            unacknowledged_str.push_str(&format!(
+                "        srp_id={} {} plsp_id={} sent={:.6}\n",
+                request.srp_id,
+                request.message_type,
+                request.plsp_id,
+                request.sent.as_secs_f64()
+            ));
+        }
+        let latency_str = match self.latency_summary() {
+            Some(summary) => format!(
+                "min {:.3}s / mean {:.3}s / max {:.3}s over {} request(s)",
+                summary.min.as_secs_f64(),
+                summary.mean.as_secs_f64(),
+                summary.max.as_secs_f64(),
+                summary.count
+            ),
+            None => String::from("-"),
+        };
+        let title = "==[SRP Correlation]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                latency                      = {latency_str}
+                failures                     = {failures}
+                unexpected acknowledgements  = {unexpected}
+                {completed_title}
+            {completed_str}    {unacknowledged_title}
+            {unacknowledged_str}"#,
+            title = title,
+            latency_str = latency_str,
+            failures = self.failures().count(),
+            unexpected = self.unexpected.len(),
+            completed_title = "[[acknowledged]]".bold(),
+            completed_str = completed_str,
+            unacknowledged_title = "[[unacknowledged]]".bold(),
+            unacknowledged_str = unacknowledged_str
+        )
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn parse(input: &[u8]) -> PcepMessage {
+        let (_remaining, message) =
+            PcepMessage::parse_message(input).expect("[!!] Error while parsing pcep message");
+        message
+    }
+
+    fn update(srp_id: u8) -> PcepMessage {
+        parse(&[
+            0x20, 0x0b, 0x00, 0x1c, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, srp_id, 0x20, 0x10, 0x00, 0x08, 0x00, 0x0e, 0xb0, 0x09, 0x07, 0x10, 0x00, 0x04,
+        ])
+    }
+
+    fn report(srp_id: u8) -> PcepMessage {
+        parse(&[
+            0x20, 0x0a, 0x00, 0x1c, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, srp_id, 0x20, 0x10, 0x00, 0x08, 0x00, 0x0e, 0xb0, 0x19, 0x07, 0x10, 0x00, 0x04,
+        ])
+    }
+
+    #[test]
+    fn test_srp_correlation() {
+        let report_with_error: &[u8] = &[
+            0x20, 0x0a, 0x00, 0x24, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x07, 0x20, 0x10, 0x00, 0x10, 0x00, 0x0e, 0xb0, 0x09, 0x00, 0x14, 0x00, 0x04,
+            0x00, 0x00, 0x00, 0x04, 0x07, 0x10, 0x00, 0x04,
+        ];
+        let initiate: &[u8] = &[
+            0x20, 0x0c, 0x00, 0x24, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x06, 0x20, 0x10, 0x00, 0x10, 0x00, 0x00, 0x00, 0x09, 0x00, 0x11, 0x00, 0x04,
+            0x6c, 0x73, 0x70, 0x32, 0x07, 0x10, 0x00, 0x04,
+        ];
+        let mut correlator = SrpCorrelator::new();
+        assert_eq!(correlator.latency_summary(), None);
+        correlator.process(Duration::from_millis(1000), &update(5));
+        correlator.process(Duration::from_millis(1500), &report(5));
+        correlator.process(Duration::from_millis(1600), &report(5));
+        correlator.process(Duration::from_millis(2000), &parse(initiate));
+        correlator.process(Duration::from_millis(3000), &update(7));
+        correlator.process(Duration::from_millis(3250), &parse(report_with_error));
+        correlator.process(Duration::from_millis(4000), &report(9));
+
+        assert_eq!(correlator.completed().len(), 2);
+        assert_eq!(
+            correlator.completed()[0].latency(),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            correlator.completed()[0].outcome,
+            RequestOutcome::Reported(OperationalStatus::Up)
+        );
+        let failures: Vec<&CorrelatedRequest> = correlator.failures().collect();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(
+            failures[0].outcome,
+            RequestOutcome::LspError(LspErrorCode::UnacceptableParameters)
+        );
+        let unacknowledged: Vec<&PendingRequest> = correlator.unacknowledged().collect();
+        assert_eq!(unacknowledged.len(), 1);
+        assert_eq!(unacknowledged[0].symbolic_name.as_deref(), Some("lsp2"));
+        assert_eq!(correlator.unexpected(), &[(Duration::from_millis(4000), 9)]);
+        let summary = correlator.latency_summary().expect("[!!] No latency");
+        assert_eq!(summary.min, Duration::from_millis(250));
+        assert_eq!(summary.max, Duration::from_millis(500));
+    }
+}
@@ -0,0 +1,89 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::net::TcpStream;
+use std::time::Duration;
+
+use pcep_parser::messages::message::PcepMessage;
+use pcep_parser::session::types::SessionConfig;
+use pcep_parser::sim::pcc::{LspConfig, PccSimulator};
+use pcep_parser::sim::transport::PcepSession;
+
+const USAGE: &str = "Usage: pcep-pcc-sim [--pce <address:port>] [--inventory <file>] \
+                     [--keepalive <secs>] [--deadtimer <secs>] [--msd <depth>]";
+
+fn value<T: std::str::FromStr>(args: &mut impl Iterator<Item = String>, flag: &str) -> T {
+    match args.next().map(|v| v.parse::<T>()) {
+        Some(Ok(value)) => value,
+        _ => {
+            eprintln!("[!!] Missing or invalid value for {}", flag);
+            eprintln!("{}", USAGE);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut pce = String::from("127.0.0.1:4189");
+    let mut inventory = vec![];
+    let mut config = SessionConfig::default();
+    let mut max_sid_depth = 10u8;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--pce" => pce = value(&mut args, "--pce"),
+            "--inventory" => {
+                let path: String = value(&mut args, "--inventory");
+                inventory = LspConfig::parse_inventory(&fs::read_to_string(path)?)?;
+            }
+            "--keepalive" => config.keepalive = value(&mut args, "--keepalive"),
+            "--deadtimer" => config.deadtimer = value(&mut args, "--deadtimer"),
+            "--msd" => max_sid_depth = value(&mut args, "--msd"),
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let mut simulator = PccSimulator::new(inventory, max_sid_depth);
+    let open_object = simulator.open_object(config.keepalive, config.deadtimer, config.sid);
+    let stream = TcpStream::connect(&pce)?;
+    let mut session = PcepSession::establish(stream, config, open_object)?;
+    println!("[+] Session up with {}", pce);
+
+    for message in simulator.synchronization() {
+        session.send(&message)?;
+    }
+    println!(
+        "[+] Reported {} LSP(s), synchronization done",
+        simulator.lsps().count()
+    );
+
+    loop {
+        let message = match session.poll(Duration::from_secs(1)) {
+            Ok(Some(message)) => message,
+            Ok(None) => continue,
+            Err(e) => {
+                println!("[+] Session down: {}", e);
+                break;
+            }
+        };
+        println!("[+] Received {}", message.message_type());
+        if let PcepMessage::PcClose(_) = message {
+            continue;
+        }
+        for reply in simulator.handle(&message) {
+            println!("[+] Sending {}", reply.message_type());
+            session.send(&reply)?;
+        }
+    }
+    for lsp in simulator.lsps() {
+        println!(
+            "    plsp_id = {:<6} lsp_id = {:<6} name = {}",
+            lsp.plsp_id, lsp.lsp_id, lsp.name
+        );
+    }
+    Ok(())
+}
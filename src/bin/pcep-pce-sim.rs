@@ -0,0 +1,184 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::net::TcpListener;
+use std::time::{Duration, Instant};
+
+use pcep_parser::messages::message::PcepMessage;
+use pcep_parser::session::types::SessionConfig;
+use pcep_parser::sim::pce::{PceSimulator, Script, Step};
+use pcep_parser::sim::transport::PcepSession;
+
+const USAGE: &str =
+    "Usage: pcep-pce-sim [--listen <address:port>] [--script <file.yaml|file.json>] \
+                     [--keepalive <secs>] [--deadtimer <secs>] [--msd <depth>]";
+
+fn value<T: std::str::FromStr>(args: &mut impl Iterator<Item = String>, flag: &str) -> T {
+    match args.next().map(|v| v.parse::<T>()) {
+        Some(Ok(value)) => value,
+        _ => {
+            eprintln!("[!!] Missing or invalid value for {}", flag);
+            eprintln!("{}", USAGE);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn load_script(path: &str) -> Result<Script, Box<dyn Error>> {
+    let contents = fs::read_to_string(path)?;
+    let script = if path.ends_with(".json") {
+        Script::from_json(&contents)?
+    } else {
+        Script::from_yaml(&contents)?
+    };
+    Ok(script)
+}
+
+struct Runner {
+    session: PcepSession,
+    simulator: PceSimulator,
+    started: Instant,
+}
+
+impl Runner {
+    // Feed PCC messages to the simulator until done returns a verdict
+    // or the deadline passes
+    fn wait_for<F>(
+        &mut self,
+        deadline: Instant,
+        mut done: F,
+    ) -> Result<Option<Result<(), String>>, Box<dyn Error>>
+    where
+        F: FnMut(&PceSimulator, Option<&PcepMessage>) -> Option<Result<(), String>>,
+    {
+        if let Some(verdict) = done(&self.simulator, None) {
+            return Ok(Some(verdict));
+        }
+        while Instant::now() < deadline {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let message = match self.session.poll(timeout)? {
+                Some(message) => message,
+                None => continue,
+            };
+            self.simulator.process(self.started.elapsed(), &message);
+            if let Some(verdict) = done(&self.simulator, Some(&message)) {
+                return Ok(Some(verdict));
+            }
+        }
+        Ok(None)
+    }
+
+    fn run_step(
+        &mut self,
+        step: &Step,
+        timeout: Duration,
+    ) -> Result<Result<(), String>, Box<dyn Error>> {
+        let deadline = Instant::now() + timeout;
+        let verdict = match step {
+            Step::WaitSync => self
+                .wait_for(deadline, |simulator, _message| {
+                    simulator.is_synchronized().then_some(Ok(()))
+                })?
+                .unwrap_or_else(|| Err("PCC did not complete synchronization".to_string())),
+            Step::Sleep { seconds } => {
+                let deadline = Instant::now() + Duration::from_secs(*seconds);
+                self.wait_for(deadline, |_simulator, _message| None)?;
+                Ok(())
+            }
+            _ => {
+                let request = match self.simulator.request(self.started.elapsed(), step) {
+                    Ok(Some(request)) => request,
+                    Ok(None) => return Ok(Ok(())),
+                    Err(reason) => return Ok(Err(reason)),
+                };
+                self.session.send(&request)?;
+                self.wait_for(deadline, |simulator, message| {
+                    message.and_then(|message| simulator.check(step, message))
+                })?
+                .unwrap_or_else(|| Err("no answer from the PCC".to_string()))
+            }
+        };
+        Ok(verdict)
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut listen = String::from("0.0.0.0:4189");
+    let mut script = None;
+    let mut config = SessionConfig::default();
+    let mut max_sid_depth = 10u8;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--listen" => listen = value(&mut args, "--listen"),
+            "--script" => {
+                let path: String = value(&mut args, "--script");
+                script = Some(load_script(&path)?);
+            }
+            "--keepalive" => config.keepalive = value(&mut args, "--keepalive"),
+            "--deadtimer" => config.deadtimer = value(&mut args, "--deadtimer"),
+            "--msd" => max_sid_depth = value(&mut args, "--msd"),
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let listener = TcpListener::bind(&listen)?;
+    println!("[+] Listening on {}", listener.local_addr()?);
+    let (stream, peer) = listener.accept()?;
+    let open_object = PceSimulator::open_object(
+        config.keepalive,
+        config.deadtimer,
+        config.sid,
+        max_sid_depth,
+    );
+    let session = PcepSession::establish(stream, config, open_object)?;
+    println!("[+] Session up with {}", peer);
+    let mut runner = Runner {
+        session,
+        simulator: PceSimulator::new(),
+        started: Instant::now(),
+    };
+
+    let script = match script {
+        Some(script) => script,
+        None => {
+            // Without a script, keep collecting reports until the PCC leaves
+            if let Err(e) = runner.wait_for(
+                Instant::now() + Duration::from_secs(u32::MAX as u64),
+                |_s, _m| None,
+            ) {
+                println!("[+] Session down: {}", e);
+            }
+            for lsp in runner.simulator.lspdb().lsps() {
+                println!("{}", lsp);
+            }
+            return Ok(());
+        }
+    };
+
+    let mut failures = 0;
+    for step in script.steps.iter() {
+        match runner.run_step(step, Duration::from_secs(script.timeout))? {
+            Ok(()) => println!("[PASS] {}", step),
+            Err(reason) => {
+                failures += 1;
+                println!("[FAIL] {}: {}", step, reason);
+            }
+        }
+    }
+    println!(
+        "[+] {} step(s), {} failure(s), {} LSP(s) in the LSP database",
+        script.steps.len(),
+        failures,
+        runner.simulator.lspdb().len()
+    );
+    runner.session.close()?;
+    if failures > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+use std::time::Duration;
+
+use pcep_parser::analyzer::session::SessionAnalyzer;
+use pcep_parser::analyzer::srp::SrpCorrelator;
+use pcep_parser::capture::pcap::PcapReader;
+use pcep_parser::capture::reassembly::Reassembler;
+use pcep_parser::lspdb::sync::{SyncPhase, SyncTracker};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("Usage: pcep-session-analyzer <capture.pcap>");
+            std::process::exit(1);
+        }
+    };
+    let mut f = File::open(&path)?;
+    let mut contents: Vec<u8> = vec![];
+    f.read_to_end(&mut contents)?;
+
+    let (truncated, segments) = match PcapReader::read_tcp_segments(&contents) {
+        Ok((remaining, segments)) => (remaining.len(), segments),
+        Err(_e) => {
+            eprintln!("[!] {} is not a classic pcap capture", path);
+            std::process::exit(1);
+        }
+    };
+
+    let mut reassembler = Reassembler::new();
+    let mut analyzer = SessionAnalyzer::new();
+    // SRP-IDs restart with every session, requests are correlated per session
+    let mut correlators: BTreeMap<usize, SrpCorrelator> = BTreeMap::new();
+    // State synchronization of each session, with its last message timestamp
+    let mut trackers: BTreeMap<usize, (SyncTracker, Duration)> = BTreeMap::new();
+    for segment in segments.iter() {
+        for captured in reassembler.push(segment) {
+            let index = analyzer.process(&captured);
+            let from_pcc = captured.source == analyzer.sessions()[index].pcc.address;
+            let (tracker, last_seen) = trackers
+                .entry(index)
+                .or_insert_with(|| (SyncTracker::new(), captured.timestamp));
+            tracker.process(captured.timestamp, from_pcc, &captured.message);
+            *last_seen = captured.timestamp;
+            correlators
+                .entry(index)
+                .or_default()
+                .process(captured.timestamp, &captured.message);
+        }
+    }
+
+    for (index, session) in analyzer.sessions().iter().enumerate() {
+        println!("{}", session);
+        if let Some((tracker, last_seen)) = trackers.get_mut(&index) {
+            tracker.finish(*last_seen);
+            if tracker.phase() != SyncPhase::NotStarted || !tracker.issues().is_empty() {
+                println!("{}", tracker);
+            }
+        }
+    }
+    for (index, correlator) in correlators.iter() {
+        if !correlator.completed().is_empty() || correlator.unacknowledged().count() > 0 {
+            let session = &analyzer.sessions()[*index];
+            println!(
+                "[+] session {}: {} <-> {}",
+                index, session.pcc.address, session.pce.address
+            );
+            println!("{}", correlator);
+        }
+    }
+    if !reassembler.undecodable().is_empty() {
+        println!(
+            "[!] {} undecodable message(s) skipped",
+            reassembler.undecodable().len()
+        );
+    }
+    if truncated > 0 {
+        eprintln!(
+            "[!] Capture truncated, last {} byte(s) could not be read",
+            truncated
+        );
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::borrowed::{Items, ObjectIter, ObjectView};
+use crate::error::{self, ParseError};
+use crate::messages::header::CommonHeader;
+use crate::messages::types::MessageType;
+
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::{Err, IResult};
+
+// A PCEP message borrowed from the input, with its objects walked lazily
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MessageView<'a> {
+    flags: u8,
+    message_type: MessageType,
+    body: &'a [u8],
+}
+
+impl<'a> MessageView<'a> {
+    // Same framing as PcepMessage::parse_message: Err::Incomplete carries
+    // the bytes still missing from the header or the message_length
+    pub fn parse(input: &'a [u8]) -> IResult<&'a [u8], Self> {
+        let (remaining, common_header) = CommonHeader::parse_common_header(input)?;
+        if common_header.message_length < 4 {
+            return Err(Err::Failure(Error::new(input, ErrorKind::LengthValue)));
+        }
+        let body_len = common_header.message_length as usize - 4;
+        let (remaining, body) = bytes::streaming::take(body_len)(remaining)?;
+        let message = MessageView {
+            flags: common_header.flags,
+            message_type: common_header.message_type,
+            body,
+        };
+        Ok((remaining, message))
+    }
+
+    pub fn parse_complete(input: &'a [u8]) -> Result<(&'a [u8], Self), ParseError> {
+        error::parse_complete(input, Self::parse)
+    }
+
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    pub fn message_type(&self) -> MessageType {
+        self.message_type
+    }
+
+    pub fn body(&self) -> &'a [u8] {
+        self.body
+    }
+
+    // Objects of the message body in wire order, error offsets are
+    // relative to the start of the body
+    pub fn objects(&self) -> ObjectIter<'a> {
+        Items::new(self.body, ObjectView::parse)
+    }
+}
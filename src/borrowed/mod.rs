@@ -0,0 +1,194 @@
+// Borrowed, allocation free views over PCEP messages. Each view keeps
+// a slice of the input and decodes fields on demand; objects, TLVs and
+// subobjects are walked lazily with the iterators below.
+pub mod message;
+pub mod object;
+pub mod tlv;
+
+use crate::error::{self, ParseError};
+use nom::IResult;
+
+pub use message::MessageView;
+pub use object::{ObjectView, SubObjectView};
+pub use tlv::TlvView;
+
+pub type ObjectIter<'a> = Items<'a, ObjectView<'a>>;
+pub type TlvIter<'a> = Items<'a, TlvView<'a>>;
+pub type SubObjectIter<'a> = Items<'a, SubObjectView<'a>>;
+
+// Items packed back to back in a complete slice. Yields an error,
+// with its offset in that slice, and stops at the first item that
+// does not parse.
+#[derive(Debug, Clone)]
+pub struct Items<'a, T> {
+    base: &'a [u8],
+    left: &'a [u8],
+    pending: Option<ParseError>,
+    parse: fn(&'a [u8]) -> IResult<&'a [u8], T>,
+}
+
+impl<'a, T> Items<'a, T> {
+    pub(crate) fn new(base: &'a [u8], parse: fn(&'a [u8]) -> IResult<&'a [u8], T>) -> Self {
+        Items {
+            base,
+            left: base,
+            pending: None,
+            parse,
+        }
+    }
+
+    // Nothing to iterate, only the error to report
+    fn failed(error: ParseError, parse: fn(&'a [u8]) -> IResult<&'a [u8], T>) -> Self {
+        Items {
+            base: &[],
+            left: &[],
+            pending: Some(error),
+            parse,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Items<'a, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.pending.take() {
+            return Some(Err(error));
+        }
+        if self.left.is_empty() {
+            return None;
+        }
+        match error::complete(self.left, self.parse) {
+            Ok((remaining, item)) => {
+                self.left = remaining;
+                Some(Ok(item))
+            }
+            Err(e) => {
+                let offset = self.base.len() - self.left.len();
+                let mut error = ParseError::from_nom(self.left, e);
+                error.offset += offset;
+                self.left = &[];
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::builder::{ipv4_hop, LspBuilder};
+    use crate::common::{IntendedAttrList, IntendedPathList, OperationalStatus};
+    use crate::encoder::Encode;
+    use crate::messages::message::PcepMessage;
+    use crate::messages::pcrpt::{PcRpt, StateReport};
+    use crate::messages::pcupdate::Path;
+    use crate::objects::ero::EroObject;
+    use std::net::Ipv4Addr;
+
+    fn report(lsp: LspBuilder, hops: Vec<Ipv4Addr>) -> PcepMessage {
+        let ero_object = EroObject::new(hops.into_iter().map(ipv4_hop).collect());
+        PcepMessage::PcRpt(PcRpt::new(vec![StateReport::new(
+            None,
+            lsp.build(),
+            Some(Path::new(
+                IntendedPathList {
+                    paths: vec![(None, ero_object)],
+                },
+                IntendedAttrList::new(None, None, vec![]),
+            )),
+        )]))
+    }
+
+    // State synchronization of two LSPs, closed by the end-of-sync marker
+    fn report_bytes() -> Vec<u8> {
+        let synchronized = |plsp_id: u32, name: &str| {
+            LspBuilder::new()
+                .plsp_id(plsp_id)
+                .name(name)
+                .operational_status(OperationalStatus::Up)
+                .delegate(true)
+                .sync(true)
+        };
+        let messages = vec![
+            report(
+                synchronized(1, "lsp-a"),
+                vec![Ipv4Addr::new(10, 0, 1, 1), Ipv4Addr::new(10, 0, 1, 2)],
+            ),
+            report(synchronized(2, "lsp-b"), vec![Ipv4Addr::new(10, 0, 2, 1)]),
+            report(LspBuilder::new(), vec![]),
+        ];
+        let mut buf = vec![];
+        for message in messages {
+            message.encode(&mut buf);
+        }
+        buf
+    }
+
+    #[test]
+    fn test_borrowed_matches_owned() {
+        let input = report_bytes();
+        let mut expected_plsp_ids = vec![];
+        let mut left = &input[..];
+        while !left.is_empty() {
+            let (remaining, owned) =
+                PcepMessage::parse_message(left).expect("[!!] Error while parsing pcep message");
+            if let PcepMessage::PcRpt(ref pcrpt) = owned {
+                for report in pcrpt.state_reports() {
+                    expected_plsp_ids.push(report.lsp_object().plsp_id);
+                }
+            }
+            left = remaining;
+        }
+
+        let mut names = vec![];
+        let mut plsp_ids = vec![];
+        let mut hops = vec![];
+        let mut left = &input[..];
+        while !left.is_empty() {
+            let (remaining, view) =
+                MessageView::parse(left).expect("[!!] Error while parsing pcep message");
+            for object in view.objects() {
+                let object = object.expect("[!!] Error while parsing object");
+                if let Some(plsp_id) = object.plsp_id() {
+                    plsp_ids.push(plsp_id);
+                    names.push(object.symbolic_path_name());
+                }
+                for subobject in object.subobjects() {
+                    let subobject = subobject.expect("[!!] Error while parsing subobject");
+                    hops.extend(subobject.ipv4_prefix().map(|(addr, _len)| addr));
+                }
+            }
+            left = remaining;
+        }
+        // The end-of-synchronization marker is an LSP object with PLSP-ID 0
+        assert_eq!(plsp_ids, expected_plsp_ids);
+        assert_eq!(names[..2], [Some("lsp-a"), Some("lsp-b")]);
+        assert_eq!(hops.len(), 3);
+    }
+
+    #[test]
+    fn test_borrowed_truncation() {
+        let input = report_bytes();
+        let message_length = u16::from_be_bytes([input[2], input[3]]) as usize;
+        // Streaming: asks for the rest of the message
+        assert!(matches!(
+            MessageView::parse(&input[..message_length - 1]),
+            Err(nom::Err::Incomplete(_))
+        ));
+        // Complete: an LSP object claiming 16 bytes in an 8 byte body
+        let input: &[u8] = &[
+            0x20, 0x0a, 0x00, 0x0c, 0x20, 0x10, 0x00, 0x10, 0x00, 0x00, 0x10, 0x09,
+        ];
+        let (_remaining, view) =
+            MessageView::parse(input).expect("[!!] Error while parsing pcep message");
+        let mut objects = view.objects();
+        let error = objects
+            .next()
+            .and_then(Result::err)
+            .expect("[!!] Expected an object error");
+        assert!(objects.next().is_none());
+        assert_eq!(error.offset, 8);
+        assert!(error.is_truncated());
+    }
+}
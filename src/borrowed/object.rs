@@ -0,0 +1,178 @@
+use crate::borrowed::{Items, SubObjectIter, TlvIter, TlvView};
+use crate::error::{ParseError, ParseErrorKind};
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::number;
+use nom::{Err, IResult};
+use std::net::Ipv4Addr;
+
+// A PCEP object borrowed from the message body, fields are decoded on
+// demand from the object body (the bytes after the common header)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ObjectView<'a> {
+    object_class_type: ObjectClassType,
+    flag_process: bool,
+    flag_ignore: bool,
+    body: &'a [u8],
+}
+
+impl<'a> ObjectView<'a> {
+    pub fn parse(input: &'a [u8]) -> IResult<&'a [u8], Self> {
+        let (remaining, cobj) = CommonObject::parse_common_object(input)?;
+        let (remaining, body) = bytes::streaming::take(cobj.object_length - 4)(remaining)?;
+        let object = ObjectView {
+            object_class_type: cobj.object_class_type,
+            flag_process: cobj.flag_process,
+            flag_ignore: cobj.flag_ignore,
+            body,
+        };
+        Ok((remaining, object))
+    }
+
+    pub fn object_class_type(&self) -> ObjectClassType {
+        self.object_class_type
+    }
+
+    pub fn flag_process(&self) -> bool {
+        self.flag_process
+    }
+
+    pub fn flag_ignore(&self) -> bool {
+        self.flag_ignore
+    }
+
+    pub fn body(&self) -> &'a [u8] {
+        self.body
+    }
+
+    // Length of the fixed fields ahead of the optional TLVs, None for
+    // objects that carry no TLVs
+    pub(crate) fn tlvs_offset(object_class_type: ObjectClassType) -> Option<usize> {
+        match object_class_type {
+            ObjectClassType::Open(_)
+            | ObjectClassType::NoPath(_)
+            | ObjectClassType::Error(_)
+            | ObjectClassType::Close(_)
+            | ObjectClassType::Of(_)
+            | ObjectClassType::Lsp(_) => Some(4),
+            ObjectClassType::Rp(_) | ObjectClassType::Srp(_) | ObjectClassType::PathAttrib(_) => {
+                Some(8)
+            }
+            ObjectClassType::Lspa(_) => Some(16),
+            _ => None,
+        }
+    }
+
+    // TLVs of the object, empty for objects that carry none
+    pub fn tlvs(&self) -> TlvIter<'a> {
+        match Self::tlvs_offset(self.object_class_type) {
+            Some(offset) if offset <= self.body.len() => {
+                Items::new(&self.body[offset..], TlvView::parse)
+            }
+            Some(offset) => Items::failed(
+                ParseError {
+                    offset: self.body.len(),
+                    kind: ParseErrorKind::Truncated {
+                        needed: Some(offset - self.body.len()),
+                    },
+                },
+                TlvView::parse,
+            ),
+            None => Items::new(&[], TlvView::parse),
+        }
+    }
+
+    // Subobjects of an ERO, empty for any other object
+    pub fn subobjects(&self) -> SubObjectIter<'a> {
+        match self.object_class_type {
+            ObjectClassType::Ero(_) => Items::new(self.body, SubObjectView::parse),
+            _ => Items::new(&[], SubObjectView::parse),
+        }
+    }
+
+    fn be_u32_at(&self, offset: usize) -> Option<u32> {
+        let bytes = self.body.get(offset..offset + 4)?;
+        Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    // PLSP-ID of an LSP object
+    pub fn plsp_id(&self) -> Option<u32> {
+        match self.object_class_type {
+            ObjectClassType::Lsp(_) => self.be_u32_at(0).map(|word| word >> 12),
+            _ => None,
+        }
+    }
+
+    // SRP-ID-number of an SRP object
+    pub fn srp_id(&self) -> Option<u32> {
+        match self.object_class_type {
+            ObjectClassType::Srp(_) => self.be_u32_at(4),
+            _ => None,
+        }
+    }
+
+    // Name from the SYMBOLIC-PATH-NAME TLV, if present and valid UTF-8
+    pub fn symbolic_path_name(&self) -> Option<&'a str> {
+        self.tlvs()
+            .map_while(Result::ok)
+            .find_map(|tlv| tlv.symbolic_path_name())
+    }
+}
+
+// An ERO subobject borrowed from the object body
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SubObjectView<'a> {
+    flag_l: bool,
+    subobject_type: u8,
+    body: &'a [u8],
+}
+
+impl<'a> SubObjectView<'a> {
+    pub fn parse(input: &'a [u8]) -> IResult<&'a [u8], Self> {
+        let (remaining, l_type) = number::streaming::be_u8(input)?;
+        let (remaining, subobject_len) = number::streaming::be_u8(remaining)?;
+        if subobject_len < 2 {
+            return Err(Err::Failure(Error::new(input, ErrorKind::LengthValue)));
+        }
+        let (remaining, body) = bytes::streaming::take(subobject_len - 2)(remaining)?;
+        let subobject = SubObjectView {
+            flag_l: l_type & 0b1000_0000 != 0,
+            subobject_type: l_type & 0b0111_1111,
+            body,
+        };
+        Ok((remaining, subobject))
+    }
+
+    pub fn flag_l(&self) -> bool {
+        self.flag_l
+    }
+
+    pub fn subobject_type(&self) -> u8 {
+        self.subobject_type
+    }
+
+    pub fn body(&self) -> &'a [u8] {
+        self.body
+    }
+
+    // Address and prefix length of an IPv4 prefix subobject
+    pub fn ipv4_prefix(&self) -> Option<(Ipv4Addr, u8)> {
+        match (self.subobject_type, self.body) {
+            (1, [a, b, c, d, pref_len, ..]) => Some((Ipv4Addr::new(*a, *b, *c, *d), *pref_len)),
+            _ => None,
+        }
+    }
+
+    // SID of an SR-ERO subobject, None when the S flag says it is absent
+    pub fn sr_sid(&self) -> Option<u32> {
+        match (self.subobject_type, self.body) {
+            (36, [_nt, flags, a, b, c, d, ..]) if flags & 0b0000_0100 == 0 => {
+                Some(u32::from_be_bytes([*a, *b, *c, *d]))
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use crate::common::Utils;
+
+use nom::bytes;
+use nom::number;
+use nom::IResult;
+
+// A TLV borrowed from the object body, padding excluded from value
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TlvView<'a> {
+    tlv_type: u16,
+    value: &'a [u8],
+}
+
+impl<'a> TlvView<'a> {
+    pub fn parse(input: &'a [u8]) -> IResult<&'a [u8], Self> {
+        let (remaining, tlv_type) = number::streaming::be_u16(input)?;
+        let (remaining, tlv_len) = number::streaming::be_u16(remaining)?;
+        let (remaining, value) = bytes::streaming::take(tlv_len)(remaining)?;
+        let (remaining, _padding) = Utils::consume_padding(tlv_len as usize, remaining)?;
+        Ok((remaining, TlvView { tlv_type, value }))
+    }
+
+    pub fn tlv_type(&self) -> u16 {
+        self.tlv_type
+    }
+
+    pub fn value(&self) -> &'a [u8] {
+        self.value
+    }
+
+    // SYMBOLIC-PATH-NAME (type 17), if the name is valid UTF-8
+    pub fn symbolic_path_name(&self) -> Option<&'a str> {
+        match self.tlv_type {
+            17 => std::str::from_utf8(self.value).ok(),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,447 @@
+use std::net::Ipv4Addr;
+
+use crate::common::{IntendedAttrList, IntendedPathList, OperationalStatus};
+use crate::encoder::Encode;
+use crate::messages::pcinitiate::{
+    PCInitiate, PceInitiateLspInstatiation, PceInitiatedLspDeletion, PceInitiatedLspRequest,
+};
+use crate::messages::pcupdate::{Path, PcepUpdate, UpdateRequest};
+use crate::objects::bandwidth::BandwidthObject;
+use crate::objects::endpoints::EndPointsObject;
+use crate::objects::ero::EroObject;
+use crate::objects::lsp::LspObject;
+use crate::objects::metric::MetricObject;
+use crate::objects::srp::SrpObject;
+use crate::subobjects::header::SubObject;
+use crate::subobjects::prefix::Ipv4PrefixSubobject;
+use crate::subobjects::sr::{Ipv4AdjNAI, NaiType, SrSubobject};
+use crate::subobjects::types::SubObjectTypes;
+use crate::tlvs::tlv_set::SymbolicPathNameTLV;
+use crate::tlvs::types::Tlv;
+
+// Fluent builders to craft messages, mostly for tests. Every length
+// field is filled in by build(), the result equals what parsing its own
+// encoding gives.
+
+// Strict hop through an IPv4 address
+pub fn ipv4_hop(ipv4_addr: Ipv4Addr) -> SubObject {
+    SubObject::new(
+        false,
+        SubObjectTypes::Ipv4Prefix(Ipv4PrefixSubobject::new(ipv4_addr, 32)),
+    )
+}
+
+// Strict SR hop with an MPLS label as SID
+pub fn sr_hop(label: u32, nai: NaiType) -> SubObject {
+    let sr_subobject = SrSubobject {
+        nai_type: nai,
+        flag_m: true,
+        sid: label << 12,
+        ..Default::default()
+    };
+    SubObject::new(false, SubObjectTypes::Sr(sr_subobject))
+}
+
+// NAI of an adjacency from local_ipv4 to remote_ipv4
+pub fn adj(local_ipv4: Ipv4Addr, remote_ipv4: Ipv4Addr) -> NaiType {
+    NaiType::Ipv4Adj(Ipv4AdjNAI {
+        local_ipv4,
+        remote_ipv4,
+    })
+}
+
+// Length field of an object or message, as encode() writes it
+fn encoded_length(item: &impl Encode) -> u16 {
+    item.to_bytes().len() as u16
+}
+
+#[derive(Debug, Clone)]
+pub struct LspBuilder {
+    plsp_id: u32,
+    operational_status: OperationalStatus,
+    name: Option<String>,
+    flag_delegate: bool,
+    flag_administrative: bool,
+    flag_remove: bool,
+    flag_sync: bool,
+    flag_create: bool,
+    tlvs: Vec<Tlv>,
+}
+
+impl Default for LspBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LspBuilder {
+    pub fn new() -> Self {
+        LspBuilder {
+            plsp_id: 0,
+            operational_status: OperationalStatus::Down,
+            name: None,
+            flag_delegate: false,
+            flag_administrative: false,
+            flag_remove: false,
+            flag_sync: false,
+            flag_create: false,
+            tlvs: vec![],
+        }
+    }
+
+    pub fn plsp_id(mut self, plsp_id: u32) -> Self {
+        self.plsp_id = plsp_id;
+        self
+    }
+
+    pub fn operational_status(mut self, operational_status: OperationalStatus) -> Self {
+        self.operational_status = operational_status;
+        self
+    }
+
+    // SYMBOLIC-PATH-NAME TLV, encoded ahead of the other TLVs
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(String::from(name));
+        self
+    }
+
+    pub fn delegate(mut self, flag_delegate: bool) -> Self {
+        self.flag_delegate = flag_delegate;
+        self
+    }
+
+    pub fn administrative(mut self, flag_administrative: bool) -> Self {
+        self.flag_administrative = flag_administrative;
+        self
+    }
+
+    pub fn remove(mut self, flag_remove: bool) -> Self {
+        self.flag_remove = flag_remove;
+        self
+    }
+
+    pub fn sync(mut self, flag_sync: bool) -> Self {
+        self.flag_sync = flag_sync;
+        self
+    }
+
+    pub fn create(mut self, flag_create: bool) -> Self {
+        self.flag_create = flag_create;
+        self
+    }
+
+    pub fn tlv(mut self, tlv: Tlv) -> Self {
+        self.tlvs.push(tlv);
+        self
+    }
+
+    pub fn build(self) -> LspObject {
+        let mut tlvs = vec![];
+        if let Some(name) = self.name {
+            tlvs.push(Tlv::SymbolicPathName(SymbolicPathNameTLV {
+                tlv_type: 17,
+                tlv_len: name.len() as u16,
+                symbolic_path_name: name,
+            }));
+        }
+        tlvs.extend(self.tlvs);
+        tlvs.iter_mut().for_each(Tlv::set_tlv_len);
+        let mut lsp_object = LspObject::new(self.plsp_id, self.operational_status, tlvs);
+        lsp_object.flag_delegate = self.flag_delegate;
+        lsp_object.flag_administrative = self.flag_administrative;
+        lsp_object.flag_remove = self.flag_remove;
+        lsp_object.flag_sync = self.flag_sync;
+        lsp_object.flag_create = self.flag_create;
+        lsp_object.common_object.object_length = encoded_length(&lsp_object);
+        lsp_object
+    }
+}
+
+// Attributes shared by the PCInitiate and PCUpd paths
+#[derive(Debug, Default)]
+struct PathAttributes {
+    ero: Vec<SubObject>,
+    bandwidth: Option<f32>,
+    metrics: Vec<MetricObject>,
+}
+
+impl PathAttributes {
+    fn intended_path(&mut self) -> IntendedPathList {
+        let mut ero_object = EroObject::new(std::mem::take(&mut self.ero));
+        ero_object.common_object.object_length = encoded_length(&ero_object);
+        IntendedPathList {
+            paths: vec![(None, ero_object)],
+        }
+    }
+
+    fn intended_attr_lst(&mut self) -> IntendedAttrList {
+        let bandwidth_object = self.bandwidth.map(|bandwidth| {
+            let mut bandwidth_object = BandwidthObject::new(bandwidth);
+            bandwidth_object.common_object.object_length = encoded_length(&bandwidth_object);
+            bandwidth_object
+        });
+        let mut metrics = std::mem::take(&mut self.metrics);
+        for metric_object in metrics.iter_mut() {
+            metric_object.common_object_mut().object_length = encoded_length(metric_object);
+        }
+        IntendedAttrList::new(None, bandwidth_object, metrics)
+    }
+}
+
+fn srp_object(srp_id: u32, flag_remove: bool) -> SrpObject {
+    let mut srp_object = SrpObject::new(srp_id, flag_remove);
+    srp_object.common_object.object_length = encoded_length(&srp_object);
+    srp_object
+}
+
+// A single LSP instantiation, or a deletion with remove()
+#[derive(Debug)]
+pub struct PCInitiateBuilder {
+    srp_id: u32,
+    flag_remove: bool,
+    lsp: LspBuilder,
+    endpoints: Option<(Ipv4Addr, Ipv4Addr)>,
+    path: PathAttributes,
+}
+
+impl Default for PCInitiateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PCInitiateBuilder {
+    // SRP-ID 1, as 0 is reserved
+    pub fn new() -> Self {
+        PCInitiateBuilder {
+            srp_id: 1,
+            flag_remove: false,
+            lsp: LspBuilder::new(),
+            endpoints: None,
+            path: Default::default(),
+        }
+    }
+
+    pub fn srp_id(mut self, srp_id: u32) -> Self {
+        self.srp_id = srp_id;
+        self
+    }
+
+    // SRP R flag: delete the LSP instead, only the SRP and LSP objects
+    // are sent
+    pub fn remove(mut self) -> Self {
+        self.flag_remove = true;
+        self
+    }
+
+    pub fn lsp(mut self, lsp: LspBuilder) -> Self {
+        self.lsp = lsp;
+        self
+    }
+
+    pub fn endpoints_ipv4(mut self, source: Ipv4Addr, destination: Ipv4Addr) -> Self {
+        self.endpoints = Some((source, destination));
+        self
+    }
+
+    pub fn ero(mut self, hops: Vec<SubObject>) -> Self {
+        self.path.ero = hops;
+        self
+    }
+
+    pub fn bandwidth(mut self, bandwidth: f32) -> Self {
+        self.path.bandwidth = Some(bandwidth);
+        self
+    }
+
+    pub fn metric(mut self, metric: MetricObject) -> Self {
+        self.path.metrics.push(metric);
+        self
+    }
+
+    pub fn build(mut self) -> PCInitiate {
+        let srp_object = srp_object(self.srp_id, self.flag_remove);
+        let lsp_object = self.lsp.build();
+        let lsp_request = if self.flag_remove {
+            PceInitiatedLspRequest::LspDeletion(PceInitiatedLspDeletion::new(
+                srp_object, lsp_object,
+            ))
+        } else {
+            PceInitiatedLspRequest::LspInstantiation(PceInitiateLspInstatiation::new(
+                srp_object,
+                lsp_object,
+                self.endpoints.map(|(source, destination)| {
+                    let mut endpoints_object = EndPointsObject::new_ipv4(source, destination);
+                    endpoints_object.common_object_mut().object_length =
+                        encoded_length(&endpoints_object);
+                    endpoints_object
+                }),
+                self.path.intended_path(),
+                Some(self.path.intended_attr_lst()),
+            ))
+        };
+        let mut pc_initiate = PCInitiate::new(vec![lsp_request]);
+        pc_initiate.common_header.message_length = encoded_length(&pc_initiate);
+        pc_initiate
+    }
+}
+
+// A single update request
+#[derive(Debug)]
+pub struct PcepUpdateBuilder {
+    srp_id: u32,
+    lsp: LspBuilder,
+    path: PathAttributes,
+}
+
+impl Default for PcepUpdateBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PcepUpdateBuilder {
+    // SRP-ID 1, as 0 is reserved
+    pub fn new() -> Self {
+        PcepUpdateBuilder {
+            srp_id: 1,
+            lsp: LspBuilder::new(),
+            path: Default::default(),
+        }
+    }
+
+    pub fn srp_id(mut self, srp_id: u32) -> Self {
+        self.srp_id = srp_id;
+        self
+    }
+
+    pub fn lsp(mut self, lsp: LspBuilder) -> Self {
+        self.lsp = lsp;
+        self
+    }
+
+    pub fn ero(mut self, hops: Vec<SubObject>) -> Self {
+        self.path.ero = hops;
+        self
+    }
+
+    pub fn bandwidth(mut self, bandwidth: f32) -> Self {
+        self.path.bandwidth = Some(bandwidth);
+        self
+    }
+
+    pub fn metric(mut self, metric: MetricObject) -> Self {
+        self.path.metrics.push(metric);
+        self
+    }
+
+    pub fn build(mut self) -> PcepUpdate {
+        let path = Path::new(self.path.intended_path(), self.path.intended_attr_lst());
+        let update_request =
+            UpdateRequest::new(srp_object(self.srp_id, false), self.lsp.build(), path);
+        let mut pcep_update = PcepUpdate::new(vec![update_request]);
+        pcep_update.common_header.message_length = encoded_length(&pcep_update);
+        pcep_update
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::messages::message::PcepMessage;
+    use crate::messages::types::MessageType;
+    use crate::objects::metric::MetricType;
+    use crate::tlvs::tlv_set::UnknownTLV;
+
+    #[test]
+    fn test_pcinitiate_builder() {
+        let pc_initiate = PCInitiate::builder()
+            .srp_id(6)
+            .lsp(
+                LspBuilder::new()
+                    .plsp_id(0)
+                    .name("lsp")
+                    .delegate(true)
+                    .administrative(true),
+            )
+            .endpoints_ipv4(Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(10, 0, 0, 2))
+            .build();
+        // Same bytes as the PCInitiate round trip vector in encoder.rs
+        let expected: &[u8] = &[
+            0x20, 0x0c, 0x00, 0x30, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x06, 0x20, 0x10, 0x00, 0x10, 0x00, 0x00, 0x00, 0x09, 0x00, 0x11, 0x00, 0x03,
+            0x6c, 0x73, 0x70, 0x00, 0x04, 0x10, 0x00, 0x0c, 0x0a, 0x00, 0x00, 0x01, 0x0a, 0x00,
+            0x00, 0x02, 0x07, 0x10, 0x00, 0x04,
+        ];
+        assert_eq!(pc_initiate.to_bytes(), expected);
+        let (_remaining, message) =
+            PcepMessage::parse_message(expected).expect("[!!] Error while parsing pcep message");
+        assert_eq!(message, PcepMessage::PcInitiate(pc_initiate));
+
+        let deletion = PCInitiate::builder()
+            .srp_id(7)
+            .remove()
+            .lsp(LspBuilder::new().plsp_id(235))
+            .build();
+        assert!(matches!(
+            deletion.lsp_requests(),
+            [PceInitiatedLspRequest::LspDeletion(_)]
+        ));
+    }
+
+    #[test]
+    fn test_pcep_update_builder() {
+        let pcep_update = PcepUpdate::builder()
+            .srp_id(12)
+            .lsp(
+                LspBuilder::new()
+                    .plsp_id(235)
+                    .name("cfg_PSADEL-5")
+                    .delegate(true)
+                    .administrative(true),
+            )
+            .ero(vec![
+                sr_hop(
+                    24001,
+                    adj(
+                        Ipv4Addr::new(10, 104, 105, 2),
+                        Ipv4Addr::new(10, 104, 105, 1),
+                    ),
+                ),
+                ipv4_hop(Ipv4Addr::new(10, 104, 105, 1)),
+            ])
+            .bandwidth(125000.0)
+            .metric(MetricObject::new(MetricType::Igp, 10.0, false))
+            .build();
+        // SRP 12, LSP 8 + 16 (name padded to 12), ERO 4 + 16 + 8,
+        // BANDWIDTH 8, METRIC 12
+        assert_eq!(pcep_update.common_header.message_type, MessageType::PCUpd);
+        assert_eq!(pcep_update.common_header.message_length, 88);
+        let bytes = pcep_update.to_bytes();
+        assert_eq!(bytes.len(), 88);
+        let update_request = &pcep_update.update_requests()[0];
+        let lsp_object = update_request.lsp_object();
+        assert_eq!(lsp_object.common_object.object_length, 24);
+        match lsp_object.tlvs.as_deref() {
+            Some([Tlv::SymbolicPathName(tlv)]) => assert_eq!(tlv.tlv_len, 12),
+            _ => panic!("[!!] Expected a symbolic path name tlv"),
+        }
+        let (_remaining, message) =
+            PcepMessage::parse_message(&bytes).expect("[!!] Error while parsing pcep message");
+        assert_eq!(message, PcepMessage::PcUpd(pcep_update));
+
+        // tlv_len of a TLV handed to the builder is filled in too
+        let lsp_object = LspBuilder::new()
+            .tlv(Tlv::Unknown(UnknownTLV {
+                tlv_type: 65000,
+                tlv_len: 0,
+                tlv_data: vec![0x01, 0x02, 0x03],
+            }))
+            .build();
+        assert_eq!(lsp_object.common_object.object_length, 16);
+        match lsp_object.tlvs.as_deref() {
+            Some([Tlv::Unknown(tlv)]) => assert_eq!(tlv.tlv_len, 3),
+            _ => panic!("[!!] Expected an unknown tlv"),
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod pcap;
+pub mod reassembly;
@@ -0,0 +1,199 @@
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::number;
+use nom::number::Endianness;
+use nom::{Err, IResult};
+
+use std::net::{Ipv4Addr, SocketAddrV4};
+use std::time::Duration;
+
+const LINKTYPE_NULL: u32 = 0;
+const LINKTYPE_ETHERNET: u32 = 1;
+const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_LINUX_SLL: u32 = 113;
+
+// Classic libpcap file header
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct PcapHeader {
+    pub endianness: Endianness,
+    pub nanosecond: bool,
+    pub version_major: u16,
+    pub version_minor: u16,
+    pub snaplen: u32,
+    pub linktype: u32,
+}
+
+impl PcapHeader {
+    pub fn parse_pcap_header(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, magic) = number::complete::be_u32(input)?;
+        let (endianness, nanosecond) = match magic {
+            0xa1b2c3d4 => (Endianness::Big, false),
+            0xd4c3b2a1 => (Endianness::Little, false),
+            0xa1b23c4d => (Endianness::Big, true),
+            0x4d3cb2a1 => (Endianness::Little, true),
+            _ => return Err(Err::Failure(Error::new(input, ErrorKind::Tag))),
+        };
+        let (remaining, version_major) = number::complete::u16(endianness)(remaining)?;
+        let (remaining, version_minor) = number::complete::u16(endianness)(remaining)?;
+        // thiszone and sigfigs
+        let (remaining, _) = bytes::complete::take(8usize)(remaining)?;
+        let (remaining, snaplen) = number::complete::u32(endianness)(remaining)?;
+        let (remaining, linktype) = number::complete::u32(endianness)(remaining)?;
+        let header = PcapHeader {
+            endianness,
+            nanosecond,
+            version_major,
+            version_minor,
+            snaplen,
+            linktype,
+        };
+        Ok((remaining, header))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TcpSegment {
+    pub timestamp: Duration,
+    pub source: SocketAddrV4,
+    pub destination: SocketAddrV4,
+    pub sequence: u32,
+    pub flag_syn: bool,
+    pub flag_fin: bool,
+    pub flag_rst: bool,
+    pub payload: Vec<u8>,
+}
+
+pub struct PcapReader;
+
+impl PcapReader {
+    fn parse_record<'a>(
+        header: &PcapHeader,
+        input: &'a [u8],
+    ) -> IResult<&'a [u8], (Duration, &'a [u8])> {
+        let (remaining, ts_sec) = number::complete::u32(header.endianness)(input)?;
+        let (remaining, ts_frac) = number::complete::u32(header.endianness)(remaining)?;
+        let (remaining, incl_len) = number::complete::u32(header.endianness)(remaining)?;
+        let (remaining, _orig_len) = number::complete::u32(header.endianness)(remaining)?;
+        let (remaining, frame) = bytes::complete::take(incl_len as usize)(remaining)?;
+        let nanos = if header.nanosecond {
+            ts_frac
+        } else {
+            ts_frac.saturating_mul(1000)
+        };
+        Ok((remaining, (Duration::new(ts_sec as u64, nanos), frame)))
+    }
+
+    // Strip the link layer, returning the IPv4 packet if there is one
+    fn ipv4_packet(linktype: u32, frame: &[u8]) -> Option<&[u8]> {
+        let (mut ethertype, mut packet) = match linktype {
+            LINKTYPE_ETHERNET if frame.len() >= 14 => {
+                (u16::from_be_bytes([frame[12], frame[13]]), &frame[14..])
+            }
+            LINKTYPE_LINUX_SLL if frame.len() >= 16 => {
+                (u16::from_be_bytes([frame[14], frame[15]]), &frame[16..])
+            }
+            LINKTYPE_NULL if frame.len() >= 4 => (0x0800, &frame[4..]),
+            LINKTYPE_RAW => (0x0800, frame),
+            _ => return None,
+        };
+        // 802.1Q VLAN tags
+        while ethertype == 0x8100 && packet.len() >= 4 {
+            ethertype = u16::from_be_bytes([packet[2], packet[3]]);
+            packet = &packet[4..];
+        }
+        if ethertype != 0x0800 || packet.is_empty() || packet[0] >> 4 != 4 {
+            return None;
+        }
+        Some(packet)
+    }
+
+    fn tcp_segment(timestamp: Duration, packet: &[u8]) -> Option<TcpSegment> {
+        let ihl = (packet[0] & 0x0f) as usize * 4;
+        if packet.len() < ihl + 20 || ihl < 20 || packet[9] != 6 {
+            return None;
+        }
+        let total_len = (u16::from_be_bytes([packet[2], packet[3]]) as usize).min(packet.len());
+        let source_ip = Ipv4Addr::new(packet[12], packet[13], packet[14], packet[15]);
+        let destination_ip = Ipv4Addr::new(packet[16], packet[17], packet[18], packet[19]);
+        let tcp = &packet[ihl..total_len.max(ihl)];
+        if tcp.len() < 20 {
+            return None;
+        }
+        let source_port = u16::from_be_bytes([tcp[0], tcp[1]]);
+        let destination_port = u16::from_be_bytes([tcp[2], tcp[3]]);
+        let sequence = u32::from_be_bytes([tcp[4], tcp[5], tcp[6], tcp[7]]);
+        let data_offset = (tcp[12] >> 4) as usize * 4;
+        let flags = tcp[13];
+        if data_offset < 20 || data_offset > tcp.len() {
+            return None;
+        }
+        Some(TcpSegment {
+            timestamp,
+            source: SocketAddrV4::new(source_ip, source_port),
+            destination: SocketAddrV4::new(destination_ip, destination_port),
+            sequence,
+            flag_fin: flags & 0x01 == 0x01,
+            flag_syn: flags & 0x02 == 0x02,
+            flag_rst: flags & 0x04 == 0x04,
+            payload: tcp[data_offset..].to_vec(),
+        })
+    }
+
+    // Extract every IPv4/TCP segment of a classic pcap capture. A truncated
+    // last record stops the read, its bytes are returned as remaining
+    pub fn read_tcp_segments(input: &[u8]) -> IResult<&[u8], Vec<TcpSegment>> {
+        let (mut left, header) = PcapHeader::parse_pcap_header(input)?;
+        let mut segments = vec![];
+        while !left.is_empty() {
+            let (remaining, (timestamp, frame)) = match Self::parse_record(&header, left) {
+                Ok(record) => record,
+                Err(Err::Error(_)) => break,
+                Err(e) => return Err(e),
+            };
+            if let Some(packet) = Self::ipv4_packet(header.linktype, frame) {
+                if let Some(segment) = Self::tcp_segment(timestamp, packet) {
+                    segments.push(segment);
+                }
+            }
+            left = remaining;
+        }
+        Ok((left, segments))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[test]
+    fn test_pcap_tcp_segment_extraction() {
+        let input: &[u8] = &[
+            0xd4, 0xc3, 0xb2, 0xa1, 0x02, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0xff, 0xff, 0x00, 0x00, 0x65, 0x00, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00,
+            0x20, 0xa1, 0x07, 0x00, 0x2c, 0x00, 0x00, 0x00, 0x2c, 0x00, 0x00, 0x00, 0x45, 0x00,
+            0x00, 0x2c, 0x00, 0x00, 0x00, 0x00, 0x40, 0x06, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x01,
+            0x0a, 0x00, 0x00, 0x02, 0xc3, 0x50, 0x10, 0x5d, 0x00, 0x00, 0x00, 0x64, 0x00, 0x00,
+            0x00, 0x00, 0x50, 0x18, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x20, 0x02, 0x00, 0x04,
+        ];
+        let (remaining, segments) =
+            PcapReader::read_tcp_segments(input).expect("[!!] Error while reading pcap");
+        assert!(remaining.is_empty());
+        assert_eq!(segments.len(), 1);
+        let segment = &segments[0];
+        assert_eq!(segment.timestamp, Duration::new(10, 500_000_000));
+        assert_eq!(
+            segment.source,
+            SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 50000)
+        );
+        assert_eq!(segment.destination.port(), 4189);
+        assert_eq!(segment.sequence, 100);
+        assert_eq!(segment.payload, vec![0x20, 0x02, 0x00, 0x04]);
+
+        // Capture cut in the middle of the next record header
+        let mut truncated = input.to_vec();
+        truncated.extend_from_slice(&[0x0b, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        let (remaining, segments) = PcapReader::read_tcp_segments(&truncated)
+            .expect("[!!] Truncated last record lost the segments before it");
+        assert_eq!(segments.len(), 1);
+        assert_eq!(remaining.len(), 6);
+    }
+}
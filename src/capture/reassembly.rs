@@ -0,0 +1,205 @@
+use crate::capture::pcap::TcpSegment;
+use crate::messages::message::PcepMessage;
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddrV4;
+use std::time::Duration;
+
+// A PCEP message recovered from a capture
+#[derive(Debug, PartialEq, Eq)]
+pub struct CapturedMessage {
+    pub timestamp: Duration,
+    pub source: SocketAddrV4,
+    pub destination: SocketAddrV4,
+    pub message: PcepMessage,
+}
+
+// One direction of a TCP connection
+#[derive(Debug, Default)]
+struct Stream {
+    next_sequence: Option<u32>,
+    pending: BTreeMap<u32, Vec<u8>>,
+    buffer: Vec<u8>,
+}
+
+impl Stream {
+    fn push(&mut self, segment: &TcpSegment) {
+        if segment.flag_syn {
+            self.next_sequence = Some(segment.sequence.wrapping_add(1));
+            self.buffer.clear();
+            self.pending.clear();
+            return;
+        }
+        if segment.payload.is_empty() {
+            return;
+        }
+        self.next_sequence.get_or_insert(segment.sequence);
+        // Keep the longest copy when the same sequence number is seen twice
+        let pending = self.pending.entry(segment.sequence).or_default();
+        if pending.len() < segment.payload.len() {
+            *pending = segment.payload.clone();
+        }
+        // Out of order segments wait in pending until the gap is filled
+        while let Some(next) = self.next_sequence {
+            let ready = self
+                .pending
+                .keys()
+                .copied()
+                .find(|sequence| sequence.wrapping_sub(next) as i32 <= 0);
+            let Some(payload) = ready.and_then(|sequence| self.pending.remove(&sequence)) else {
+                break;
+            };
+            // Drop the part we already have (retransmission/overlap)
+            let overlap = next.wrapping_sub(ready.unwrap_or(next)) as usize;
+            if overlap < payload.len() {
+                self.append(&payload[overlap..]);
+            }
+        }
+    }
+
+    fn append(&mut self, payload: &[u8]) {
+        self.buffer.extend_from_slice(payload);
+        self.next_sequence = self
+            .next_sequence
+            .map(|next| next.wrapping_add(payload.len() as u32));
+    }
+
+    // Split the in-order byte stream into complete PCEP messages
+    fn drain_messages(&mut self) -> Vec<Result<PcepMessage, Vec<u8>>> {
+        let mut messages = vec![];
+        while self.buffer.len() >= 4 {
+            let message_length = u16::from_be_bytes([self.buffer[2], self.buffer[3]]) as usize;
+            if message_length < 4 {
+                // Lost framing, nothing sensible can be done with the rest
+                self.buffer.clear();
+                break;
+            }
+            if self.buffer.len() < message_length {
+                break;
+            }
+            let raw: Vec<u8> = self.buffer.drain(..message_length).collect();
+            match PcepMessage::parse_message(&raw) {
+                Ok((_remaining, message)) => messages.push(Ok(message)),
+                Err(_e) => messages.push(Err(raw)),
+            }
+        }
+        messages
+    }
+}
+
+// Reassembles TCP streams and yields the PCEP messages they carry
+#[derive(Debug, Default)]
+pub struct Reassembler {
+    streams: HashMap<(SocketAddrV4, SocketAddrV4), Stream>,
+    undecodable: Vec<(Duration, SocketAddrV4, SocketAddrV4, Vec<u8>)>,
+}
+
+impl Reassembler {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn push(&mut self, segment: &TcpSegment) -> Vec<CapturedMessage> {
+        let key = (segment.source, segment.destination);
+        let stream = self.streams.entry(key).or_default();
+        stream.push(segment);
+        let mut captured = vec![];
+        for message in stream.drain_messages() {
+            match message {
+                Ok(message) => captured.push(CapturedMessage {
+                    timestamp: segment.timestamp,
+                    source: segment.source,
+                    destination: segment.destination,
+                    message,
+                }),
+                Err(raw) => self.undecodable.push((
+                    segment.timestamp,
+                    segment.source,
+                    segment.destination,
+                    raw,
+                )),
+            }
+        }
+        if segment.flag_fin || segment.flag_rst {
+            self.streams.remove(&key);
+        }
+        captured
+    }
+
+    // Messages that were framed correctly but could not be decoded
+    pub fn undecodable(&self) -> &[(Duration, SocketAddrV4, SocketAddrV4, Vec<u8>)] {
+        &self.undecodable
+    }
+
+    pub fn messages_from_segments(segments: &[TcpSegment]) -> Vec<CapturedMessage> {
+        let mut reassembler = Reassembler::new();
+        segments
+            .iter()
+            .flat_map(|segment| reassembler.push(segment))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::messages::types::MessageType;
+    use std::net::Ipv4Addr;
+
+    fn segment(sequence: u32, payload: &[u8]) -> TcpSegment {
+        TcpSegment {
+            timestamp: Duration::from_secs(sequence as u64),
+            source: SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 50000),
+            destination: SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 2), 4189),
+            sequence,
+            flag_syn: false,
+            flag_fin: false,
+            flag_rst: false,
+            payload: payload.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_reassembly_split_and_out_of_order() {
+        let segments = vec![
+            segment(100, &[0x20, 0x01, 0x00, 0x0c, 0x01, 0x10]),
+            segment(112, &[0x20, 0x02, 0x00, 0x04]),
+            segment(106, &[0x00, 0x08, 0x20, 0x1e, 0x78, 0x01]),
+            segment(106, &[0x00, 0x08, 0x20, 0x1e, 0x78, 0x01]),
+            segment(116, &[0x20, 0x02]),
+            segment(118, &[0x00, 0x04]),
+        ];
+        let messages = Reassembler::messages_from_segments(&segments);
+        let message_types: Vec<MessageType> = messages
+            .iter()
+            .map(|captured| captured.message.message_type())
+            .collect();
+        assert_eq!(message_types.len(), 3);
+        assert_eq!(message_types[0], MessageType::Open);
+        assert_eq!(message_types[1], MessageType::Keepalive);
+        assert_eq!(message_types[2], MessageType::Keepalive);
+    }
+
+    #[test]
+    fn test_reassembly_overlapping_out_of_order() {
+        // Open (12 bytes) then Keepalive (4 bytes), the later segments overlap
+        // each other and arrive before the one filling the gap
+        let segments = vec![
+            segment(100, &[0x20, 0x01, 0x00]),
+            segment(106, &[0x00, 0x08, 0x20, 0x1e]),
+            segment(109, &[0x1e, 0x78, 0x01, 0x20, 0x02]),
+            segment(103, &[0x0c, 0x01, 0x10, 0x00, 0x08]),
+            segment(114, &[0x00, 0x04]),
+        ];
+        let messages = Reassembler::messages_from_segments(&segments);
+        let message_types: Vec<MessageType> = messages
+            .iter()
+            .map(|captured| captured.message.message_type())
+            .collect();
+        assert_eq!(
+            message_types,
+            vec![MessageType::Open, MessageType::Keepalive],
+            "[!!] Overlapping segments were not trimmed while draining"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::encoder::Encode;
+use crate::messages::message::PcepMessage;
+
+use bytes::{Buf, BytesMut};
+use nom::{Err, Needed};
+use std::io;
+use tokio_util::codec::{Decoder, Encoder};
+
+// Frames a byte stream into PcepMessages on CommonHeader::message_length.
+// Incomplete from parse_message means the message is not fully buffered
+// yet, inconsistencies inside a buffered message are Failures.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PcepCodec;
+
+impl PcepCodec {
+    pub fn new() -> Self {
+        PcepCodec
+    }
+}
+
+fn invalid_data(reason: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+impl Decoder for PcepCodec {
+    type Item = PcepMessage;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let (consumed, message) = match PcepMessage::parse_message(src) {
+            Ok((remaining, message)) => (src.len() - remaining.len(), message),
+            Err(Err::Incomplete(needed)) => {
+                if let Needed::Size(size) = needed {
+                    src.reserve(size.get());
+                }
+                return Ok(None);
+            }
+            Err(e) => {
+                return Err(invalid_data(format!(
+                    "undecodable PCEP message: {:?}",
+                    e.map_input(|input| input.len())
+                )))
+            }
+        };
+        src.advance(consumed);
+        Ok(Some(message))
+    }
+}
+
+impl Encoder<PcepMessage> for PcepCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: PcepMessage, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        dst.extend_from_slice(&item.to_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::messages::keepalive::KeepAlive;
+    use crate::messages::types::MessageType;
+
+    const KEEPALIVE_OPEN: &[u8] = &[
+        0x20, 0x02, 0x00, 0x04, 0x20, 0x01, 0x00, 0x0c, 0x01, 0x10, 0x00, 0x08, 0x20, 0x1e, 0x78,
+        0x01,
+    ];
+
+    #[test]
+    fn test_codec_partial_reads() {
+        let mut codec = PcepCodec::new();
+        let mut src = BytesMut::new();
+        let mut decoded = vec![];
+        // One byte at a time, as the worst case of a TCP stream
+        for byte in KEEPALIVE_OPEN {
+            src.extend_from_slice(&[*byte]);
+            while let Some(message) = codec.decode(&mut src).expect("[!!] Decoding error") {
+                decoded.push(message.message_type());
+            }
+        }
+        assert_eq!(decoded, vec![MessageType::Keepalive, MessageType::Open]);
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn test_codec_round_trip_and_malformed() {
+        let mut codec = PcepCodec::new();
+        let mut dst = BytesMut::new();
+        codec
+            .encode(PcepMessage::Keepalive(KeepAlive::new()), &mut dst)
+            .expect("[!!] Encoding error");
+        let message = codec.decode(&mut dst).expect("[!!] Decoding error");
+        assert_eq!(
+            message.map(|m| m.message_type()),
+            Some(MessageType::Keepalive)
+        );
+
+        // Open message whose object claims 12 bytes within an 8 byte body
+        let mut src = BytesMut::from(
+            &[
+                0x20, 0x01, 0x00, 0x0c, 0x01, 0x10, 0x00, 0x0c, 0x20, 0x1e, 0x78, 0x01,
+            ][..],
+        );
+        assert!(codec.decode(&mut src).is_err());
+    }
+}
@@ -28,8 +28,9 @@ impl Utils {
 }
 
 #[non_exhaustive]
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
 pub enum Version {
+    #[default]
     One,
 }
 
@@ -41,18 +42,21 @@ impl std::fmt::Display for Version {
     }
 }
 
-impl From<u8> for Version {
-    fn from(value: u8) -> Self {
+impl TryFrom<u8> for Version {
+    type Error = u8;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            1 => Version::One,
-            _ => unimplemented!("Unknown version: {}", value),
+            1 => Ok(Version::One),
+            _ => Err(value),
         }
     }
 }
 
-impl Default for Version {
-    fn default() -> Self {
-        Self::One
+impl From<Version> for u8 {
+    fn from(value: Version) -> Self {
+        match value {
+            Version::One => 1,
+        }
     }
 }
 
@@ -82,6 +86,19 @@ impl From<u8> for OperationalStatus {
     }
 }
 
+impl From<OperationalStatus> for u8 {
+    fn from(value: OperationalStatus) -> Self {
+        match value {
+            OperationalStatus::Down => 0,
+            OperationalStatus::Up => 1,
+            OperationalStatus::Active => 2,
+            OperationalStatus::GoingDown => 3,
+            OperationalStatus::GoingUp => 4,
+            OperationalStatus::Reserved => 5,
+        }
+    }
+}
+
 impl std::fmt::Display for OperationalStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -95,9 +112,14 @@ impl std::fmt::Display for OperationalStatus {
     }
 }
 
+use crate::encoder::Encode;
 use crate::objects::bandwidth::BandwidthObject;
+use crate::objects::classes::ObjectClassType;
+use crate::objects::ero::EroObject;
+use crate::objects::header::CommonObject;
 use crate::objects::lspa::LspaObject;
 use crate::objects::metric::MetricObject;
+use crate::objects::pathattrib::PathAttribObject;
 use indoc::writedoc;
 
 // Attribute List Entity
@@ -108,27 +130,46 @@ pub struct IntendedAttrList {
     metric_list: Option<Vec<MetricObject>>,
 }
 impl IntendedAttrList {
+    pub fn new(
+        lspa_object: Option<LspaObject>,
+        bandwidth_object: Option<BandwidthObject>,
+        metric_list: Vec<MetricObject>,
+    ) -> Self {
+        IntendedAttrList {
+            lspa_object,
+            bandwidth_object,
+            metric_list: if metric_list.is_empty() {
+                None
+            } else {
+                Some(metric_list)
+            },
+        }
+    }
+
+    // Every member is optional, so parsing stops at the first object
+    // that is not LSPA, BANDWIDTH or METRIC. Errors inside a member are
+    // passed on rather than taken as its absence.
     pub fn parse_intended_attr_list(input: &[u8]) -> IResult<&[u8], Self> {
-        let (input, lspa_object) = match LspaObject::parse_lspa_object(input) {
-            Ok((remaining, lspa_object)) => (remaining, Some(lspa_object)),
-            Err(_e) => (input, None),
+        let (input, lspa_object) = match CommonObject::peek_object_class(input)? {
+            (input, Some(ObjectClassType::Lspa(_))) => {
+                let (remaining, lspa_object) = LspaObject::parse_lspa_object(input)?;
+                (remaining, Some(lspa_object))
+            }
+            (input, _) => (input, None),
         };
-        let (input, bandwidth_object) = match BandwidthObject::parse_bandwidth_object(input) {
-            Ok((remaining, bandwidth_object)) => (remaining, Some(bandwidth_object)),
-            Err(_e) => (input, None),
+        let (input, bandwidth_object) = match CommonObject::peek_object_class(input)? {
+            (input, Some(ObjectClassType::Bandwidth(_))) => {
+                let (remaining, bandwidth_object) = BandwidthObject::parse_bandwidth_object(input)?;
+                (remaining, Some(bandwidth_object))
+            }
+            (input, _) => (input, None),
         };
         let mut left = input;
         let mut metric_objects = vec![];
-        while left.first().is_some() {
-            match MetricObject::parse_metric_object(left) {
-                Ok((remaining, metric_object)) => {
-                    left = remaining;
-                    metric_objects.push(metric_object);
-                }
-                Err(_e) => {
-                    break;
-                }
-            }
+        while let (_, Some(ObjectClassType::Metric(_))) = CommonObject::peek_object_class(left)? {
+            let (remaining, metric_object) = MetricObject::parse_metric_object(left)?;
+            left = remaining;
+            metric_objects.push(metric_object);
         }
         let attr_lst = IntendedAttrList {
             lspa_object,
@@ -141,6 +182,17 @@ impl IntendedAttrList {
         };
         Ok((left, attr_lst))
     }
+
+    pub fn metric_list(&self) -> &[MetricObject] {
+        self.metric_list.as_deref().unwrap_or(&[])
+    }
+
+    // One line form, e.g. bw=1Mbps igp=10, empty without attributes
+    pub fn summary(&self) -> String {
+        let mut fields: Vec<String> = self.bandwidth_object.iter().map(|b| b.summary()).collect();
+        fields.extend(self.metric_list().iter().map(MetricObject::summary));
+        fields.join(" ")
+    }
 }
 
 impl std::fmt::Display for IntendedAttrList {
@@ -165,3 +217,155 @@ impl std::fmt::Display for IntendedAttrList {
         writedoc!(f, "{}", attr_lst)
     }
 }
+
+// Intended path list: one or more EROs, each optionally preceded by
+// its PATH-ATTRIB object when the PCE sends multiple paths per LSP
+#[derive(Debug, PartialEq, Eq)]
+pub struct IntendedPathList {
+    pub paths: Vec<(Option<PathAttribObject>, EroObject)>,
+}
+
+impl IntendedPathList {
+    // One line form, an ero=[...] per path
+    pub fn summary(&self) -> String {
+        let eros: Vec<String> = self
+            .paths
+            .iter()
+            .map(|(_path_attrib, ero_object)| ero_object.summary())
+            .collect();
+        eros.join(" ")
+    }
+
+    fn is_path_attrib_next(input: &[u8]) -> IResult<&[u8], bool> {
+        let (input, object_class) = CommonObject::peek_object_class(input)?;
+        Ok((
+            input,
+            matches!(object_class, Some(ObjectClassType::PathAttrib(_))),
+        ))
+    }
+
+    pub fn parse_intended_path_list(input: &[u8]) -> IResult<&[u8], Self> {
+        let mut left = input;
+        let mut paths = vec![];
+        loop {
+            let (remaining, path_attrib_object) = if Self::is_path_attrib_next(left)?.1 {
+                let (remaining, path_attrib_object) =
+                    PathAttribObject::parse_path_attrib_object(left)?;
+                (remaining, Some(path_attrib_object))
+            } else {
+                (left, None)
+            };
+            let (remaining, ero_object) = EroObject::parse_ero_object(remaining)?;
+            paths.push((path_attrib_object, ero_object));
+            left = remaining;
+            // Additional paths are always introduced by a PATH-ATTRIB object
+            if !Self::is_path_attrib_next(left)?.1 {
+                break;
+            }
+        }
+        Ok((left, IntendedPathList { paths }))
+    }
+}
+
+impl std::fmt::Display for IntendedPathList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut paths_str = String::new();
+        for (path_attrib_object, ero_object) in self.paths.iter() {
+            if let Some(ref path_attrib_object) = path_attrib_object {
+                let path_attrib_str = format!("{}", path_attrib_object);
+                paths_str.push_str(&path_attrib_str);
+            }
+            let ero_str = format!("{}", ero_object);
+            paths_str.push_str(&ero_str);
+        }
+        writedoc!(f, "{}", paths_str)
+    }
+}
+
+impl Encode for IntendedAttrList {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        if let Some(ref lspa_object) = self.lspa_object {
+            lspa_object.encode(buf);
+        }
+        if let Some(ref bandwidth_object) = self.bandwidth_object {
+            bandwidth_object.encode(buf);
+        }
+        for metric_object in self.metric_list() {
+            metric_object.encode(buf);
+        }
+    }
+}
+
+impl Encode for IntendedPathList {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        for (path_attrib_object, ero_object) in self.paths.iter() {
+            if let Some(ref path_attrib_object) = path_attrib_object {
+                path_attrib_object.encode(buf);
+            }
+            ero_object.encode(buf);
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[test]
+    fn test_intended_path_list_parsing_multipath() {
+        let input: &[u8] = &[
+            0x2d, 0x10, 0x00, 0x14, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01, 0x00, 0x3d,
+            0x00, 0x04, 0x00, 0x00, 0x00, 0x03, 0x07, 0x10, 0x00, 0x0c, 0x01, 0x08, 0xc0, 0xa8,
+            0x96, 0x2d, 0x20, 0x00, 0x2d, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00,
+            0x00, 0x02, 0x07, 0x10, 0x00, 0x0c, 0x01, 0x08, 0xc0, 0xa8, 0x96, 0x2e, 0x20, 0x00,
+            0x09, 0x10, 0x00, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x07, 0x07, 0x01, 0x00,
+        ];
+        let (remaining, path_list) = IntendedPathList::parse_intended_path_list(input)
+            .expect("[!!] Error while parsing intended path list");
+        assert_eq!(remaining.len(), 20);
+        assert_eq!(path_list.paths.len(), 2);
+        let path_ids: Vec<u32> = path_list
+            .paths
+            .iter()
+            .map(|(path_attrib, _ero)| path_attrib.as_ref().map(|p| p.path_id).unwrap_or(0))
+            .collect();
+        assert_eq!(path_ids, vec![1, 2]);
+        assert_eq!(
+            path_list.paths[0].0.as_ref().and_then(|p| p.weight()),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_intended_path_list_parsing_single_ero() {
+        let input: &[u8] = &[
+            0x07, 0x10, 0x00, 0x0c, 0x01, 0x08, 0xc0, 0xa8, 0x96, 0x2d, 0x20, 0x00,
+        ];
+        let (remaining, path_list) = IntendedPathList::parse_intended_path_list(input)
+            .expect("[!!] Error while parsing intended path list");
+        assert!(remaining.is_empty());
+        assert_eq!(path_list.paths.len(), 1);
+        assert!(path_list.paths[0].0.is_none());
+    }
+
+    #[test]
+    fn test_intended_attr_list_errors_are_not_absence() {
+        // An ERO is not part of the attribute list and is left alone
+        let input: &[u8] = &[
+            0x07, 0x10, 0x00, 0x0c, 0x01, 0x08, 0xc0, 0xa8, 0x96, 0x2d, 0x20, 0x00,
+        ];
+        let (remaining, attr_lst) = IntendedAttrList::parse_intended_attr_list(input)
+            .expect("[!!] Error while parsing intended attribute list");
+        assert_eq!(remaining, input);
+        assert_eq!(attr_lst, IntendedAttrList::new(None, None, vec![]));
+
+        // A cut LSPA object used to be reported as no LSPA at all
+        let input: &[u8] = &[
+            0x09, 0x10, 0x00, 0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        assert_eq!(
+            IntendedAttrList::parse_intended_attr_list(input),
+            Err(nom::Err::Incomplete(nom::Needed::new(8)))
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use crate::messages::types::MessageType;
+use crate::objects::header::CommonObject;
+use crate::tlvs::types::Tlv;
+
+// Serialization back to wire format, the inverse of the parse_* functions.
+// Lengths are always recomputed from the encoded content, the length
+// fields stored in parsed structs are ignored.
+pub trait Encode {
+    fn encode(&self, buf: &mut Vec<u8>);
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        self.encode(&mut buf);
+        buf
+    }
+}
+
+pub struct Encoder;
+
+impl Encoder {
+    pub fn encode_common_header(
+        buf: &mut Vec<u8>,
+        flags: u8,
+        message_type: MessageType,
+        body: &[u8],
+    ) {
+        // Version 1 in the upper 3 bits
+        buf.push((1 << 5) | (flags & 0b1_1111));
+        buf.push(message_type.into());
+        buf.extend_from_slice(&((body.len() + 4) as u16).to_be_bytes());
+        buf.extend_from_slice(body);
+    }
+
+    pub fn encode_object(
+        buf: &mut Vec<u8>,
+        object_class: u8,
+        object_type: u8,
+        common_object: &CommonObject,
+        body: &[u8],
+    ) {
+        buf.push(object_class);
+        buf.push(
+            (object_type << 4)
+                | ((common_object.reserved & 0b11) << 2)
+                | ((common_object.flag_process as u8) << 1)
+                | common_object.flag_ignore as u8,
+        );
+        buf.extend_from_slice(&((body.len() + 4) as u16).to_be_bytes());
+        buf.extend_from_slice(body);
+    }
+
+    // TLV length excludes the padding up to the next 4-byte boundary
+    pub fn encode_tlv(buf: &mut Vec<u8>, tlv_type: u16, value: &[u8]) {
+        buf.extend_from_slice(&tlv_type.to_be_bytes());
+        buf.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        buf.extend_from_slice(value);
+        buf.resize(buf.len() + (4 - value.len() % 4) % 4, 0);
+    }
+
+    pub fn encode_tlvs(buf: &mut Vec<u8>, tlvs: &Option<Vec<Tlv>>) {
+        if let Some(ref tlvs) = tlvs {
+            for tlv in tlvs {
+                tlv.encode(buf);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::messages::message::PcepMessage;
+    use crate::objects::classtype::ClassTypeObject;
+    use crate::objects::loadbalancing::LoadBalancingObject;
+    use crate::objects::nopath::NoPathObject;
+    use crate::objects::of::OfObject;
+    use crate::objects::svec::SvecObject;
+
+    fn assert_round_trip(input: &[u8]) {
+        let (remaining, message) =
+            PcepMessage::parse_message(input).expect("[!!] Error while parsing pcep message");
+        assert!(remaining.is_empty());
+        assert_eq!(message.to_bytes(), input);
+    }
+
+    #[test]
+    fn test_encode_open_round_trip() {
+        assert_round_trip(&[
+            0x20, 0x01, 0x00, 0x20, 0x01, 0x10, 0x00, 0x1c, 0x20, 0x1e, 0x78, 0x01, 0x00, 0x10,
+            0x00, 0x04, 0x00, 0x00, 0x00, 0x13, 0x00, 0x17, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x07,
+        ]);
+    }
+
+    #[test]
+    fn test_encode_pcrpt_round_trip() {
+        assert_round_trip(&[
+            0x20, 0x0a, 0x00, 0x48, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x05, 0x20, 0x10, 0x00, 0x10, 0x00, 0x0e, 0xb0, 0x19, 0x00, 0x11, 0x00, 0x04,
+            0x6c, 0x73, 0x70, 0x31, 0x07, 0x10, 0x00, 0x1c, 0x01, 0x08, 0xc0, 0xa8, 0x96, 0x2d,
+            0x20, 0x00, 0x24, 0x10, 0x30, 0x01, 0x05, 0xdc, 0x30, 0x00, 0x0a, 0x68, 0x69, 0x02,
+            0x0a, 0x68, 0x69, 0x01, 0x06, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x02, 0x41, 0x20,
+            0x00, 0x00,
+        ]);
+    }
+
+    #[test]
+    fn test_encode_pcinitiate_round_trip() {
+        assert_round_trip(&[
+            0x20, 0x0c, 0x00, 0x30, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x06, 0x20, 0x10, 0x00, 0x10, 0x00, 0x00, 0x00, 0x09, 0x00, 0x11, 0x00, 0x03,
+            0x6c, 0x73, 0x70, 0x00, 0x04, 0x10, 0x00, 0x0c, 0x0a, 0x00, 0x00, 0x01, 0x0a, 0x00,
+            0x00, 0x02, 0x07, 0x10, 0x00, 0x04,
+        ]);
+    }
+
+    #[test]
+    fn test_encode_unknown_object_round_trip() {
+        // PCInitiate with an unknown object (class 128) after the ERO
+        let input: &[u8] = &[
+            0x20, 0x0c, 0x00, 0x38, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x06, 0x20, 0x10, 0x00, 0x10, 0x00, 0x00, 0x00, 0x09, 0x00, 0x11, 0x00, 0x03,
+            0x6c, 0x73, 0x70, 0x00, 0x04, 0x10, 0x00, 0x0c, 0x0a, 0x00, 0x00, 0x01, 0x0a, 0x00,
+            0x00, 0x02, 0x07, 0x10, 0x00, 0x04, 0x80, 0x10, 0x00, 0x08, 0xde, 0xad, 0xbe, 0xef,
+        ];
+        assert_round_trip(input);
+        let (_remaining, message) =
+            PcepMessage::parse_message(input).expect("[!!] Error while parsing pcep message");
+        match message {
+            PcepMessage::PcInitiate(pc_initiate) => match &pc_initiate.lsp_requests()[0] {
+                crate::messages::pcinitiate::PceInitiatedLspRequest::LspInstantiation(inst) => {
+                    assert_eq!(inst.unknown_objects().len(), 1);
+                    assert_eq!(inst.unknown_objects()[0].object_class(), 128);
+                }
+                _ => panic!("[!!] Expected an LSP instantiation"),
+            },
+            _ => panic!("[!!] Expected a PCInitiate message"),
+        }
+
+        // Same object between the SRP and LSP objects stays there
+        assert_round_trip(&[
+            0x20, 0x0c, 0x00, 0x38, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x06, 0x80, 0x10, 0x00, 0x08, 0xde, 0xad, 0xbe, 0xef, 0x20, 0x10, 0x00, 0x10,
+            0x00, 0x00, 0x00, 0x09, 0x00, 0x11, 0x00, 0x03, 0x6c, 0x73, 0x70, 0x00, 0x04, 0x10,
+            0x00, 0x0c, 0x0a, 0x00, 0x00, 0x01, 0x0a, 0x00, 0x00, 0x02, 0x07, 0x10, 0x00, 0x04,
+        ]);
+        // PCUpd and PCRpt
+        assert_round_trip(&[
+            0x20, 0x0b, 0x00, 0x24, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x01, 0x80, 0x10, 0x00, 0x08, 0xde, 0xad, 0xbe, 0xef, 0x20, 0x10, 0x00, 0x08,
+            0x00, 0x00, 0x10, 0x09, 0x07, 0x10, 0x00, 0x04,
+        ]);
+        assert_round_trip(&[
+            0x20, 0x0a, 0x00, 0x20, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x01, 0x80, 0x10, 0x00, 0x08, 0xde, 0xad, 0xbe, 0xef, 0x20, 0x10, 0x00, 0x08,
+            0x00, 0x00, 0x10, 0x09,
+        ]);
+
+        // Same object with the P flag set cannot be processed
+        let mut input = input.to_vec();
+        input[49] |= 0b10;
+        assert!(matches!(
+            PcepMessage::parse_message(&input),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn test_encode_of_object_round_trip() {
+        let input: &[u8] = &[0x15, 0x10, 0x00, 0x08, 0x00, 0x01, 0x00, 0x00];
+        let (remaining, of_object) =
+            OfObject::parse_of_object(input).expect("[!!] Failed to parse OF object");
+        assert!(remaining.is_empty());
+        assert_eq!(of_object.to_bytes(), input);
+    }
+
+    #[test]
+    fn test_encode_no_path_object_round_trip() {
+        let input: &[u8] = &[
+            0x03, 0x10, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0x00, 0x01, 0x00, 0x04, 0x00, 0x00,
+            0x00, 0x03,
+        ];
+        let (remaining, no_path_object) =
+            NoPathObject::parse_no_path_object(input).expect("[!!] Failed to parse no-path object");
+        assert!(remaining.is_empty());
+        assert_eq!(no_path_object.to_bytes(), input);
+    }
+
+    #[test]
+    fn test_encode_svec_object_round_trip() {
+        let input: &[u8] = &[
+            0x0b, 0x10, 0x00, 0x10, 0x00, 0x00, 0x01, 0x05, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+            0x00, 0x02,
+        ];
+        let (remaining, svec_object) =
+            SvecObject::parse_svec_object(input).expect("[!!] Failed to parse svec object");
+        assert!(remaining.is_empty());
+        assert_eq!(svec_object.to_bytes(), input);
+    }
+
+    #[test]
+    fn test_encode_load_balancing_object_round_trip() {
+        let input: &[u8] = &[
+            0x0e, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x04, 0x49, 0x74, 0x24, 0x00,
+        ];
+        let (remaining, load_balancing_object) =
+            LoadBalancingObject::parse_load_balancing_object(input)
+                .expect("[!!] Failed to parse load-balancing object");
+        assert!(remaining.is_empty());
+        assert_eq!(load_balancing_object.to_bytes(), input);
+    }
+
+    #[test]
+    fn test_encode_class_type_object_round_trip() {
+        let input: &[u8] = &[0x16, 0x10, 0x00, 0x08, 0x00, 0x00, 0x00, 0x03];
+        let (remaining, class_type_object) = ClassTypeObject::parse_class_type_object(input)
+            .expect("[!!] Failed to parse classtype object");
+        assert!(remaining.is_empty());
+        assert_eq!(class_type_object.to_bytes(), input);
+    }
+}
@@ -0,0 +1,134 @@
+use nom::error::ErrorKind;
+use nom::{Err, IResult, Needed};
+
+// Why the input could not be parsed
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParseErrorKind {
+    // The input ends before the item it started, needed is the number
+    // of missing bytes when the parser could tell
+    Truncated { needed: Option<usize> },
+    // The bytes are all there but do not form a valid item
+    Malformed(ErrorKind),
+}
+
+// Error of the complete-input API, offset is counted from the start of
+// the buffer handed to the parser
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ParseError {
+    pub offset: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl ParseError {
+    // Error of a streaming parser run over input, seen as complete
+    pub fn from_nom(input: &[u8], err: Err<nom::error::Error<&[u8]>>) -> Self {
+        match err {
+            Err::Incomplete(needed) => ParseError {
+                offset: input.len(),
+                kind: ParseErrorKind::Truncated {
+                    needed: match needed {
+                        Needed::Size(size) => Some(size.get()),
+                        Needed::Unknown => None,
+                    },
+                },
+            },
+            Err::Error(e) | Err::Failure(e) => {
+                let offset = (e.input.as_ptr() as usize)
+                    .checked_sub(input.as_ptr() as usize)
+                    .filter(|offset| *offset <= input.len())
+                    .unwrap_or(input.len());
+                let kind = match e.code {
+                    // Raised by complete() for a length delimited item
+                    // that ended inside one of its components
+                    ErrorKind::Complete => ParseErrorKind::Truncated { needed: None },
+                    code => ParseErrorKind::Malformed(code),
+                };
+                ParseError { offset, kind }
+            }
+        }
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        matches!(self.kind, ParseErrorKind::Truncated { .. })
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ParseErrorKind::Truncated {
+                needed: Some(needed),
+            } => write!(
+                f,
+                "truncated input at offset {} ({} more bytes needed)",
+                self.offset, needed
+            ),
+            ParseErrorKind::Truncated { needed: None } => {
+                write!(f, "truncated input at offset {}", self.offset)
+            }
+            ParseErrorKind::Malformed(code) => write!(
+                f,
+                "malformed input at offset {} ({})",
+                self.offset,
+                code.description()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Run a streaming parser over input known to be complete: running out
+// of bytes is reported as truncation instead of Err::Incomplete
+pub fn parse_complete<'a, O, F>(input: &'a [u8], parser: F) -> Result<(&'a [u8], O), ParseError>
+where
+    F: FnOnce(&'a [u8]) -> IResult<&'a [u8], O>,
+{
+    parser(input).map_err(|e| ParseError::from_nom(input, e))
+}
+
+// Parse a length delimited slice (message body, object body, TLV list,
+// ERO subobjects) that is already fully buffered. Incomplete from inside
+// it cannot be cured by reading more, so it becomes a Failure pointing
+// at the end of the slice.
+pub fn complete<'a, O, F>(input: &'a [u8], parser: F) -> IResult<&'a [u8], O>
+where
+    F: FnOnce(&'a [u8]) -> IResult<&'a [u8], O>,
+{
+    match parser(input) {
+        Err(Err::Incomplete(_needed)) => Err(Err::Failure(nom::error::Error::new(
+            &input[input.len()..],
+            ErrorKind::Complete,
+        ))),
+        result => result,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use nom::bytes;
+
+    #[test]
+    fn test_complete_and_parse_complete() {
+        let input: &[u8] = &[0x01, 0x02, 0x03];
+        let take4 = bytes::streaming::take::<_, _, nom::error::Error<&[u8]>>(4usize);
+        assert_eq!(
+            parse_complete(input, take4),
+            Err(ParseError {
+                offset: 3,
+                kind: ParseErrorKind::Truncated { needed: Some(1) }
+            })
+        );
+
+        let take4 = bytes::streaming::take::<_, _, nom::error::Error<&[u8]>>(4usize);
+        let err = parse_complete(input, |i| complete(i, take4)).unwrap_err();
+        assert_eq!(err.offset, 3);
+        assert!(err.is_truncated());
+
+        let tag = bytes::streaming::tag::<_, _, nom::error::Error<&[u8]>>(&[0x01, 0x03][..]);
+        let err = parse_complete(input, tag).unwrap_err();
+        assert_eq!(err.offset, 0);
+        assert_eq!(err.kind, ParseErrorKind::Malformed(ErrorKind::Tag));
+    }
+}
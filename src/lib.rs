@@ -0,0 +1,20 @@
+pub mod analyzer;
+pub mod borrowed;
+pub mod builder;
+pub mod capture;
+#[cfg(feature = "codec")]
+pub mod codec;
+pub mod common;
+pub mod encoder;
+pub mod error;
+pub mod lspdb;
+pub mod messages;
+pub mod objects;
+pub mod options;
+pub mod registry;
+pub mod session;
+#[cfg(feature = "sim")]
+pub mod sim;
+pub mod subobjects;
+pub mod tlvs;
+pub mod validate;
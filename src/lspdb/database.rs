@@ -0,0 +1,258 @@
+use crate::capture::reassembly::CapturedMessage;
+use crate::lspdb::types::{LspDbSnapshot, LspState, StatusTransition};
+use crate::messages::message::PcepMessage;
+use crate::messages::pcinitiate::{PCInitiate, PceInitiatedLspRequest};
+use crate::messages::pcrpt::{PcRpt, StateReport};
+use crate::messages::pcupdate::{Path, PcepUpdate};
+use crate::objects::ero::EroObject;
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+fn first_ero(path: &Path) -> Option<EroObject> {
+    path.intended_path()
+        .paths
+        .first()
+        .map(|(_path_attrib, ero)| ero.clone())
+}
+
+// LSP database of one PCC, rebuilt from the stateful messages of its session
+#[derive(Debug, Default)]
+pub struct LspDatabase {
+    lsps: BTreeMap<u32, LspState>,
+    names: HashMap<String, u32>,
+    // PCInitiate requests waiting for the PCC to report the new PLSP-ID
+    pending_initiations: HashMap<String, (u32, Option<EroObject>)>,
+    position: usize,
+    last_timestamp: Option<Duration>,
+}
+
+impl LspDatabase {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn apply(&mut self, timestamp: Duration, message: &PcepMessage) {
+        match message {
+            PcepMessage::PcRpt(report_message) => self.apply_report(timestamp, report_message),
+            PcepMessage::PcUpd(update_message) => self.apply_update(update_message),
+            PcepMessage::PcInitiate(initiate_message) => self.apply_initiate(initiate_message),
+            _ => {}
+        }
+        self.position += 1;
+        self.last_timestamp = Some(timestamp);
+    }
+
+    fn apply_report(&mut self, timestamp: Duration, report_message: &PcRpt) {
+        for state_report in report_message.state_reports() {
+            if state_report.is_end_of_sync() {
+                continue;
+            }
+            if state_report.lsp_object().flag_remove {
+                self.remove(state_report.lsp_object().plsp_id);
+            } else {
+                self.apply_state_report(timestamp, state_report);
+            }
+        }
+    }
+
+    fn apply_state_report(&mut self, timestamp: Duration, state_report: &StateReport) {
+        let lsp_object = state_report.lsp_object();
+        let lsp = self.lsps.entry(lsp_object.plsp_id).or_insert_with(|| {
+            LspState::new(lsp_object.plsp_id, timestamp, lsp_object.operational_status)
+        });
+        if lsp.operational_status != lsp_object.operational_status {
+            lsp.transitions.push(StatusTransition {
+                timestamp,
+                from: lsp.operational_status,
+                to: lsp_object.operational_status,
+            });
+            lsp.operational_status = lsp_object.operational_status;
+        }
+        lsp.administrative = lsp_object.flag_administrative;
+        lsp.delegated = lsp_object.flag_delegate;
+        lsp.pce_initiated |= lsp_object.flag_create;
+        lsp.last_reported = timestamp;
+        if let Some(srp_object) = state_report.srp_object() {
+            lsp.last_srp_id = Some(srp_object.srp_id);
+        }
+        // The symbolic name is only mandatory in the first report of an LSP
+        if let Some(name) = lsp_object.symbolic_path_name() {
+            if let Some((srp_id, requested_ero)) = self.pending_initiations.remove(name) {
+                lsp.pce_initiated = true;
+                lsp.requested_ero = requested_ero;
+                lsp.last_srp_id.get_or_insert(srp_id);
+            }
+            lsp.symbolic_name = Some(name.to_string());
+            self.names.insert(name.to_string(), lsp_object.plsp_id);
+        }
+        if let Some(path) = state_report.path() {
+            lsp.ero = first_ero(path);
+            lsp.metrics = path.intended_attr_lst().metric_list().to_vec();
+        }
+    }
+
+    fn apply_update(&mut self, update_message: &PcepUpdate) {
+        for update_request in update_message.update_requests() {
+            if let Some(lsp) = self.lsps.get_mut(&update_request.lsp_object().plsp_id) {
+                lsp.requested_ero = first_ero(update_request.path());
+                lsp.last_srp_id = Some(update_request.srp_object().srp_id);
+            }
+        }
+    }
+
+    fn apply_initiate(&mut self, initiate_message: &PCInitiate) {
+        for lsp_request in initiate_message.lsp_requests() {
+            match lsp_request {
+                PceInitiatedLspRequest::LspInstantiation(instantiation) => {
+                    if let Some(name) = instantiation.lsp_object().symbolic_path_name() {
+                        let requested_ero = instantiation
+                            .ero_paths()
+                            .paths
+                            .first()
+                            .map(|(_path_attrib, ero)| ero.clone());
+                        self.pending_initiations.insert(
+                            name.to_string(),
+                            (instantiation.srp_object().srp_id, requested_ero),
+                        );
+                    }
+                }
+                PceInitiatedLspRequest::LspDeletion(deletion) => {
+                    if let Some(lsp) = self.lsps.get_mut(&deletion.lsp_object().plsp_id) {
+                        lsp.removal_requested = true;
+                        lsp.last_srp_id = Some(deletion.srp_object().srp_id);
+                    }
+                }
+            }
+        }
+    }
+
+    fn remove(&mut self, plsp_id: u32) {
+        if let Some(lsp) = self.lsps.remove(&plsp_id) {
+            if let Some(name) = lsp.symbolic_name {
+                self.names.remove(&name);
+            }
+        }
+    }
+
+    pub fn get(&self, plsp_id: u32) -> Option<&LspState> {
+        self.lsps.get(&plsp_id)
+    }
+
+    pub fn get_by_name(&self, symbolic_name: &str) -> Option<&LspState> {
+        self.lsps.get(self.names.get(symbolic_name)?)
+    }
+
+    pub fn lsps(&self) -> impl Iterator<Item = &LspState> {
+        self.lsps.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lsps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lsps.is_empty()
+    }
+
+    pub fn snapshot(&self) -> LspDbSnapshot {
+        LspDbSnapshot {
+            timestamp: self.last_timestamp,
+            position: self.position,
+            lsps: self.lsps.clone(),
+        }
+    }
+
+    // Replays a session's messages up to (and including) the given timestamp
+    pub fn snapshot_at(messages: &[CapturedMessage], until: Duration) -> LspDbSnapshot {
+        let mut lsp_db = LspDatabase::new();
+        for captured in messages.iter().take_while(|c| c.timestamp <= until) {
+            lsp_db.apply(captured.timestamp, &captured.message);
+        }
+        lsp_db.snapshot()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::common::OperationalStatus;
+    use std::net::{Ipv4Addr, SocketAddrV4};
+
+    fn parse(input: &[u8]) -> PcepMessage {
+        let (_remaining, message) =
+            PcepMessage::parse_message(input).expect("[!!] Error while parsing pcep message");
+        message
+    }
+
+    const REPORT_DOWN: &[u8] = &[
+        0x20, 0x0a, 0x00, 0x20, 0x20, 0x10, 0x00, 0x10, 0x00, 0x0e, 0xb0, 0x09, 0x00, 0x11, 0x00,
+        0x04, 0x6c, 0x73, 0x70, 0x31, 0x07, 0x10, 0x00, 0x0c, 0x01, 0x08, 0xc0, 0xa8, 0x96, 0x2d,
+        0x20, 0x00,
+    ];
+    const REPORT_UP: &[u8] = &[
+        0x20, 0x0a, 0x00, 0x24, 0x20, 0x10, 0x00, 0x08, 0x00, 0x0e, 0xb0, 0x19, 0x07, 0x10, 0x00,
+        0x0c, 0x01, 0x08, 0xc0, 0xa8, 0x96, 0x2e, 0x20, 0x00, 0x06, 0x10, 0x00, 0x0c, 0x00, 0x00,
+        0x00, 0x02, 0x41, 0x20, 0x00, 0x00,
+    ];
+    const REPORT_REMOVE: &[u8] = &[
+        0x20, 0x0a, 0x00, 0x10, 0x20, 0x10, 0x00, 0x08, 0x00, 0x0e, 0xb0, 0x0d, 0x07, 0x10, 0x00,
+        0x04,
+    ];
+
+    #[test]
+    fn test_lspdb_reports_and_removal() {
+        let mut lsp_db = LspDatabase::new();
+        lsp_db.apply(Duration::from_secs(1), &parse(REPORT_DOWN));
+        lsp_db.apply(Duration::from_secs(2), &parse(REPORT_UP));
+        let snapshot = lsp_db.snapshot();
+
+        let lsp = lsp_db.get_by_name("lsp1").expect("[!!] LSP missing");
+        assert_eq!(lsp.plsp_id, 235);
+        assert_eq!(lsp.operational_status, OperationalStatus::Up);
+        assert!(lsp.delegated);
+        assert_eq!(lsp.metrics.len(), 1);
+        assert_eq!(
+            lsp.transitions,
+            vec![StatusTransition {
+                timestamp: Duration::from_secs(2),
+                from: OperationalStatus::Down,
+                to: OperationalStatus::Up,
+            }]
+        );
+
+        lsp_db.apply(Duration::from_secs(3), &parse(REPORT_REMOVE));
+        assert!(lsp_db.is_empty());
+        assert!(lsp_db.get_by_name("lsp1").is_none());
+        assert_eq!(snapshot.position, 2);
+        assert_eq!(snapshot.get(235).map(|l| l.plsp_id), Some(235));
+    }
+
+    #[test]
+    fn test_lspdb_snapshot_at() {
+        let source = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 50000);
+        let destination = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 2), 4189);
+        let messages: Vec<CapturedMessage> = [REPORT_DOWN, REPORT_UP, REPORT_REMOVE]
+            .iter()
+            .enumerate()
+            .map(|(i, input)| CapturedMessage {
+                timestamp: Duration::from_secs(i as u64 * 60),
+                source,
+                destination,
+                message: parse(input),
+            })
+            .collect();
+        let snapshot = LspDatabase::snapshot_at(&messages, Duration::from_secs(30));
+        assert_eq!(
+            snapshot.get(235).map(|l| l.operational_status),
+            Some(OperationalStatus::Down)
+        );
+        let snapshot = LspDatabase::snapshot_at(&messages, Duration::from_secs(90));
+        assert_eq!(
+            snapshot.get_by_name("lsp1").map(|l| l.operational_status),
+            Some(OperationalStatus::Up)
+        );
+        let snapshot = LspDatabase::snapshot_at(&messages, Duration::from_secs(120));
+        assert!(snapshot.lsps.is_empty());
+    }
+}
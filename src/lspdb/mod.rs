@@ -0,0 +1,3 @@
+pub mod database;
+pub mod sync;
+pub mod types;
@@ -0,0 +1,650 @@
+use colored::Colorize;
+use indoc::writedoc;
+
+use crate::messages::message::PcepMessage;
+use crate::messages::pcrpt::{PcRpt, StateReport};
+use crate::messages::types::MessageType;
+use crate::objects::open::OpenObject;
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SyncPhase {
+    #[default]
+    NotStarted,
+    InProgress,
+    Completed,
+    // Both sides advertised the same LSP-DB-VERSION (RFC 8232)
+    Skipped,
+}
+
+impl std::fmt::Display for SyncPhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotStarted => write!(f, "SyncPhase::NotStarted"),
+            Self::InProgress => write!(f, "SyncPhase::InProgress"),
+            Self::Completed => write!(f, "SyncPhase::Completed"),
+            Self::Skipped => write!(f, "SyncPhase::Skipped"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SyncKind {
+    // RFC 8231 initial synchronization of the full LSP-DB
+    Full,
+    // RFC 8232 incremental synchronization of changed LSPs only
+    Delta,
+    // RFC 8232 resynchronization requested by the PCE
+    Triggered,
+}
+
+impl std::fmt::Display for SyncKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full => write!(f, "SyncKind::Full"),
+            Self::Delta => write!(f, "SyncKind::Delta"),
+            Self::Triggered => write!(f, "SyncKind::Triggered"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SyncEvent {
+    SyncSkipped {
+        lsp_db_version: u64,
+    },
+    SyncStarted {
+        kind: SyncKind,
+    },
+    SyncCompleted {
+        kind: SyncKind,
+        lsp_count: usize,
+        duration: Duration,
+    },
+    // PLSP-ID 0 asks for a resync of the whole LSP-DB
+    ResyncRequested {
+        plsp_id: u32,
+    },
+    LspResynced {
+        plsp_id: u32,
+    },
+}
+
+impl std::fmt::Display for SyncEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SyncSkipped { lsp_db_version } => {
+                write!(f, "sync skipped, LSP-DB-VERSION {}", lsp_db_version)
+            }
+            Self::SyncStarted { kind } => write!(f, "{} sync started", kind),
+            Self::SyncCompleted {
+                kind,
+                lsp_count,
+                duration,
+            } => write!(
+                f,
+                "{} sync completed, {} LSP(s) in {:.3}s",
+                kind,
+                lsp_count,
+                duration.as_secs_f64()
+            ),
+            Self::ResyncRequested { plsp_id } => {
+                write!(f, "resync requested for PLSP-ID {}", plsp_id)
+            }
+            Self::LspResynced { plsp_id } => write!(f, "PLSP-ID {} resynced", plsp_id),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SyncIssue {
+    // Report without the S flag while the synchronization is not finished
+    OutOfOrderReport {
+        plsp_id: u32,
+    },
+    // Report with the S flag outside of any synchronization
+    UnexpectedSyncReport {
+        plsp_id: u32,
+    },
+    UnexpectedEndOfSync,
+    // PCUpd/PCInitiate sent before the PCC finished its synchronization
+    RequestDuringSync {
+        message_type: MessageType,
+    },
+    MissingDbVersion {
+        plsp_id: u32,
+    },
+    DbVersionNotIncreasing {
+        plsp_id: u32,
+        previous: u64,
+        current: u64,
+    },
+    // Session ended before the end-of-sync marker
+    IncompleteSync {
+        kind: SyncKind,
+        lsp_count: usize,
+    },
+}
+
+impl std::fmt::Display for SyncIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutOfOrderReport { plsp_id } => {
+                write!(f, "PLSP-ID {} reported without S flag during sync", plsp_id)
+            }
+            Self::UnexpectedSyncReport { plsp_id } => {
+                write!(f, "PLSP-ID {} reported with S flag outside sync", plsp_id)
+            }
+            Self::UnexpectedEndOfSync => write!(f, "end-of-sync marker outside sync"),
+            Self::RequestDuringSync { message_type } => {
+                write!(f, "{} sent before sync completed", message_type)
+            }
+            Self::MissingDbVersion { plsp_id } => {
+                write!(f, "PLSP-ID {} reported without LSP-DB-VERSION", plsp_id)
+            }
+            Self::DbVersionNotIncreasing {
+                plsp_id,
+                previous,
+                current,
+            } => write!(
+                f,
+                "PLSP-ID {} LSP-DB-VERSION {} not above {}",
+                plsp_id, current, previous
+            ),
+            Self::IncompleteSync { kind, lsp_count } => write!(
+                f,
+                "{} sync never completed, {} LSP(s) synced",
+                kind, lsp_count
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct SpeakerSyncCapabilities {
+    stateful: bool,
+    include_db_version: bool,
+    delta_lsp_sync: bool,
+    lsp_db_version: Option<u64>,
+}
+
+impl From<&OpenObject> for SpeakerSyncCapabilities {
+    fn from(open_object: &OpenObject) -> Self {
+        let stateful_capability = open_object.stateful_capability();
+        SpeakerSyncCapabilities {
+            stateful: stateful_capability.is_some(),
+            include_db_version: stateful_capability
+                .map(|spc| spc.flag_include_db_version)
+                .unwrap_or(false),
+            delta_lsp_sync: stateful_capability
+                .map(|spc| spc.flag_delta_lsp_sync_capability)
+                .unwrap_or(false),
+            lsp_db_version: open_object.lsp_db_version(),
+        }
+    }
+}
+
+// Follows the state synchronization of one PCEP session
+#[derive(Debug, Default)]
+pub struct SyncTracker {
+    pcc: Option<SpeakerSyncCapabilities>,
+    pce: Option<SpeakerSyncCapabilities>,
+    phase: SyncPhase,
+    kind: Option<SyncKind>,
+    started: Option<Duration>,
+    synced_lsps: BTreeSet<u32>,
+    pending_resync: BTreeSet<u32>,
+    last_db_version: Option<u64>,
+    timeline: Vec<(Duration, SyncEvent)>,
+    issues: Vec<(Duration, SyncIssue)>,
+}
+
+impl SyncTracker {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn is_stateful(&self) -> bool {
+        matches!((self.pcc, self.pce), (Some(pcc), Some(pce)) if pcc.stateful && pce.stateful)
+    }
+
+    fn include_db_version(&self) -> bool {
+        matches!((self.pcc, self.pce), (Some(pcc), Some(pce)) if pcc.include_db_version && pce.include_db_version)
+    }
+
+    fn initial_sync_kind(&self) -> SyncKind {
+        match (self.pcc, self.pce) {
+            (Some(pcc), Some(pce))
+                if pcc.delta_lsp_sync && pce.delta_lsp_sync && pce.lsp_db_version.is_some() =>
+            {
+                SyncKind::Delta
+            }
+            _ => SyncKind::Full,
+        }
+    }
+
+    fn handle_open(&mut self, timestamp: Duration, from_pcc: bool, open_object: &OpenObject) {
+        let capabilities = open_object.into();
+        if from_pcc {
+            self.pcc = Some(capabilities);
+        } else {
+            self.pce = Some(capabilities);
+        }
+        if self.phase != SyncPhase::NotStarted || !self.include_db_version() {
+            return;
+        }
+        if let (Some(pcc), Some(pce)) = (self.pcc, self.pce) {
+            match (pcc.lsp_db_version, pce.lsp_db_version) {
+                (Some(pcc_version), Some(pce_version)) if pcc_version == pce_version => {
+                    self.phase = SyncPhase::Skipped;
+                    self.last_db_version = Some(pcc_version);
+                    self.timeline.push((
+                        timestamp,
+                        SyncEvent::SyncSkipped {
+                            lsp_db_version: pcc_version,
+                        },
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn start_sync(&mut self, timestamp: Duration, kind: SyncKind) {
+        self.phase = SyncPhase::InProgress;
+        self.kind = Some(kind);
+        self.started = Some(timestamp);
+        self.synced_lsps.clear();
+        self.timeline
+            .push((timestamp, SyncEvent::SyncStarted { kind }));
+    }
+
+    fn check_db_version(&mut self, timestamp: Duration, state_report: &StateReport) {
+        let plsp_id = state_report.lsp_object().plsp_id;
+        match state_report.lsp_object().lsp_db_version() {
+            Some(current) => {
+                if let Some(previous) = self.last_db_version {
+                    // Versions are not bumped while replaying the LSP-DB
+                    let in_sync = self.phase == SyncPhase::InProgress;
+                    if current < previous || (current == previous && !in_sync) {
+                        self.issues.push((
+                            timestamp,
+                            SyncIssue::DbVersionNotIncreasing {
+                                plsp_id,
+                                previous,
+                                current,
+                            },
+                        ));
+                    }
+                }
+                self.last_db_version = Some(current);
+            }
+            None => self
+                .issues
+                .push((timestamp, SyncIssue::MissingDbVersion { plsp_id })),
+        }
+    }
+
+    fn handle_report(&mut self, timestamp: Duration, report_message: &PcRpt) {
+        for state_report in report_message.state_reports() {
+            let lsp_object = state_report.lsp_object();
+            let plsp_id = lsp_object.plsp_id;
+            if state_report.is_end_of_sync() {
+                match self.phase {
+                    SyncPhase::NotStarted => {
+                        // Empty LSP-DB, nothing to replay
+                        let kind = self.initial_sync_kind();
+                        self.start_sync(timestamp, kind);
+                        self.complete_sync(timestamp);
+                    }
+                    SyncPhase::InProgress => self.complete_sync(timestamp),
+                    _ => self
+                        .issues
+                        .push((timestamp, SyncIssue::UnexpectedEndOfSync)),
+                }
+                continue;
+            }
+            if self.include_db_version() && !lsp_object.flag_remove {
+                self.check_db_version(timestamp, state_report);
+            }
+            if lsp_object.flag_sync {
+                match self.phase {
+                    SyncPhase::NotStarted => {
+                        let kind = self.initial_sync_kind();
+                        self.start_sync(timestamp, kind);
+                        self.synced_lsps.insert(plsp_id);
+                    }
+                    SyncPhase::InProgress => {
+                        self.synced_lsps.insert(plsp_id);
+                    }
+                    _ if self.pending_resync.contains(&0) => {
+                        self.pending_resync.remove(&0);
+                        self.start_sync(timestamp, SyncKind::Triggered);
+                        self.synced_lsps.insert(plsp_id);
+                    }
+                    _ if self.pending_resync.contains(&plsp_id) => {
+                        self.pending_resync.remove(&plsp_id);
+                        self.timeline
+                            .push((timestamp, SyncEvent::LspResynced { plsp_id }));
+                    }
+                    _ => self
+                        .issues
+                        .push((timestamp, SyncIssue::UnexpectedSyncReport { plsp_id })),
+                }
+            } else if matches!(self.phase, SyncPhase::NotStarted | SyncPhase::InProgress) {
+                self.issues
+                    .push((timestamp, SyncIssue::OutOfOrderReport { plsp_id }));
+            }
+        }
+    }
+
+    fn complete_sync(&mut self, timestamp: Duration) {
+        let kind = self.kind.unwrap_or(SyncKind::Full);
+        let started = self.started.unwrap_or(timestamp);
+        self.phase = SyncPhase::Completed;
+        self.timeline.push((
+            timestamp,
+            SyncEvent::SyncCompleted {
+                kind,
+                lsp_count: self.synced_lsps.len(),
+                duration: timestamp.saturating_sub(started),
+            },
+        ));
+    }
+
+    fn check_request(&mut self, timestamp: Duration, message_type: MessageType) {
+        if matches!(self.phase, SyncPhase::NotStarted | SyncPhase::InProgress) {
+            self.issues
+                .push((timestamp, SyncIssue::RequestDuringSync { message_type }));
+        }
+    }
+
+    // from_pcc tells which side of the session sent the message
+    pub fn process(&mut self, timestamp: Duration, from_pcc: bool, message: &PcepMessage) {
+        match message {
+            PcepMessage::Open(open) => self.handle_open(timestamp, from_pcc, open.open_object()),
+            PcepMessage::PcRpt(report_message) if from_pcc => {
+                self.handle_report(timestamp, report_message)
+            }
+            PcepMessage::PcUpd(update_message) if !from_pcc => {
+                let mut resync = false;
+                for update_request in update_message.update_requests() {
+                    let lsp_object = update_request.lsp_object();
+                    if lsp_object.flag_sync {
+                        resync = true;
+                        self.pending_resync.insert(lsp_object.plsp_id);
+                        self.timeline.push((
+                            timestamp,
+                            SyncEvent::ResyncRequested {
+                                plsp_id: lsp_object.plsp_id,
+                            },
+                        ));
+                    }
+                }
+                if !resync {
+                    self.check_request(timestamp, MessageType::PCUpd);
+                }
+            }
+            PcepMessage::PcInitiate(_) if !from_pcc => {
+                self.check_request(timestamp, MessageType::PCInitiate)
+            }
+            _ => {}
+        }
+    }
+
+    // To be called once the session is over
+    pub fn finish(&mut self, timestamp: Duration) {
+        let kind = match self.phase {
+            SyncPhase::InProgress => self.kind.unwrap_or(SyncKind::Full),
+            // Both sides are stateful but the PCC never sent its end-of-sync
+            SyncPhase::NotStarted if self.is_stateful() => self.initial_sync_kind(),
+            _ => return,
+        };
+        self.issues.push((
+            timestamp,
+            SyncIssue::IncompleteSync {
+                kind,
+                lsp_count: self.synced_lsps.len(),
+            },
+        ));
+    }
+
+    pub fn phase(&self) -> SyncPhase {
+        self.phase
+    }
+
+    pub fn timeline(&self) -> &[(Duration, SyncEvent)] {
+        &self.timeline
+    }
+
+    pub fn issues(&self) -> &[(Duration, SyncIssue)] {
+        &self.issues
+    }
+}
+
+impl std::fmt::Display for SyncTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut timeline_str = String::new();
+        for (timestamp, event) in self.timeline.iter() {
+            timeline_str.push_str(&format!(
+                "        [{:.6}] {}\n",
+                timestamp.as_secs_f64(),
+                event
+            ));
+        }
+        let mut issues_str = String::new();
+        for (timestamp, issue) in self.issues.iter() {
+            issues_str.push_str(&format!(
+                "        [{:.6}] {}\n",
+                timestamp.as_secs_f64(),
+                issue
+            ));
+        }
+        let title = "==[State Synchronization]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                phase                        = {phase}
+                {timeline_title}
+            {timeline_str}    {issues_title}
+            {issues_str}"#,
+            title = title,
+            phase = self.phase,
+            timeline_title = "[[timeline]]".bold(),
+            timeline_str = timeline_str,
+            issues_title = "[[issues]]".bold(),
+            issues_str = issues_str
+        )
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn parse(input: &[u8]) -> PcepMessage {
+        let (_remaining, message) =
+            PcepMessage::parse_message(input).expect("[!!] Error while parsing pcep message");
+        message
+    }
+
+    fn open(lsp_db_version: u8) -> PcepMessage {
+        parse(&[
+            0x20,
+            0x01,
+            0x00,
+            0x20,
+            0x01,
+            0x10,
+            0x00,
+            0x1c,
+            0x20,
+            0x1e,
+            0x78,
+            0x01,
+            0x00,
+            0x10,
+            0x00,
+            0x04,
+            0x00,
+            0x00,
+            0x00,
+            0x13,
+            0x00,
+            0x17,
+            0x00,
+            0x08,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            lsp_db_version,
+        ])
+    }
+
+    // Up, administrative, delegated, with the given S flag and LSP-DB-VERSION
+    fn report(plsp_id: u8, flag_sync: bool, lsp_db_version: u8) -> PcepMessage {
+        let flags = if flag_sync { 0x1b } else { 0x19 };
+        parse(&[
+            0x20,
+            0x0a,
+            0x00,
+            0x1c,
+            0x20,
+            0x10,
+            0x00,
+            0x14,
+            0x00,
+            0x00,
+            plsp_id << 4,
+            flags,
+            0x00,
+            0x17,
+            0x00,
+            0x08,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            lsp_db_version,
+            0x07,
+            0x10,
+            0x00,
+            0x04,
+        ])
+    }
+
+    const END_OF_SYNC: &[u8] = &[
+        0x20, 0x0a, 0x00, 0x10, 0x20, 0x10, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x07, 0x10, 0x00,
+        0x04,
+    ];
+
+    #[test]
+    fn test_sync_tracker_delta_sync() {
+        let mut tracker = SyncTracker::new();
+        tracker.process(Duration::from_secs(0), true, &open(9));
+        tracker.process(Duration::from_secs(0), false, &open(7));
+        tracker.process(Duration::from_secs(1), true, &report(1, true, 8));
+        tracker.process(Duration::from_secs(2), true, &report(2, true, 9));
+        tracker.process(Duration::from_secs(3), true, &parse(END_OF_SYNC));
+        tracker.process(Duration::from_secs(4), true, &report(1, false, 10));
+        tracker.finish(Duration::from_secs(5));
+        assert_eq!(tracker.phase(), SyncPhase::Completed);
+        assert!(tracker.issues().is_empty());
+        assert_eq!(
+            tracker.timeline()[1],
+            (
+                Duration::from_secs(3),
+                SyncEvent::SyncCompleted {
+                    kind: SyncKind::Delta,
+                    lsp_count: 2,
+                    duration: Duration::from_secs(2),
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_sync_tracker_skipped_sync() {
+        let mut tracker = SyncTracker::new();
+        tracker.process(Duration::from_secs(0), true, &open(7));
+        tracker.process(Duration::from_secs(0), false, &open(7));
+        tracker.process(Duration::from_secs(1), true, &report(1, false, 8));
+        assert_eq!(tracker.phase(), SyncPhase::Skipped);
+        assert_eq!(
+            tracker.timeline(),
+            &[(
+                Duration::from_secs(0),
+                SyncEvent::SyncSkipped { lsp_db_version: 7 }
+            )]
+        );
+        assert!(tracker.issues().is_empty());
+    }
+
+    #[test]
+    fn test_sync_tracker_out_of_order_and_incomplete() {
+        let mut tracker = SyncTracker::new();
+        tracker.process(Duration::from_secs(0), true, &open(9));
+        tracker.process(Duration::from_secs(0), false, &open(7));
+        tracker.process(Duration::from_secs(1), true, &report(1, true, 8));
+        tracker.process(Duration::from_secs(2), true, &report(2, false, 7));
+        tracker.finish(Duration::from_secs(3));
+        assert_eq!(
+            tracker.issues(),
+            &[
+                (
+                    Duration::from_secs(2),
+                    SyncIssue::DbVersionNotIncreasing {
+                        plsp_id: 2,
+                        previous: 8,
+                        current: 7,
+                    }
+                ),
+                (
+                    Duration::from_secs(2),
+                    SyncIssue::OutOfOrderReport { plsp_id: 2 }
+                ),
+                (
+                    Duration::from_secs(3),
+                    SyncIssue::IncompleteSync {
+                        kind: SyncKind::Delta,
+                        lsp_count: 1,
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sync_tracker_never_started() {
+        let mut tracker = SyncTracker::new();
+        tracker.process(Duration::from_secs(0), true, &open(9));
+        tracker.process(Duration::from_secs(0), false, &open(7));
+        tracker.finish(Duration::from_secs(60));
+        assert_eq!(tracker.phase(), SyncPhase::NotStarted);
+        assert_eq!(
+            tracker.issues(),
+            &[(
+                Duration::from_secs(60),
+                SyncIssue::IncompleteSync {
+                    kind: SyncKind::Delta,
+                    lsp_count: 0,
+                }
+            )]
+        );
+
+        // Nothing to synchronize without the stateful capability
+        let mut tracker = SyncTracker::new();
+        tracker.finish(Duration::from_secs(60));
+        assert!(tracker.issues().is_empty());
+    }
+}
@@ -0,0 +1,173 @@
+use colored::Colorize;
+use indoc::writedoc;
+
+use crate::common::OperationalStatus;
+use crate::objects::ero::EroObject;
+use crate::objects::metric::MetricObject;
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct StatusTransition {
+    pub timestamp: Duration,
+    pub from: OperationalStatus,
+    pub to: OperationalStatus,
+}
+
+impl std::fmt::Display for StatusTransition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{:.6}] {} -> {}",
+            self.timestamp.as_secs_f64(),
+            self.from,
+            self.to
+        )
+    }
+}
+
+// What the PCC last reported about one LSP, plus what the PCE asked for
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LspState {
+    pub plsp_id: u32,
+    pub symbolic_name: Option<String>,
+    pub operational_status: OperationalStatus,
+    pub administrative: bool,
+    pub delegated: bool,
+    pub pce_initiated: bool,
+    pub ero: Option<EroObject>,
+    pub metrics: Vec<MetricObject>,
+    pub requested_ero: Option<EroObject>,
+    pub last_srp_id: Option<u32>,
+    pub removal_requested: bool,
+    pub first_reported: Duration,
+    pub last_reported: Duration,
+    pub transitions: Vec<StatusTransition>,
+}
+
+impl LspState {
+    pub fn new(plsp_id: u32, timestamp: Duration, operational_status: OperationalStatus) -> Self {
+        LspState {
+            plsp_id,
+            symbolic_name: None,
+            operational_status,
+            administrative: false,
+            delegated: false,
+            pce_initiated: false,
+            ero: None,
+            metrics: vec![],
+            requested_ero: None,
+            last_srp_id: None,
+            removal_requested: false,
+            first_reported: timestamp,
+            last_reported: timestamp,
+            transitions: vec![],
+        }
+    }
+}
+
+impl std::fmt::Display for LspState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut transitions_str = String::new();
+        for transition in self.transitions.iter() {
+            transitions_str.push_str(&format!("        {}\n", transition));
+        }
+        let mut metrics_str = String::new();
+        for metric in self.metrics.iter() {
+            metrics_str.push_str(&format!(
+                "{}={}{} ",
+                metric.metric_type(),
+                metric.metric_value(),
+                metric.metric_type().unit()
+            ));
+        }
+        let ero_str = match self.ero {
+            Some(ref ero) => format!(
+                "{} subobject(s)",
+                ero.subobjects.as_ref().map_or(0, |s| s.len())
+            ),
+            None => String::from("-"),
+        };
+        let title = "==[LSP]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                plsp_id                      = {plsp_id}
+                symbolic_name                = {symbolic_name}
+                operational_status           = {operational_status}
+                administrative               = {administrative}
+                delegated                    = {delegated}
+                pce_initiated                = {pce_initiated}
+                ero                          = {ero_str}
+                metrics                      = {metrics_str}
+                last_srp_id                  = {last_srp_id}
+                removal_requested            = {removal_requested}
+                last_reported                = {last_reported:.6}
+                transitions
+            {transitions_str}"#,
+            title = title,
+            plsp_id = self.plsp_id,
+            symbolic_name = self.symbolic_name.as_deref().unwrap_or("-"),
+            operational_status = self.operational_status,
+            administrative = self.administrative,
+            delegated = self.delegated,
+            pce_initiated = self.pce_initiated,
+            ero_str = ero_str,
+            metrics_str = metrics_str,
+            last_srp_id = self
+                .last_srp_id
+                .map_or_else(|| String::from("-"), |id| id.to_string()),
+            removal_requested = self.removal_requested,
+            last_reported = self.last_reported.as_secs_f64(),
+            transitions_str = transitions_str
+        )
+    }
+}
+
+// Copy of the database at a given point of the message stream
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LspDbSnapshot {
+    pub timestamp: Option<Duration>,
+    pub position: usize,
+    pub lsps: BTreeMap<u32, LspState>,
+}
+
+impl LspDbSnapshot {
+    pub fn get(&self, plsp_id: u32) -> Option<&LspState> {
+        self.lsps.get(&plsp_id)
+    }
+
+    pub fn get_by_name(&self, symbolic_name: &str) -> Option<&LspState> {
+        self.lsps
+            .values()
+            .find(|lsp| lsp.symbolic_name.as_deref() == Some(symbolic_name))
+    }
+}
+
+impl std::fmt::Display for LspDbSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut lsps_str = String::new();
+        for lsp in self.lsps.values() {
+            lsps_str.push_str(&format!("{}", lsp));
+        }
+        let title = "##[LSP-DB Snapshot]##".yellow();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                timestamp                    = {timestamp}
+                messages applied             = {position}
+                lsps                         = {count}
+            {lsps_str}"#,
+            title = title,
+            timestamp = self
+                .timestamp
+                .map_or_else(|| String::from("-"), |t| format!("{:.6}", t.as_secs_f64())),
+            position = self.position,
+            count = self.lsps.len(),
+            lsps_str = lsps_str
+        )
+    }
+}
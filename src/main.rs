@@ -2,26 +2,76 @@ use std::error::Error;
 use std::fs::File;
 use std::io::Read;
 
-mod common;
-mod messages;
-mod objects;
-mod subobjects;
-//mod tlvs;
-mod tlvs;
+use pcep_parser::borrowed::MessageView;
+use pcep_parser::messages::header::CommonHeader;
+use pcep_parser::messages::keepalive::KeepAlive;
+use pcep_parser::messages::message::PcepMessage;
+use pcep_parser::messages::open::Open;
+use pcep_parser::messages::pcclose::PcClose;
+use pcep_parser::messages::pcerr::PcErr;
+use pcep_parser::messages::pcinitiate::PCInitiate;
+use pcep_parser::messages::pcrpt::PcRpt;
+use pcep_parser::messages::pcupdate::PcepUpdate;
+use pcep_parser::messages::types::MessageType;
+use pcep_parser::objects::any::PcepObject;
+use pcep_parser::objects::open::OpenObject;
 
-use messages::header::CommonHeader;
-use messages::keepalive::KeepAlive;
-use messages::open::Open;
-use messages::pcinitiate::PCInitiate;
-use messages::pcupdate::PcepUpdate;
-use messages::types::MessageType;
-use objects::open::OpenObject;
+// Every object of every message in the packet, in wire order and
+// whatever the message type
+fn print_objects(contents: &[u8]) {
+    let mut left = contents;
+    while !left.is_empty() {
+        let view = match MessageView::parse_complete(left) {
+            Ok((remaining, view)) => {
+                left = remaining;
+                view
+            }
+            Err(e) => {
+                println!("[!!] {}", e);
+                return;
+            }
+        };
+        println!("[+] {} objects", view.message_type());
+        for object in PcepObject::objects(view.body()) {
+            match object {
+                Ok(object) => print!("{}", object),
+                Err(e) => println!("[!!] {}", e),
+            }
+        }
+    }
+}
+
+// One line per message of the packet
+fn print_summaries(contents: &[u8]) {
+    let mut left = contents;
+    while !left.is_empty() {
+        match PcepMessage::parse_complete(left) {
+            Ok((remaining, message)) => {
+                left = remaining;
+                println!("{}", message.summary());
+            }
+            Err(e) => {
+                println!("[!!] {}", e);
+                return;
+            }
+        }
+    }
+}
 
 fn main() -> Result<(), Box<dyn Error>> {
     let mut f = File::open("tmp_packet")?;
     let mut contents: Vec<u8> = vec![];
     f.read_to_end(&mut contents)?;
 
+    if std::env::args().any(|arg| arg == "--objects") {
+        print_objects(&contents);
+        return Ok(());
+    }
+    if std::env::args().any(|arg| arg == "--summary") {
+        print_summaries(&contents);
+        return Ok(());
+    }
+
     let (remaining, common_header) = match CommonHeader::parse_common_header(&contents) {
         Ok((remaining, header)) => (remaining, header),
         Err(e) => panic!("{:?}", e),
@@ -44,6 +94,16 @@ fn main() -> Result<(), Box<dyn Error>> {
             println!("[+] Pcep keepalive message..");
             print!("{}", keepalive_msg);
         }
+        MessageType::PCRpt => match PcRpt::parse_report_message(remaining) {
+            Ok((_remaining, mut report_message)) => {
+                println!("[+] Pcep PCRpt message");
+                report_message.common_header = common_header;
+                print!("{}", report_message);
+            }
+            Err(e) => {
+                panic!("{:#?}", e)
+            }
+        },
         MessageType::PCUpd => match PcepUpdate::parse_update_message(remaining) {
             Ok((_remaining, mut update_message)) => {
                 println!("[+] Pcep PCupdate message");
@@ -64,6 +124,26 @@ fn main() -> Result<(), Box<dyn Error>> {
                 panic!("{:#?}", e)
             }
         },
+        MessageType::PCErr => match PcErr::parse_error_message(remaining) {
+            Ok((_remaining, mut error_message)) => {
+                println!("[+] Pcep PCErr message");
+                error_message.common_header = common_header;
+                print!("{}", error_message);
+            }
+            Err(e) => {
+                panic!("{:#?}", e)
+            }
+        },
+        MessageType::PCClose => match PcClose::parse_close_message(remaining) {
+            Ok((_remaining, mut close_message)) => {
+                println!("[+] Pcep PCClose message");
+                close_message.common_header = common_header;
+                print!("{}", close_message);
+            }
+            Err(e) => {
+                panic!("{:#?}", e)
+            }
+        },
         _ => {
             println!("[!!]Unknown message type detected");
         }
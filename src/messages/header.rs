@@ -2,10 +2,10 @@ use colored::Colorize;
 use indoc::writedoc;
 use nom::bits;
 use nom::combinator::map_res;
-use nom::error::Error;
+use nom::error::{Error, ErrorKind};
 use nom::number;
 use nom::sequence::tuple;
-use nom::IResult;
+use nom::{Err, IResult};
 
 use crate::common::Version;
 use crate::messages::types::MessageType;
@@ -40,14 +40,27 @@ impl std::fmt::Display for CommonHeader {
 }
 
 impl CommonHeader {
+    // Header for a locally built message, message_length is set when encoding
+    pub fn new(message_type: MessageType) -> Self {
+        CommonHeader {
+            version: Version::One,
+            flags: 0,
+            message_type,
+            message_length: 0,
+        }
+    }
+
     pub fn parse_common_header(input: &[u8]) -> IResult<&[u8], Self> {
-        let (input, ver_flags) = Self::parse_version_flags(input)?;
+        let (remaining, ver_flags) = Self::parse_version_flags(input)?;
+        let version = Version::try_from(ver_flags.0)
+            .map_err(|_version| Err::Failure(Error::new(input, ErrorKind::Verify)))?;
+        let input = remaining;
         let (input, message_type): (&[u8], MessageType) =
             map_res(number::streaming::be_u8, |val| val.try_into())(input)?;
         let (input, message_length) = number::streaming::be_u16(input)?;
         //let message_type: MessageType = message_types.try_into().unwrap();
         let header = CommonHeader {
-            version: ver_flags.0.into(),
+            version,
             flags: ver_flags.1,
             message_type,
             message_length,
@@ -80,4 +93,13 @@ pub mod tests {
         assert_eq!(common_header, expected);
         assert_eq!(remaing, EMPTY_SLICE);
     }
+
+    #[test]
+    fn test_ch_unknown_version() {
+        let input: &[u8] = &[0x40, 0x02, 0x00, 0x04];
+        assert!(matches!(
+            CommonHeader::parse_common_header(input),
+            Err(Err::Failure(e)) if e.code == ErrorKind::Verify
+        ));
+    }
 }
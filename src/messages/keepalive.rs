@@ -1,4 +1,6 @@
+use crate::encoder::{Encode, Encoder};
 use crate::messages::header::CommonHeader;
+use crate::messages::types::MessageType;
 use colored::Colorize;
 use indoc::writedoc;
 
@@ -13,6 +15,24 @@ impl From<CommonHeader> for KeepAlive {
     }
 }
 
+impl KeepAlive {
+    pub fn new() -> Self {
+        KeepAlive {
+            common_header: CommonHeader::new(MessageType::Keepalive),
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        String::from("Keepalive")
+    }
+}
+
+impl Default for KeepAlive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl std::fmt::Display for KeepAlive {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let title = "##[KeepAlive]##".yellow();
@@ -28,11 +48,16 @@ impl std::fmt::Display for KeepAlive {
     }
 }
 
+impl Encode for KeepAlive {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        Encoder::encode_common_header(buf, self.common_header.flags, MessageType::Keepalive, &[]);
+    }
+}
+
 #[cfg(test)]
 pub mod test {
     use super::*;
     use crate::common::Version;
-    use crate::messages::types::MessageType;
     const EMPTY_SLICE: &[u8] = &[];
     #[test]
     fn test_keepalive_message_parsing() {
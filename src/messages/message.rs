@@ -0,0 +1,511 @@
+use crate::encoder::{Encode, Encoder};
+use crate::error::{self, ParseError};
+use crate::messages::header::CommonHeader;
+use crate::messages::keepalive::KeepAlive;
+use crate::messages::open::Open;
+use crate::messages::pcclose::PcClose;
+use crate::messages::pcerr::PcErr;
+use crate::messages::pcinitiate::PCInitiate;
+use crate::messages::pcrpt::PcRpt;
+use crate::messages::pcupdate::PcepUpdate;
+use crate::messages::types::MessageType;
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+use crate::options::{Diagnostic, DiagnosticKind, Diagnostics, ParseOptions};
+use crate::validate::{self, LengthIssueKind};
+
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::{Err, IResult};
+
+// Any PCEP message, decoded according to its common header
+#[derive(Debug, PartialEq, Eq)]
+pub enum PcepMessage {
+    Open(Open),
+    Keepalive(KeepAlive),
+    PcRpt(PcRpt),
+    PcUpd(PcepUpdate),
+    PcInitiate(PCInitiate),
+    PcErr(PcErr),
+    PcClose(PcClose),
+    // Message types without a decoder yet, kept as raw body
+    Unsupported(CommonHeader, Vec<u8>),
+}
+
+impl PcepMessage {
+    // Parse one message from a stream: Err::Incomplete carries the bytes
+    // still missing from the header or the message_length. Once the whole
+    // message is buffered, a parser running short inside it is a Failure.
+    pub fn parse_message(input: &[u8]) -> IResult<&[u8], Self> {
+        let options = ParseOptions {
+            check_lengths: false,
+            ..ParseOptions::lenient()
+        };
+        Self::parse_message_diagnosed(input, &mut Diagnostics::new(input, options))
+    }
+
+    // parse_message with the diagnostics of the message, strict options
+    // turn the first of them into a Failure
+    pub fn parse_message_with(
+        input: &[u8],
+        options: ParseOptions,
+    ) -> IResult<&[u8], (Self, Vec<Diagnostic>)> {
+        let mut diagnostics = Diagnostics::new(input, options);
+        let (remaining, message) = Self::parse_message_diagnosed(input, &mut diagnostics)?;
+        Ok((remaining, (message, diagnostics.into_vec())))
+    }
+
+    fn parse_message_diagnosed<'a>(
+        input: &'a [u8],
+        diagnostics: &mut Diagnostics<'a>,
+    ) -> IResult<&'a [u8], Self> {
+        let (remaining, common_header) = CommonHeader::parse_common_header(input)?;
+        if common_header.message_length < 4 {
+            return Err(Err::Failure(Error::new(input, ErrorKind::LengthValue)));
+        }
+        let body_len = common_header.message_length as usize - 4;
+        let (remaining, body) = bytes::streaming::take(body_len)(remaining)?;
+        if common_header.flags != 0 {
+            let kind = DiagnosticKind::ReservedBits {
+                field: "message flags",
+                value: common_header.flags as u32,
+            };
+            diagnostics.report(input, kind)?;
+        }
+        if diagnostics.options().check_lengths {
+            let message = &input[..input.len() - remaining.len()];
+            for issue in validate::check_lengths(message) {
+                // Trailing objects are reported one by one below
+                if let LengthIssueKind::UnparsedBody { .. } = issue.kind {
+                    continue;
+                }
+                diagnostics.report(&input[issue.offset..], DiagnosticKind::Length(issue.kind))?;
+            }
+        }
+        Self::check_object_headers(body, diagnostics)?;
+        let (mut left, message) = error::complete(body, |body| {
+            Self::parse_body(common_header, body, diagnostics)
+        })?;
+        while !left.is_empty() {
+            match error::complete(left, |left| diagnostics.skip_object(left)) {
+                Ok((remaining, ())) => left = remaining,
+                Err(err) if diagnostics.options().strict => return Err(err),
+                Err(_err) => {
+                    let kind = DiagnosticKind::Length(LengthIssueKind::UnparsedBody {
+                        length: left.len(),
+                    });
+                    diagnostics.report(left, kind)?;
+                    break;
+                }
+            }
+        }
+        Ok((remaining, message))
+    }
+
+    // Unknown classes and reserved bits of the object headers, whatever
+    // the message grammar makes of the objects themselves
+    fn check_object_headers<'a>(
+        body: &'a [u8],
+        diagnostics: &mut Diagnostics<'a>,
+    ) -> Result<(), Err<Error<&'a [u8]>>> {
+        let mut left = body;
+        while let Ok((_remaining, cobj)) = CommonObject::parse_common_object(left) {
+            if let ObjectClassType::Unknown((object_class, object_type)) = cobj.object_class_type {
+                let kind = DiagnosticKind::UnknownObject {
+                    object_class,
+                    object_type,
+                };
+                diagnostics.report(left, kind)?;
+            }
+            diagnostics.check_reserved(left, "object header", cobj.reserved as u32)?;
+            match left.get(cobj.object_length as usize..) {
+                Some(remaining) => left = remaining,
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    // Parse one message from a buffer holding all the input there is,
+    // truncation is an error with the offset it was detected at
+    pub fn parse_complete(input: &[u8]) -> Result<(&[u8], Self), ParseError> {
+        error::parse_complete(input, Self::parse_message)
+    }
+
+    // Decode the body according to the header, the remaining bytes are
+    // the part of the body the message format has no place for
+    pub(crate) fn parse_body<'a>(
+        common_header: CommonHeader,
+        body: &'a [u8],
+        diagnostics: &mut Diagnostics<'a>,
+    ) -> IResult<&'a [u8], Self> {
+        let (body, message) = match common_header.message_type {
+            MessageType::Open => {
+                let (body, open_message) =
+                    Open::parse_open_message_with(common_header, body, diagnostics)?;
+                (body, Self::Open(open_message))
+            }
+            MessageType::Keepalive => (body, Self::Keepalive(common_header.into())),
+            MessageType::PCRpt => {
+                let (body, mut report_message) =
+                    PcRpt::parse_report_message_with(body, diagnostics)?;
+                report_message.common_header = common_header;
+                (body, Self::PcRpt(report_message))
+            }
+            MessageType::PCUpd => {
+                let (body, mut update_message) =
+                    PcepUpdate::parse_update_message_with(body, diagnostics)?;
+                update_message.common_header = common_header;
+                (body, Self::PcUpd(update_message))
+            }
+            MessageType::PCInitiate => {
+                let (body, mut pc_initiate_message) =
+                    PCInitiate::parse_pcinitiate_message_with(body, diagnostics)?;
+                pc_initiate_message.common_header = common_header;
+                (body, Self::PcInitiate(pc_initiate_message))
+            }
+            MessageType::PCErr => {
+                let (body, mut error_message) = PcErr::parse_error_message_with(body, diagnostics)?;
+                error_message.common_header = common_header;
+                (body, Self::PcErr(error_message))
+            }
+            MessageType::PCClose => {
+                let (body, mut close_message) = PcClose::parse_close_message(body)?;
+                close_message.common_header = common_header;
+                (body, Self::PcClose(close_message))
+            }
+            _ => (
+                &body[body.len()..],
+                Self::Unsupported(common_header, body.to_vec()),
+            ),
+        };
+        Ok((body, message))
+    }
+
+    // One line per message instead of the multi line Display
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Open(x) => x.summary(),
+            Self::Keepalive(x) => x.summary(),
+            Self::PcRpt(x) => x.summary(),
+            Self::PcUpd(x) => x.summary(),
+            Self::PcInitiate(x) => x.summary(),
+            Self::PcErr(x) => x.summary(),
+            Self::PcClose(x) => x.summary(),
+            Self::Unsupported(common_header, body) => {
+                format!("{:?} body={} bytes", common_header.message_type, body.len())
+            }
+        }
+    }
+
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            Self::Open(_) => MessageType::Open,
+            Self::Keepalive(_) => MessageType::Keepalive,
+            Self::PcRpt(_) => MessageType::PCRpt,
+            Self::PcUpd(_) => MessageType::PCUpd,
+            Self::PcInitiate(_) => MessageType::PCInitiate,
+            Self::PcErr(_) => MessageType::PCErr,
+            Self::PcClose(_) => MessageType::PCClose,
+            Self::Unsupported(common_header, _) => common_header.message_type,
+        }
+    }
+}
+
+impl std::fmt::Display for PcepMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Open(x) => write!(f, "{}", x),
+            Self::Keepalive(x) => write!(f, "{}", x),
+            Self::PcRpt(x) => write!(f, "{}", x),
+            Self::PcUpd(x) => write!(f, "{}", x),
+            Self::PcInitiate(x) => write!(f, "{}", x),
+            Self::PcErr(x) => write!(f, "{}", x),
+            Self::PcClose(x) => write!(f, "{}", x),
+            Self::Unsupported(common_header, _) => write!(f, "{}", common_header),
+        }
+    }
+}
+
+impl Encode for PcepMessage {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::Open(x) => x.encode(buf),
+            Self::Keepalive(x) => x.encode(buf),
+            Self::PcRpt(x) => x.encode(buf),
+            Self::PcUpd(x) => x.encode(buf),
+            Self::PcInitiate(x) => x.encode(buf),
+            Self::PcErr(x) => x.encode(buf),
+            Self::PcClose(x) => x.encode(buf),
+            Self::Unsupported(common_header, body) => Encoder::encode_common_header(
+                buf,
+                common_header.flags,
+                common_header.message_type,
+                body,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::builder::{adj, sr_hop, LspBuilder};
+    use crate::error::ParseErrorKind;
+    use crate::objects::types::{BandwidthObjectType, CloseObjectType, ErrorObjectType};
+    use std::net::Ipv4Addr;
+    #[test]
+    fn test_pcep_message_parsing() {
+        let input: &[u8] = &[
+            0x20, 0x02, 0x00, 0x04, 0x20, 0x01, 0x00, 0x0c, 0x01, 0x10, 0x00, 0x08, 0x20, 0x1e,
+            0x78, 0x01,
+        ];
+        let (remaining, message) =
+            PcepMessage::parse_message(input).expect("[!!] Error while parsing pcep message");
+        assert_eq!(message.message_type(), MessageType::Keepalive);
+        let (remaining, message) =
+            PcepMessage::parse_message(remaining).expect("[!!] Error while parsing pcep message");
+        assert!(remaining.is_empty());
+        match message {
+            PcepMessage::Open(open) => assert_eq!(open.open_object().deadtimer, 120),
+            _ => panic!("[!!] Expected an Open message"),
+        }
+    }
+
+    #[test]
+    fn test_pcep_message_truncation() {
+        let input: &[u8] = &[
+            0x20, 0x02, 0x00, 0x04, 0x20, 0x01, 0x00, 0x0c, 0x01, 0x10, 0x00, 0x08, 0x20, 0x1e,
+            0x78, 0x01,
+        ];
+        // Streaming: a message cut short asks for the rest of it
+        assert_eq!(
+            PcepMessage::parse_message(&input[4..10]),
+            Err(Err::Incomplete(nom::Needed::new(6)))
+        );
+        assert_eq!(
+            PcepMessage::parse_message(&input[4..6]),
+            Err(Err::Incomplete(nom::Needed::new(2)))
+        );
+        // Complete: the same input is an error at the end of the buffer
+        assert_eq!(
+            PcepMessage::parse_complete(&input[4..10]).unwrap_err(),
+            ParseError {
+                offset: 6,
+                kind: ParseErrorKind::Truncated { needed: Some(6) }
+            }
+        );
+
+        // Open object claiming 12 bytes inside a 12 byte message
+        let input: &[u8] = &[
+            0x20, 0x01, 0x00, 0x0c, 0x01, 0x10, 0x00, 0x0c, 0x20, 0x1e, 0x78, 0x01,
+        ];
+        assert!(matches!(
+            PcepMessage::parse_message(input),
+            Err(Err::Failure(_))
+        ));
+        assert_eq!(
+            PcepMessage::parse_complete(input).unwrap_err(),
+            ParseError {
+                offset: 12,
+                kind: ParseErrorKind::Truncated { needed: None }
+            }
+        );
+
+        // Message length shorter than the common header
+        let input: &[u8] = &[0x20, 0x02, 0x00, 0x02];
+        assert_eq!(
+            PcepMessage::parse_complete(input).unwrap_err(),
+            ParseError {
+                offset: 0,
+                kind: ParseErrorKind::Malformed(ErrorKind::LengthValue)
+            }
+        );
+    }
+
+    #[test]
+    fn test_pcep_message_options() {
+        // PCRpt starting with an unknown object (class 128), then an LSP
+        // object with reserved header bits set
+        let input: &[u8] = &[
+            0x20, 0x0a, 0x00, 0x14, 0x80, 0x10, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x20, 0x14,
+            0x00, 0x08, 0x00, 0x00, 0x10, 0x09,
+        ];
+        let (remaining, (message, diagnostics)) =
+            PcepMessage::parse_message_with(input, ParseOptions::lenient())
+                .expect("[!!] Error while parsing pcep message");
+        assert!(remaining.is_empty());
+        match message {
+            PcepMessage::PcRpt(pcrpt) => assert_eq!(pcrpt.state_reports().len(), 1),
+            _ => panic!("[!!] Expected a PCRpt message"),
+        }
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic {
+                    offset: 4,
+                    kind: DiagnosticKind::UnknownObject {
+                        object_class: 128,
+                        object_type: 1
+                    }
+                },
+                Diagnostic {
+                    offset: 12,
+                    kind: DiagnosticKind::ReservedBits {
+                        field: "object header",
+                        value: 1
+                    }
+                },
+            ]
+        );
+
+        // Strict mode stops at the first violation
+        match PcepMessage::parse_message_with(input, ParseOptions::strict()) {
+            Err(Err::Failure(e)) => {
+                assert_eq!(input.len() - e.input.len(), 4);
+                assert_eq!(e.code, ErrorKind::Verify);
+            }
+            _ => panic!("[!!] Expected a strict failure"),
+        }
+
+        // Trailing object after an Open message
+        let input: &[u8] = &[
+            0x20, 0x01, 0x00, 0x18, 0x01, 0x10, 0x00, 0x08, 0x20, 0x1e, 0x78, 0x01, 0x07, 0x10,
+            0x00, 0x0c, 0x01, 0x08, 0x0a, 0x00, 0x00, 0x01, 0x20, 0x00,
+        ];
+        let (_remaining, (_message, diagnostics)) =
+            PcepMessage::parse_message_with(input, ParseOptions::lenient())
+                .expect("[!!] Error while parsing pcep message");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].offset, 12);
+        assert!(matches!(
+            diagnostics[0].kind,
+            DiagnosticKind::UnexpectedObject(ObjectClassType::Ero(_))
+        ));
+    }
+
+    #[test]
+    fn test_pcep_message_options_grammars() {
+        let unexpected = |offset, object_class_type| Diagnostic {
+            offset,
+            kind: DiagnosticKind::UnexpectedObject(object_class_type),
+        };
+        let reserved = |offset, field| Diagnostic {
+            offset,
+            kind: DiagnosticKind::ReservedBits { field, value: 1 },
+        };
+        let cases: Vec<(&[u8], Vec<Diagnostic>)> = vec![
+            // Open behind a CLOSE object, with an unassigned OPEN flag set
+            (
+                &[
+                    0x20, 0x01, 0x00, 0x14, 0x0f, 0x10, 0x00, 0x08, 0x00, 0x00, 0x00, 0x01, 0x01,
+                    0x10, 0x00, 0x08, 0x21, 0x1e, 0x78, 0x01,
+                ],
+                vec![
+                    unexpected(4, ObjectClassType::Close(CloseObjectType::Close)),
+                    reserved(12, "OPEN flags"),
+                ],
+            ),
+            // PCUpd with a BANDWIDTH object ahead of the SRP object, the
+            // SRP object with an unassigned flag set
+            (
+                &[
+                    0x20, 0x0b, 0x00, 0x24, 0x05, 0x10, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x21,
+                    0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01, 0x20, 0x10,
+                    0x00, 0x08, 0x00, 0x00, 0x10, 0x09, 0x07, 0x10, 0x00, 0x04,
+                ],
+                vec![
+                    unexpected(
+                        4,
+                        ObjectClassType::Bandwidth(BandwidthObjectType::Requested),
+                    ),
+                    reserved(12, "SRP flags"),
+                ],
+            ),
+            // PCInitiate with a CLOSE object ahead of the SRP object
+            (
+                &[
+                    0x20, 0x0c, 0x00, 0x24, 0x0f, 0x10, 0x00, 0x08, 0x00, 0x00, 0x00, 0x01, 0x21,
+                    0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x20, 0x10,
+                    0x00, 0x08, 0x00, 0x00, 0x00, 0x09, 0x07, 0x10, 0x00, 0x04,
+                ],
+                vec![unexpected(
+                    4,
+                    ObjectClassType::Close(CloseObjectType::Close),
+                )],
+            ),
+            // PCErr with an ERROR flag set and an ERROR object after the OPEN
+            (
+                &[
+                    0x20, 0x06, 0x00, 0x1c, 0x0d, 0x10, 0x00, 0x08, 0x00, 0x01, 0x01, 0x04, 0x01,
+                    0x10, 0x00, 0x08, 0x20, 0x1e, 0x78, 0x01, 0x0d, 0x10, 0x00, 0x08, 0x00, 0x00,
+                    0x01, 0x01,
+                ],
+                vec![
+                    reserved(4, "ERROR flags"),
+                    unexpected(20, ObjectClassType::Error(ErrorObjectType::Error)),
+                ],
+            ),
+        ];
+        for (input, expected) in cases {
+            let (remaining, (message, diagnostics)) =
+                PcepMessage::parse_message_with(input, ParseOptions::lenient())
+                    .expect("[!!] Lenient mode did not skip the violations");
+            assert!(remaining.is_empty());
+            assert_eq!(diagnostics, expected, "[!!] {}", message.summary());
+
+            // Strict mode stops at the first violation
+            match PcepMessage::parse_message_with(input, ParseOptions::strict()) {
+                Err(Err::Failure(e)) => {
+                    assert_eq!(input.len() - e.input.len(), expected[0].offset);
+                    assert_eq!(e.code, ErrorKind::Verify);
+                }
+                _ => panic!("[!!] Expected a strict failure for {}", message.summary()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_pcep_message_summary() {
+        let pcep_update = PcepUpdate::builder()
+            .srp_id(12)
+            .lsp(
+                LspBuilder::new()
+                    .plsp_id(235)
+                    .name("cfg_PSADEL-5")
+                    .delegate(true)
+                    .administrative(true),
+            )
+            .ero(vec![sr_hop(
+                24001,
+                adj(
+                    Ipv4Addr::new(10, 104, 105, 2),
+                    Ipv4Addr::new(10, 104, 105, 1),
+                ),
+            )])
+            .bandwidth(125000.0)
+            .build();
+        assert_eq!(
+            PcepMessage::PcUpd(pcep_update).summary(),
+            "PCUpd srp=12 plsp=235 name=cfg_PSADEL-5 D A \
+             ero=[24001(10.104.105.2->10.104.105.1)] bw=1Mbps"
+        );
+
+        // Keepalive, then the PCInitiate round trip vector of encoder.rs
+        let input: &[u8] = &[
+            0x20, 0x02, 0x00, 0x04, 0x20, 0x0c, 0x00, 0x30, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x20, 0x10, 0x00, 0x10, 0x00, 0x00, 0x00, 0x09,
+            0x00, 0x11, 0x00, 0x03, 0x6c, 0x73, 0x70, 0x00, 0x04, 0x10, 0x00, 0x0c, 0x0a, 0x00,
+            0x00, 0x01, 0x0a, 0x00, 0x00, 0x02, 0x07, 0x10, 0x00, 0x04,
+        ];
+        let (remaining, keepalive) =
+            PcepMessage::parse_message(input).expect("[!!] Error while parsing pcep message");
+        assert_eq!(keepalive.summary(), "Keepalive");
+        let (_remaining, pc_initiate) =
+            PcepMessage::parse_message(remaining).expect("[!!] Error while parsing pcep message");
+        assert_eq!(
+            pc_initiate.summary(),
+            "PCInitiate srp=6 plsp=0 name=lsp D A 10.0.0.1->10.0.0.2 ero=[]"
+        );
+    }
+}
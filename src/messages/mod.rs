@@ -1,6 +1,10 @@
 pub mod header;
 pub mod keepalive;
+pub mod message;
 pub mod open;
+pub mod pcclose;
+pub mod pcerr;
 pub mod pcinitiate;
+pub mod pcrpt;
 pub mod pcupdate;
 pub mod types;
@@ -1,7 +1,13 @@
+use crate::encoder::{Encode, Encoder};
 use crate::messages::header::CommonHeader;
+use crate::messages::types::MessageType;
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
 use crate::objects::open::OpenObject;
+use crate::options::Diagnostics;
 use colored::Colorize;
 use indoc::writedoc;
+use nom::IResult;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Open {
@@ -16,6 +22,54 @@ impl Open {
             open_object,
         }
     }
+
+    // Objects ahead of the OPEN object are skipped in lenient mode and
+    // rejected in strict mode, as are unassigned OPEN flags
+    pub fn parse_open_message_with<'a>(
+        common_header: CommonHeader,
+        input: &'a [u8],
+        diagnostics: &mut Diagnostics<'a>,
+    ) -> IResult<&'a [u8], Self> {
+        let mut left = input;
+        while !matches!(
+            CommonObject::peek_object_class(left)?.1,
+            Some(ObjectClassType::Open(_)) | None
+        ) {
+            let (remaining, ()) = diagnostics.skip_object(left)?;
+            left = remaining;
+        }
+        let (remaining, open_object) = OpenObject::parse_open_object(left)?;
+        diagnostics.check_reserved(left, "OPEN flags", open_object.flags as u32)?;
+        Ok((remaining, Open::new(common_header, open_object)))
+    }
+
+    pub fn open_object(&self) -> &OpenObject {
+        &self.open_object
+    }
+
+    // One line form, e.g. Open sid=1 keepalive=30 deadtimer=120 stateful U I msd=10
+    pub fn summary(&self) -> String {
+        let open_object = &self.open_object;
+        let mut fields = vec![
+            String::from("Open"),
+            format!("sid={}", open_object.sid),
+            format!("keepalive={}", open_object.keepalive),
+            format!("deadtimer={}", open_object.deadtimer),
+        ];
+        if let Some(stateful) = open_object.stateful_capability() {
+            fields.push(String::from("stateful"));
+            if stateful.flag_lsp_update_capability {
+                fields.push(String::from("U"));
+            }
+            if stateful.flag_lsp_instantiate_capability {
+                fields.push(String::from("I"));
+            }
+        }
+        if let Some(sr_capability) = open_object.sr_capability() {
+            fields.push(format!("msd={}", sr_capability.max_sid_depth));
+        }
+        fields.join(" ")
+    }
 }
 
 impl std::fmt::Display for Open {
@@ -35,11 +89,17 @@ impl std::fmt::Display for Open {
     }
 }
 
+impl Encode for Open {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let body = self.open_object.to_bytes();
+        Encoder::encode_common_header(buf, self.common_header.flags, MessageType::Open, &body);
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
     use crate::common::Version;
-    use crate::messages::types::MessageType;
     use crate::objects::classes::ObjectClassType;
     use crate::objects::header::CommonObject;
     use crate::objects::open::OpenObject;
@@ -78,6 +138,7 @@ pub mod tests {
             tlv_type: 26,
             tlv_len: 4,
             reserved: 0,
+            flag_nai: false,
             flag_limit: false,
             max_sid_depth: 10,
         };
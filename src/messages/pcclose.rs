@@ -0,0 +1,61 @@
+use crate::encoder::{Encode, Encoder};
+use crate::messages::header::CommonHeader;
+use crate::messages::types::MessageType;
+use crate::objects::close::CloseObject;
+
+use colored::Colorize;
+use indoc::writedoc;
+use nom::IResult;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PcClose {
+    pub common_header: CommonHeader,
+    pub close_object: CloseObject,
+}
+
+impl PcClose {
+    pub fn new(close_object: CloseObject) -> Self {
+        PcClose {
+            common_header: CommonHeader::new(MessageType::PCClose),
+            close_object,
+        }
+    }
+
+    pub fn parse_close_message(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, close_object) = CloseObject::parse_close_object(input)?;
+        let pcep_close_msg = PcClose {
+            common_header: Default::default(),
+            close_object,
+        };
+        Ok((remaining, pcep_close_msg))
+    }
+
+    // One line form, e.g. PCClose reason=DeadTimerExpired
+    pub fn summary(&self) -> String {
+        format!("PCClose reason={:?}", self.close_object.reason)
+    }
+}
+
+impl std::fmt::Display for PcClose {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = "##[PCClose]##".yellow();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_header}
+                {close_object}
+            "#,
+            title = title,
+            common_header = self.common_header,
+            close_object = self.close_object
+        )
+    }
+}
+
+impl Encode for PcClose {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let body = self.close_object.to_bytes();
+        Encoder::encode_common_header(buf, self.common_header.flags, MessageType::PCClose, &body);
+    }
+}
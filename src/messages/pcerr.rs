@@ -0,0 +1,189 @@
+use crate::encoder::{Encode, Encoder};
+use crate::messages::header::CommonHeader;
+use crate::messages::types::MessageType;
+use crate::objects::classes::ObjectClassType;
+use crate::objects::error::ErrorObject;
+use crate::objects::header::CommonObject;
+use crate::objects::open::OpenObject;
+use crate::objects::rp::RpObject;
+use crate::objects::srp::SrpObject;
+use crate::options::{Diagnostics, ParseOptions};
+
+use colored::Colorize;
+use indoc::writedoc;
+use nom::error::{Error, ErrorKind};
+use nom::{Err, IResult};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PcErr {
+    pub common_header: CommonHeader,
+    // Request-ID-numbers (RP) or SRP-IDs (SRP) the errors refer to
+    pub rp_objects: Vec<RpObject>,
+    pub srp_objects: Vec<SrpObject>,
+    pub error_objects: Vec<ErrorObject>,
+    // Open object carrying acceptable session characteristics
+    pub open_object: Option<OpenObject>,
+}
+
+impl PcErr {
+    pub fn new(error_objects: Vec<ErrorObject>) -> Self {
+        PcErr {
+            common_header: CommonHeader::new(MessageType::PCErr),
+            rp_objects: vec![],
+            srp_objects: vec![],
+            error_objects,
+            open_object: None,
+        }
+    }
+
+    pub fn parse_error_message(input: &[u8]) -> IResult<&[u8], Self> {
+        let mut diagnostics = Diagnostics::new(input, ParseOptions::default());
+        Self::parse_error_message_with(input, &mut diagnostics)
+    }
+
+    // Objects the grammar has no place for, including anything after the
+    // OPEN object, are skipped in lenient mode and rejected in strict
+    // mode, as are the ERROR reserved field and flags
+    pub fn parse_error_message_with<'a>(
+        input: &'a [u8],
+        diagnostics: &mut Diagnostics<'a>,
+    ) -> IResult<&'a [u8], Self> {
+        let mut pcep_err_msg = PcErr {
+            common_header: Default::default(),
+            rp_objects: vec![],
+            srp_objects: vec![],
+            error_objects: vec![],
+            open_object: None,
+        };
+        let mut left = input;
+        while !left.is_empty() {
+            let (_remaining, cobj) = CommonObject::parse_common_object(left)?;
+            match cobj.object_class_type {
+                _ if pcep_err_msg.open_object.is_some() => {
+                    let (remaining, ()) = diagnostics.skip_object(left)?;
+                    left = remaining;
+                }
+                ObjectClassType::Rp(_) => {
+                    let (remaining, rp_object) = RpObject::parse_rp_object(left)?;
+                    pcep_err_msg.rp_objects.push(rp_object);
+                    left = remaining;
+                }
+                ObjectClassType::Srp(_) => {
+                    let (remaining, srp_object) = SrpObject::parse_srp_object(left)?;
+                    pcep_err_msg.srp_objects.push(srp_object);
+                    left = remaining;
+                }
+                ObjectClassType::Error(_) => {
+                    let (remaining, error_object) = ErrorObject::parse_error_object(left)?;
+                    diagnostics.check_reserved(
+                        left,
+                        "ERROR reserved",
+                        error_object.reserved as u32,
+                    )?;
+                    diagnostics.check_reserved(left, "ERROR flags", error_object.flags as u32)?;
+                    pcep_err_msg.error_objects.push(error_object);
+                    left = remaining;
+                }
+                ObjectClassType::Open(_) => {
+                    let (remaining, open_object) = OpenObject::parse_open_object(left)?;
+                    pcep_err_msg.open_object = Some(open_object);
+                    left = remaining;
+                }
+                _ => {
+                    let (remaining, ()) = diagnostics.skip_object(left)?;
+                    left = remaining;
+                }
+            }
+        }
+        if pcep_err_msg.error_objects.is_empty() {
+            return Err(Err::Failure(Error::new(input, ErrorKind::Fail)));
+        }
+        Ok((left, pcep_err_msg))
+    }
+
+    // One line form, e.g. PCErr srp=7 error=19/3
+    pub fn summary(&self) -> String {
+        let mut fields = vec![String::from("PCErr")];
+        fields.extend(
+            self.rp_objects
+                .iter()
+                .map(|rp| format!("rp={}", rp.request_id)),
+        );
+        fields.extend(self.srp_objects.iter().map(SrpObject::summary));
+        fields.extend(self.error_objects.iter().map(ErrorObject::summary));
+        if self.open_object.is_some() {
+            fields.push(String::from("open"));
+        }
+        fields.join(" ")
+    }
+}
+
+impl std::fmt::Display for PcErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut objects_str = String::new();
+        for rp_object in self.rp_objects.iter() {
+            objects_str.push_str(&format!("{}", rp_object));
+        }
+        for srp_object in self.srp_objects.iter() {
+            objects_str.push_str(&format!("{}", srp_object));
+        }
+        for error_object in self.error_objects.iter() {
+            objects_str.push_str(&format!("{}", error_object));
+        }
+        if let Some(ref open_object) = self.open_object {
+            objects_str.push_str(&format!("{}", open_object));
+        }
+        let title = "##[PCErr]##".yellow();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_header}
+                {objects_str}
+            "#,
+            title = title,
+            common_header = self.common_header,
+            objects_str = objects_str
+        )
+    }
+}
+
+impl Encode for PcErr {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = vec![];
+        for rp_object in self.rp_objects.iter() {
+            rp_object.encode(&mut body);
+        }
+        for srp_object in self.srp_objects.iter() {
+            srp_object.encode(&mut body);
+        }
+        for error_object in self.error_objects.iter() {
+            error_object.encode(&mut body);
+        }
+        if let Some(ref open_object) = self.open_object {
+            open_object.encode(&mut body);
+        }
+        Encoder::encode_common_header(buf, self.common_header.flags, MessageType::PCErr, &body);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[test]
+    fn test_pcerr_message_parsing() {
+        let input: &[u8] = &[
+            0x0d, 0x10, 0x00, 0x08, 0x00, 0x00, 0x01, 0x04, 0x01, 0x10, 0x00, 0x08, 0x20, 0x1e,
+            0x78, 0x01,
+        ];
+        let (remaining, pcerr) =
+            PcErr::parse_error_message(input).expect("[!!] Failed to parse PCErr message");
+        assert!(remaining.is_empty());
+        assert_eq!(pcerr.error_objects.len(), 1);
+        assert_eq!(pcerr.error_objects[0].error_type, 1);
+        assert_eq!(pcerr.error_objects[0].error_value, 4);
+        let open_object = pcerr.open_object.expect("[!!] Open object missing");
+        assert_eq!(open_object.keepalive, 30);
+        assert_eq!(open_object.deadtimer, 120);
+    }
+}
@@ -1,9 +1,17 @@
-use crate::common::IntendedAttrList;
+use crate::builder::PCInitiateBuilder;
+use crate::common::{IntendedAttrList, IntendedPathList};
+use crate::encoder::{Encode, Encoder};
 use crate::messages::header::CommonHeader;
+use crate::messages::types::MessageType;
+use crate::objects::classes::ObjectClassType;
 use crate::objects::endpoints::EndPointsObject;
-use crate::objects::ero::EroObject;
+use crate::objects::header::CommonObject;
 use crate::objects::lsp::LspObject;
 use crate::objects::srp::SrpObject;
+use crate::objects::unknown::{UnknownObject, UnknownObjects};
+use crate::options::{Diagnostics, ParseOptions};
+use crate::tlvs::tlv_set::SrPCECapabilityTLV;
+use crate::validate::{check_sr_ero, check_srp_id, Violation, ViolationKind};
 
 use colored::Colorize;
 use indoc::writedoc;
@@ -16,15 +24,99 @@ pub struct PCInitiate {
 }
 
 impl PCInitiate {
+    pub fn new(lsp_requests: Vec<PceInitiatedLspRequest>) -> Self {
+        PCInitiate {
+            common_header: CommonHeader::new(MessageType::PCInitiate),
+            pce_initiated_lsp_lst: PceInitiatedLspList { lsp_requests },
+        }
+    }
+
+    // Single request message with lengths filled in, see builder.rs
+    pub fn builder() -> PCInitiateBuilder {
+        PCInitiateBuilder::new()
+    }
+
     pub fn parse_pcinitiate_message(input: &[u8]) -> IResult<&[u8], Self> {
+        let mut diagnostics = Diagnostics::new(input, ParseOptions::default());
+        Self::parse_pcinitiate_message_with(input, &mut diagnostics)
+    }
+
+    // Objects that cannot start an LSP request are skipped in lenient
+    // mode and rejected in strict mode, as are unassigned SRP flags
+    pub fn parse_pcinitiate_message_with<'a>(
+        input: &'a [u8],
+        diagnostics: &mut Diagnostics<'a>,
+    ) -> IResult<&'a [u8], Self> {
         let (remaining, pce_initiated_lsp_lst) =
-            PceInitiatedLspList::parse_pce_initiated_lsp_list(input)?;
+            PceInitiatedLspList::parse_pce_initiated_lsp_list_with(input, diagnostics)?;
         let pc_initiate_message = PCInitiate {
             common_header: Default::default(),
             pce_initiated_lsp_lst,
         };
         Ok((remaining, pc_initiate_message))
     }
+
+    pub fn lsp_requests(&self) -> &[PceInitiatedLspRequest] {
+        &self.pce_initiated_lsp_lst.lsp_requests
+    }
+
+    // One line form, e.g.
+    // PCInitiate srp=6 plsp=0 name=lsp D A 10.0.0.1->10.0.0.2 ero=[]
+    pub fn summary(&self) -> String {
+        let lsp_requests: Vec<String> = self
+            .lsp_requests()
+            .iter()
+            .map(PceInitiatedLspRequest::summary)
+            .collect();
+        format!("PCInitiate {}", lsp_requests.join(" | "))
+    }
+
+    // RFC 8281 rules the grammar does not check, in request order
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (request, lsp_request) in self.lsp_requests().iter().enumerate() {
+            let violation = |kind| Violation { request, kind };
+            match lsp_request {
+                PceInitiatedLspRequest::LspInstantiation(instantiation) => {
+                    violations.extend(check_srp_id(request, instantiation.srp_object.srp_id));
+                    let lsp_object = &instantiation.lsp_object;
+                    if lsp_object.plsp_id != 0 {
+                        violations.push(violation(ViolationKind::NonZeroPlspId {
+                            plsp_id: lsp_object.plsp_id,
+                        }));
+                    }
+                    if lsp_object.symbolic_path_name().is_none() {
+                        violations.push(violation(ViolationKind::MissingSymbolicPathName));
+                    }
+                }
+                PceInitiatedLspRequest::LspDeletion(deletion) => {
+                    violations.extend(check_srp_id(request, deletion.srp_object.srp_id));
+                    let plsp_id = deletion.lsp_object.plsp_id;
+                    if !deletion.srp_object.flag_remove {
+                        violations.push(violation(ViolationKind::MissingRemoveFlag { plsp_id }));
+                    }
+                    if plsp_id == 0 {
+                        violations.push(violation(ViolationKind::ZeroPlspId));
+                    }
+                }
+            }
+        }
+        violations
+    }
+
+    // SR-ERO rules against the SR capability the PCC sent in its Open,
+    // see validate::check_sr_ero
+    pub fn validate_sr(&self, capability: &SrPCECapabilityTLV) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (request, lsp_request) in self.lsp_requests().iter().enumerate() {
+            if let PceInitiatedLspRequest::LspInstantiation(instantiation) = lsp_request {
+                for (_path_attrib, ero_object) in instantiation.ero_paths.paths.iter() {
+                    violations.extend(check_sr_ero(request, ero_object, capability));
+                }
+            }
+        }
+        violations
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -34,19 +126,34 @@ pub struct PceInitiatedLspList {
 
 impl PceInitiatedLspList {
     pub fn parse_pce_initiated_lsp_list(input: &[u8]) -> IResult<&[u8], Self> {
+        let mut diagnostics = Diagnostics::new(input, ParseOptions::default());
+        Self::parse_pce_initiated_lsp_list_with(input, &mut diagnostics)
+    }
+
+    pub fn parse_pce_initiated_lsp_list_with<'a>(
+        input: &'a [u8],
+        diagnostics: &mut Diagnostics<'a>,
+    ) -> IResult<&'a [u8], Self> {
         let mut lsp_requests = vec![];
         let mut left = input;
-        while left.first().is_some() {
-            match PceInitiatedLspRequest::parse_pce_initiated_lsp_request(left) {
-                Ok((remaining, pce_init_lsp_req)) => {
+        // Each request starts with an SRP object. Anything else (e.g. a
+        // VENDOR-INFORMATION object, not supported yet) is skipped.
+        while !left.is_empty() {
+            match CommonObject::peek_object_class(left)?.1 {
+                Some(ObjectClassType::Srp(_)) => {
+                    let (remaining, pce_init_lsp_req) =
+                        PceInitiatedLspRequest::parse_pce_initiated_lsp_request(left)?;
+                    diagnostics.check_reserved(
+                        left,
+                        "SRP flags",
+                        pce_init_lsp_req.srp_object().flags,
+                    )?;
                     left = remaining;
                     lsp_requests.push(pce_init_lsp_req);
                 }
-                Err(_e) => {
-                    // For now breaking out of loop
-                    // As no support for the VENDOROBJECT
-                    // TODO: Handle errors gracefully
-                    break;
+                _ => {
+                    let (remaining, ()) = diagnostics.skip_object(left)?;
+                    left = remaining;
                 }
             }
         }
@@ -62,6 +169,20 @@ pub enum PceInitiatedLspRequest {
 }
 
 impl PceInitiatedLspRequest {
+    pub fn summary(&self) -> String {
+        match self {
+            Self::LspInstantiation(instantiation) => instantiation.summary(),
+            Self::LspDeletion(deletion) => deletion.summary(),
+        }
+    }
+
+    pub fn srp_object(&self) -> &SrpObject {
+        match self {
+            Self::LspInstantiation(instantiation) => instantiation.srp_object(),
+            Self::LspDeletion(deletion) => deletion.srp_object(),
+        }
+    }
+
     pub fn parse_pce_initiated_lsp_request(input: &[u8]) -> IResult<&[u8], Self> {
         let (_input, srp_object) = SrpObject::parse_srp_object(input)?;
         match srp_object.flag_remove {
@@ -89,33 +210,101 @@ pub struct PceInitiateLspInstatiation {
     srp_object: SrpObject,
     lsp_object: LspObject,
     endpoints_object: Option<EndPointsObject>,
-    ero_object: EroObject,
+    ero_paths: IntendedPathList,
     attr_lst: Option<IntendedAttrList>,
+    unknown_objects: UnknownObjects,
 }
 
 impl PceInitiateLspInstatiation {
+    pub fn new(
+        srp_object: SrpObject,
+        lsp_object: LspObject,
+        endpoints_object: Option<EndPointsObject>,
+        ero_paths: IntendedPathList,
+        attr_lst: Option<IntendedAttrList>,
+    ) -> Self {
+        PceInitiateLspInstatiation {
+            srp_object,
+            lsp_object,
+            endpoints_object,
+            ero_paths,
+            attr_lst,
+            unknown_objects: UnknownObjects::new(),
+        }
+    }
+
+    // Unknown objects are accepted between the known ones, see
+    // UnknownObject::parse_unknown_objects
     fn parse_pce_initiated_lsp_instantiation(input: &[u8]) -> IResult<&[u8], Self> {
+        let mut unknown_objects = UnknownObjects::new();
         let (remaining, srp_object) = SrpObject::parse_srp_object(input)?;
+        let (remaining, unknown) = UnknownObject::parse_unknown_objects(remaining)?;
+        unknown_objects.push(0, unknown);
         let (remaining, lsp_object) = LspObject::parse_lsp_object(remaining)?;
-        let (remaining, endpoints_object) = match EndPointsObject::parse_endpoints_object(remaining)
-        {
-            Ok((remaining, endpoints_obj)) => (remaining, Some(endpoints_obj)),
-            Err(_e) => (remaining, None),
-        };
-        let (remaining, ero_object) = EroObject::parse_ero_object(remaining)?;
-        let (remaining, attr_lst) = match IntendedAttrList::parse_intended_attr_list(remaining) {
-            Ok((remaining, attr_lst)) => (remaining, Some(attr_lst)),
-            Err(_e) => (remaining, None),
+        let (remaining, unknown) = UnknownObject::parse_unknown_objects(remaining)?;
+        unknown_objects.push(1, unknown);
+        let (remaining, endpoints_object) = match CommonObject::peek_object_class(remaining)? {
+            (remaining, Some(ObjectClassType::EndPoints(_))) => {
+                let (remaining, endpoints_obj) =
+                    EndPointsObject::parse_endpoints_object(remaining)?;
+                (remaining, Some(endpoints_obj))
+            }
+            (remaining, _) => (remaining, None),
         };
+        let (remaining, unknown) = UnknownObject::parse_unknown_objects(remaining)?;
+        unknown_objects.push(2, unknown);
+        let (remaining, ero_paths) = IntendedPathList::parse_intended_path_list(remaining)?;
+        let (remaining, unknown) = UnknownObject::parse_unknown_objects(remaining)?;
+        unknown_objects.push(3, unknown);
+        let (remaining, attr_lst) = IntendedAttrList::parse_intended_attr_list(remaining)?;
+        let (remaining, unknown) = UnknownObject::parse_unknown_objects(remaining)?;
+        unknown_objects.push(4, unknown);
         let pce_init_lsp_instantiation = PceInitiateLspInstatiation {
             srp_object,
             lsp_object,
             endpoints_object,
-            ero_object,
-            attr_lst,
+            ero_paths,
+            attr_lst: Some(attr_lst),
+            unknown_objects,
         };
         Ok((remaining, pce_init_lsp_instantiation))
     }
+
+    pub fn srp_object(&self) -> &SrpObject {
+        &self.srp_object
+    }
+
+    pub fn lsp_object(&self) -> &LspObject {
+        &self.lsp_object
+    }
+
+    pub fn endpoints_object(&self) -> Option<&EndPointsObject> {
+        self.endpoints_object.as_ref()
+    }
+
+    pub fn ero_paths(&self) -> &IntendedPathList {
+        &self.ero_paths
+    }
+
+    pub fn attr_lst(&self) -> Option<&IntendedAttrList> {
+        self.attr_lst.as_ref()
+    }
+
+    pub fn unknown_objects(&self) -> &[UnknownObject] {
+        self.unknown_objects.objects()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut fields = vec![self.srp_object.summary(), self.lsp_object.summary()];
+        fields.extend(self.endpoints_object.iter().map(EndPointsObject::summary));
+        fields.push(self.ero_paths.summary());
+        fields.extend(self.attr_lst.iter().map(IntendedAttrList::summary));
+        if !self.unknown_objects.is_empty() {
+            fields.push(format!("unknown={}", self.unknown_objects.len()));
+        }
+        fields.retain(|field| !field.is_empty());
+        fields.join(" ")
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -125,6 +314,13 @@ pub struct PceInitiatedLspDeletion {
 }
 
 impl PceInitiatedLspDeletion {
+    pub fn new(srp_object: SrpObject, lsp_object: LspObject) -> Self {
+        PceInitiatedLspDeletion {
+            srp_object,
+            lsp_object,
+        }
+    }
+
     fn parse_initiated_deletion(input: &[u8]) -> IResult<&[u8], Self> {
         let (remaining, srp_object) = SrpObject::parse_srp_object(input)?;
         let (remaining, lsp_object) = LspObject::parse_lsp_object(remaining)?;
@@ -134,6 +330,22 @@ impl PceInitiatedLspDeletion {
         };
         Ok((remaining, pce_init_lsp_deletion))
     }
+
+    pub fn srp_object(&self) -> &SrpObject {
+        &self.srp_object
+    }
+
+    pub fn lsp_object(&self) -> &LspObject {
+        &self.lsp_object
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} {}",
+            self.srp_object.summary(),
+            self.lsp_object.summary()
+        )
+    }
 }
 
 // Display trait for all complex types
@@ -165,24 +377,31 @@ impl std::fmt::Display for PceInitiateLspInstatiation {
         } else {
             String::new()
         };
+        let mut unknown_objects_str = String::new();
+        for unknown_object in self.unknown_objects.objects() {
+            unknown_objects_str.push_str(&format!("{}", unknown_object));
+        }
         writedoc!(
             f,
             r#"
                 {srp_object}
                 {:indent$}{lsp_object}
                 {:indent$}{end_points_obj_str}
-                {:indent$}{ero_object}
+                {:indent$}{ero_paths}
                 {:indent$}{attr_lst_str}
+                {:indent$}{unknown_objects_str}
             "#,
             "",
             "",
             "",
             "",
+            "",
             srp_object = self.srp_object,
             lsp_object = self.lsp_object,
             end_points_obj_str = end_points_obj_str,
-            ero_object = self.ero_object,
+            ero_paths = self.ero_paths,
             attr_lst_str = attr_lst_str,
+            unknown_objects_str = unknown_objects_str,
             indent = 4
         )
     }
@@ -233,3 +452,53 @@ impl std::fmt::Display for PCInitiate {
         )
     }
 }
+
+impl Encode for PCInitiate {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = vec![];
+        for lsp_request in self.lsp_requests() {
+            lsp_request.encode(&mut body);
+        }
+        Encoder::encode_common_header(
+            buf,
+            self.common_header.flags,
+            MessageType::PCInitiate,
+            &body,
+        );
+    }
+}
+
+impl Encode for PceInitiatedLspRequest {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::LspInstantiation(lsp_inst_req) => lsp_inst_req.encode(buf),
+            Self::LspDeletion(lsp_del_req) => lsp_del_req.encode(buf),
+        }
+    }
+}
+
+impl Encode for PceInitiateLspInstatiation {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.srp_object.encode(buf);
+        self.unknown_objects.encode_gap(0, buf);
+        self.lsp_object.encode(buf);
+        self.unknown_objects.encode_gap(1, buf);
+        if let Some(ref endpoints_object) = self.endpoints_object {
+            endpoints_object.encode(buf);
+        }
+        self.unknown_objects.encode_gap(2, buf);
+        self.ero_paths.encode(buf);
+        self.unknown_objects.encode_gap(3, buf);
+        if let Some(ref attr_lst) = self.attr_lst {
+            attr_lst.encode(buf);
+        }
+        self.unknown_objects.encode_gap(4, buf);
+    }
+}
+
+impl Encode for PceInitiatedLspDeletion {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.srp_object.encode(buf);
+        self.lsp_object.encode(buf);
+    }
+}
@@ -0,0 +1,333 @@
+use crate::encoder::{Encode, Encoder};
+use crate::messages::header::CommonHeader;
+use crate::messages::pcupdate::Path;
+use crate::messages::types::MessageType;
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+use crate::objects::lsp::LspObject;
+use crate::objects::srp::SrpObject;
+use crate::objects::unknown::{UnknownObject, UnknownObjects};
+use crate::options::{Diagnostics, ParseOptions};
+use crate::validate::{check_srp_id, Violation, ViolationKind};
+
+use colored::Colorize;
+use indoc::writedoc;
+use nom::bytes;
+use nom::IResult;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PcRpt {
+    pub common_header: CommonHeader,
+    state_report_lst: Vec<StateReport>,
+}
+
+impl PcRpt {
+    pub fn new(state_reports: Vec<StateReport>) -> Self {
+        PcRpt {
+            common_header: CommonHeader::new(MessageType::PCRpt),
+            state_report_lst: state_reports,
+        }
+    }
+
+    pub fn parse_report_message(input: &[u8]) -> IResult<&[u8], Self> {
+        let mut diagnostics = Diagnostics::new(input, ParseOptions::default());
+        Self::parse_report_message_with(input, &mut diagnostics)
+    }
+
+    // Objects that cannot start a state report are skipped in lenient
+    // mode and rejected in strict mode
+    pub fn parse_report_message_with<'a>(
+        input: &'a [u8],
+        diagnostics: &mut Diagnostics<'a>,
+    ) -> IResult<&'a [u8], Self> {
+        let mut state_report_lst = vec![];
+        let mut left = input;
+        while !left.is_empty() {
+            match CommonObject::peek_object_class(left)?.1 {
+                Some(ObjectClassType::Srp(_)) | Some(ObjectClassType::Lsp(_)) => {
+                    let (remaining, state_report) = StateReport::parse_state_report(left)?;
+                    left = remaining;
+                    state_report_lst.push(state_report);
+                }
+                _ => {
+                    let (remaining, ()) = diagnostics.skip_object(left)?;
+                    left = remaining;
+                }
+            }
+        }
+        let pcep_report_msg = PcRpt {
+            common_header: Default::default(),
+            state_report_lst,
+        };
+        Ok((left, pcep_report_msg))
+    }
+
+    pub fn state_reports(&self) -> &[StateReport] {
+        &self.state_report_lst
+    }
+
+    // One line form, the reports separated by |, e.g.
+    // PCRpt srp=12 plsp=235 name=cfg_PSADEL-5 D A oper=up ero=[10.0.0.2]
+    pub fn summary(&self) -> String {
+        let state_reports: Vec<String> = self
+            .state_reports()
+            .iter()
+            .map(StateReport::summary)
+            .collect();
+        format!("PCRpt {}", state_reports.join(" | "))
+    }
+
+    // RFC 8231 rules the grammar does not check, in report order
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (request, state_report) in self.state_reports().iter().enumerate() {
+            if let Some(srp_object) = state_report.srp_object() {
+                violations.extend(check_srp_id(request, srp_object.srp_id));
+            }
+            let lsp_object = state_report.lsp_object();
+            if !state_report.is_end_of_sync() && !lsp_object.has_lsp_identifiers() {
+                violations.push(Violation {
+                    request,
+                    kind: ViolationKind::MissingLspIdentifiers {
+                        plsp_id: lsp_object.plsp_id,
+                    },
+                });
+            }
+        }
+        violations
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StateReport {
+    srp_object: Option<SrpObject>,
+    lsp_object: LspObject,
+    path: Option<Path>,
+    unknown_objects: UnknownObjects,
+}
+
+impl StateReport {
+    pub fn new(srp_object: Option<SrpObject>, lsp_object: LspObject, path: Option<Path>) -> Self {
+        StateReport {
+            srp_object,
+            lsp_object,
+            path,
+            unknown_objects: UnknownObjects::new(),
+        }
+    }
+
+    // Actual path and attributes (RRO, reported BANDWIDTH/METRIC) are
+    // skipped until the next state report starts, unknown objects among
+    // them are kept
+    fn skip_actual_path(input: &[u8]) -> IResult<&[u8], Vec<UnknownObject>> {
+        let mut unknown_objects = vec![];
+        let mut left = input;
+        while !left.is_empty() {
+            let (_remaining, cobj) = CommonObject::parse_common_object(left)?;
+            match cobj.object_class_type {
+                ObjectClassType::Srp(_) | ObjectClassType::Lsp(_) => break,
+                ObjectClassType::Unknown(_) => {
+                    let (remaining, mut unknown) = UnknownObject::parse_unknown_objects(left)?;
+                    unknown_objects.append(&mut unknown);
+                    left = remaining;
+                }
+                _ => {
+                    let (remaining, _object) =
+                        bytes::streaming::take(cobj.object_length as usize)(left)?;
+                    left = remaining;
+                }
+            }
+        }
+        Ok((left, unknown_objects))
+    }
+
+    fn parse_state_report(input: &[u8]) -> IResult<&[u8], Self> {
+        let mut unknown_objects = UnknownObjects::new();
+        let (remaining, srp_object) = match CommonObject::peek_object_class(input)?.1 {
+            Some(ObjectClassType::Srp(_)) => {
+                let (remaining, srp_object) = SrpObject::parse_srp_object(input)?;
+                (remaining, Some(srp_object))
+            }
+            _ => (input, None),
+        };
+        let (remaining, unknown) = UnknownObject::parse_unknown_objects(remaining)?;
+        unknown_objects.push(0, unknown);
+        let (remaining, lsp_object) = LspObject::parse_lsp_object(remaining)?;
+        let (remaining, unknown) = UnknownObject::parse_unknown_objects(remaining)?;
+        unknown_objects.push(1, unknown);
+        let (remaining, path) = match CommonObject::peek_object_class(remaining)?.1 {
+            Some(ObjectClassType::Ero(_)) | Some(ObjectClassType::PathAttrib(_)) => {
+                let (remaining, path) = Path::parse_path(remaining)?;
+                (remaining, Some(path))
+            }
+            _ => (remaining, None),
+        };
+        let (remaining, unknown) = Self::skip_actual_path(remaining)?;
+        unknown_objects.push(2, unknown);
+        let state_report = StateReport {
+            srp_object,
+            lsp_object,
+            path,
+            unknown_objects,
+        };
+        Ok((remaining, state_report))
+    }
+
+    pub fn srp_object(&self) -> Option<&SrpObject> {
+        self.srp_object.as_ref()
+    }
+
+    pub fn lsp_object(&self) -> &LspObject {
+        &self.lsp_object
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_ref()
+    }
+
+    pub fn unknown_objects(&self) -> &[UnknownObject] {
+        self.unknown_objects.objects()
+    }
+
+    pub fn summary(&self) -> String {
+        if self.is_end_of_sync() {
+            return String::from("plsp=0 end-of-sync");
+        }
+        let mut fields: Vec<String> = self.srp_object.iter().map(SrpObject::summary).collect();
+        fields.push(self.lsp_object.summary());
+        let operational_status = format!("{:?}", self.lsp_object.operational_status);
+        fields.push(format!("oper={}", operational_status.to_lowercase()));
+        fields.extend(self.path.iter().map(Path::summary));
+        if !self.unknown_objects.is_empty() {
+            fields.push(format!("unknown={}", self.unknown_objects.len()));
+        }
+        fields.retain(|field| !field.is_empty());
+        fields.join(" ")
+    }
+
+    // RFC 8231: a report with PLSP-ID 0 marks the end of state synchronization
+    pub fn is_end_of_sync(&self) -> bool {
+        self.lsp_object.plsp_id == 0
+    }
+}
+
+impl std::fmt::Display for StateReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let srp_object_str = if let Some(ref srp_object) = self.srp_object {
+            format!("{}", srp_object)
+        } else {
+            String::new()
+        };
+        let path_str = if let Some(ref path) = self.path {
+            format!("{}", path)
+        } else {
+            String::new()
+        };
+        let mut unknown_objects_str = String::new();
+        for unknown_object in self.unknown_objects.objects() {
+            unknown_objects_str.push_str(&format!("{}", unknown_object));
+        }
+        writedoc!(
+            f,
+            r#"
+                {srp_object_str}
+                {:indent$}{lsp_object}
+                {:indent$}{path_str}
+                {:indent$}{unknown_objects_str}
+            "#,
+            "",
+            "",
+            "",
+            srp_object_str = srp_object_str,
+            lsp_object = self.lsp_object,
+            path_str = path_str,
+            unknown_objects_str = unknown_objects_str,
+            indent = 4
+        )
+    }
+}
+
+impl std::fmt::Display for PcRpt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut state_report_lst_str = String::new();
+        for state_report in self.state_report_lst.iter() {
+            let state_report_str = format!("{}", state_report);
+            state_report_lst_str.push_str(&state_report_str);
+        }
+        let title = "##[PCRpt]##".yellow();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_header}
+                {state_report_lst_str}
+            "#,
+            title = title,
+            common_header = self.common_header,
+            state_report_lst_str = state_report_lst_str
+        )
+    }
+}
+
+impl Encode for PcRpt {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = vec![];
+        for state_report in self.state_report_lst.iter() {
+            state_report.encode(&mut body);
+        }
+        Encoder::encode_common_header(buf, self.common_header.flags, MessageType::PCRpt, &body);
+    }
+}
+
+impl Encode for StateReport {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        if let Some(ref srp_object) = self.srp_object {
+            srp_object.encode(buf);
+        }
+        self.unknown_objects.encode_gap(0, buf);
+        self.lsp_object.encode(buf);
+        self.unknown_objects.encode_gap(1, buf);
+        if let Some(ref path) = self.path {
+            path.encode(buf);
+        }
+        self.unknown_objects.encode_gap(2, buf);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::common::OperationalStatus;
+    use crate::objects::metric::MetricType;
+    #[test]
+    fn test_pcrpt_message_parsing() {
+        let input: &[u8] = &[
+            0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x20, 0x10,
+            0x00, 0x10, 0x00, 0x0e, 0xb0, 0x19, 0x00, 0x11, 0x00, 0x04, 0x6c, 0x73, 0x70, 0x31,
+            0x07, 0x10, 0x00, 0x0c, 0x01, 0x08, 0xc0, 0xa8, 0x96, 0x2d, 0x20, 0x00, 0x06, 0x10,
+            0x00, 0x0c, 0x00, 0x00, 0x00, 0x02, 0x41, 0x20, 0x00, 0x00, 0x08, 0x10, 0x00, 0x0c,
+            0x01, 0x08, 0x0a, 0x00, 0x00, 0x01, 0x20, 0x00, 0x20, 0x10, 0x00, 0x08, 0x00, 0x00,
+            0x00, 0x00, 0x07, 0x10, 0x00, 0x04,
+        ];
+        let (remaining, report_message) =
+            PcRpt::parse_report_message(input).expect("[!!] Error while parsing pcrpt message");
+        assert!(remaining.is_empty());
+        let reports = report_message.state_reports();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].srp_object().map(|srp| srp.srp_id), Some(5));
+        assert_eq!(reports[0].lsp_object().plsp_id, 235);
+        assert_eq!(reports[0].lsp_object().symbolic_path_name(), Some("lsp1"));
+        assert_eq!(
+            reports[0].lsp_object().operational_status,
+            OperationalStatus::Up
+        );
+        let path = reports[0].path().expect("[!!] Missing reported path");
+        assert_eq!(path.intended_path().paths.len(), 1);
+        assert_eq!(
+            path.intended_attr_lst().metric_list()[0].metric_type(),
+            MetricType::Te
+        );
+        assert!(reports[1].is_end_of_sync());
+        assert!(reports[1].srp_object().is_none());
+    }
+}
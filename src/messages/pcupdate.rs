@@ -1,12 +1,21 @@
-use crate::common::IntendedAttrList;
+use crate::builder::PcepUpdateBuilder;
+use crate::common::{IntendedAttrList, IntendedPathList};
+use crate::encoder::{Encode, Encoder};
 use crate::messages::header::CommonHeader;
-use crate::objects::ero::EroObject;
+use crate::messages::types::MessageType;
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
 use crate::objects::lsp::LspObject;
 use crate::objects::srp::SrpObject;
+use crate::objects::unknown::{UnknownObject, UnknownObjects};
+use crate::options::{Diagnostics, ParseOptions};
+use crate::tlvs::tlv_set::SrPCECapabilityTLV;
+use crate::validate::{check_sr_ero, check_srp_id, Violation, ViolationKind};
 
 use colored::Colorize;
 use indoc::writedoc;
-use nom::IResult;
+use nom::error::{Error, ErrorKind};
+use nom::{Err, IResult};
 #[derive(Debug, PartialEq, Eq)]
 pub struct PcepUpdate {
     pub common_header: CommonHeader,
@@ -14,14 +23,88 @@ pub struct PcepUpdate {
 }
 
 impl PcepUpdate {
+    pub fn new(update_requests: Vec<UpdateRequest>) -> Self {
+        PcepUpdate {
+            common_header: CommonHeader::new(MessageType::PCUpd),
+            update_request_lst: UpdateRequestList {
+                update_request_lst: update_requests,
+            },
+        }
+    }
+
+    // Single request message with lengths filled in, see builder.rs
+    pub fn builder() -> PcepUpdateBuilder {
+        PcepUpdateBuilder::new()
+    }
+
     pub fn parse_update_message(input: &[u8]) -> IResult<&[u8], Self> {
-        let (remaining, update_request_lst) = UpdateRequestList::parse_update_request_list(input)?;
+        let mut diagnostics = Diagnostics::new(input, ParseOptions::default());
+        Self::parse_update_message_with(input, &mut diagnostics)
+    }
+
+    // Objects that cannot start an update request are skipped in lenient
+    // mode and rejected in strict mode, as are unassigned SRP flags
+    pub fn parse_update_message_with<'a>(
+        input: &'a [u8],
+        diagnostics: &mut Diagnostics<'a>,
+    ) -> IResult<&'a [u8], Self> {
+        let (remaining, update_request_lst) =
+            UpdateRequestList::parse_update_request_list(input, diagnostics)?;
         let pcep_update_msg = PcepUpdate {
             common_header: Default::default(),
             update_request_lst,
         };
         Ok((remaining, pcep_update_msg))
     }
+
+    pub fn update_requests(&self) -> &[UpdateRequest] {
+        &self.update_request_lst.update_request_lst
+    }
+
+    // One line form, e.g.
+    // PCUpd srp=12 plsp=235 name=cfg_PSADEL-5 D A ero=[24001(...)] bw=1Mbps
+    pub fn summary(&self) -> String {
+        let update_requests: Vec<String> = self
+            .update_requests()
+            .iter()
+            .map(UpdateRequest::summary)
+            .collect();
+        format!("PCUpd {}", update_requests.join(" | "))
+    }
+
+    // RFC 8231 rules the grammar does not check, in request order
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (request, update_request) in self.update_requests().iter().enumerate() {
+            violations.extend(check_srp_id(request, update_request.srp_object.srp_id));
+            let plsp_id = update_request.lsp_object.plsp_id;
+            if plsp_id == 0 {
+                violations.push(Violation {
+                    request,
+                    kind: ViolationKind::ZeroPlspId,
+                });
+            }
+            if !update_request.lsp_object.flag_delegate {
+                violations.push(Violation {
+                    request,
+                    kind: ViolationKind::NotDelegated { plsp_id },
+                });
+            }
+        }
+        violations
+    }
+
+    // SR-ERO rules against the SR capability the PCC sent in its Open,
+    // see validate::check_sr_ero
+    pub fn validate_sr(&self, capability: &SrPCECapabilityTLV) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (request, update_request) in self.update_requests().iter().enumerate() {
+            for (_path_attrib, ero_object) in update_request.path.intended_path.paths.iter() {
+                violations.extend(check_sr_ero(request, ero_object, capability));
+            }
+        }
+        violations
+    }
 }
 
 impl std::fmt::Display for PcepUpdate {
@@ -47,13 +130,31 @@ pub struct UpdateRequestList {
 }
 
 impl UpdateRequestList {
-    fn parse_update_request_list(input: &[u8]) -> IResult<&[u8], Self> {
-        // TODO: for loops
-        let (remaining, update_req) = UpdateRequest::parse_update_request(input)?;
-        let update_req_lst = UpdateRequestList {
-            update_request_lst: vec![update_req],
-        };
-        Ok((remaining, update_req_lst))
+    fn parse_update_request_list<'a>(
+        input: &'a [u8],
+        diagnostics: &mut Diagnostics<'a>,
+    ) -> IResult<&'a [u8], Self> {
+        let mut update_request_lst = vec![];
+        let mut left = input;
+        while !left.is_empty() {
+            match CommonObject::peek_object_class(left)?.1 {
+                Some(ObjectClassType::Srp(_)) => {
+                    let (remaining, update_req) = UpdateRequest::parse_update_request(left)?;
+                    diagnostics.check_reserved(left, "SRP flags", update_req.srp_object.flags)?;
+                    left = remaining;
+                    update_request_lst.push(update_req);
+                }
+                _ => {
+                    let (remaining, ()) = diagnostics.skip_object(left)?;
+                    left = remaining;
+                }
+            }
+        }
+        if update_request_lst.is_empty() {
+            return Err(Err::Failure(Error::new(input, ErrorKind::Fail)));
+        }
+        let update_req_lst = UpdateRequestList { update_request_lst };
+        Ok((left, update_req_lst))
     }
 }
 
@@ -77,36 +178,92 @@ pub struct UpdateRequest {
     srp_object: SrpObject,
     lsp_object: LspObject,
     path: Path,
+    unknown_objects: UnknownObjects,
 }
 
 impl UpdateRequest {
+    pub fn new(srp_object: SrpObject, lsp_object: LspObject, path: Path) -> Self {
+        UpdateRequest {
+            srp_object,
+            lsp_object,
+            path,
+            unknown_objects: UnknownObjects::new(),
+        }
+    }
+
+    // Unknown objects are accepted between the known ones, see
+    // UnknownObject::parse_unknown_objects
     fn parse_update_request(input: &[u8]) -> IResult<&[u8], Self> {
+        let mut unknown_objects = UnknownObjects::new();
         let (remaining, srp_object) = SrpObject::parse_srp_object(input)?;
+        let (remaining, unknown) = UnknownObject::parse_unknown_objects(remaining)?;
+        unknown_objects.push(0, unknown);
         let (remaining, lsp_object) = LspObject::parse_lsp_object(remaining)?;
+        let (remaining, unknown) = UnknownObject::parse_unknown_objects(remaining)?;
+        unknown_objects.push(1, unknown);
         let (remaining, path) = Path::parse_path(remaining)?;
+        let (remaining, unknown) = UnknownObject::parse_unknown_objects(remaining)?;
+        unknown_objects.push(2, unknown);
         let update_request = UpdateRequest {
             srp_object,
             lsp_object,
             path,
+            unknown_objects,
         };
         Ok((remaining, update_request))
     }
+
+    pub fn srp_object(&self) -> &SrpObject {
+        &self.srp_object
+    }
+
+    pub fn lsp_object(&self) -> &LspObject {
+        &self.lsp_object
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn unknown_objects(&self) -> &[UnknownObject] {
+        self.unknown_objects.objects()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut fields = vec![
+            self.srp_object.summary(),
+            self.lsp_object.summary(),
+            self.path.summary(),
+        ];
+        if !self.unknown_objects.is_empty() {
+            fields.push(format!("unknown={}", self.unknown_objects.len()));
+        }
+        fields.retain(|field| !field.is_empty());
+        fields.join(" ")
+    }
 }
 
 impl std::fmt::Display for UpdateRequest {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut unknown_objects_str = String::new();
+        for unknown_object in self.unknown_objects.objects() {
+            unknown_objects_str.push_str(&format!("{}", unknown_object));
+        }
         writedoc!(
             f,
             r#"
                 {srp_object}
                 {:indent$}{lsp_object}
                 {:indent$}{path}
+                {:indent$}{unknown_objects_str}
             "#,
             "",
             "",
+            "",
             srp_object = self.srp_object,
             lsp_object = self.lsp_object,
             path = self.path,
+            unknown_objects_str = unknown_objects_str,
             indent = 4
         )
     }
@@ -114,20 +271,45 @@ impl std::fmt::Display for UpdateRequest {
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Path {
-    intended_path: EroObject,
+    intended_path: IntendedPathList,
     intended_attr_lst: IntendedAttrList,
 }
 
 impl Path {
-    fn parse_path(input: &[u8]) -> IResult<&[u8], Self> {
-        let (remaining, ero_object) = EroObject::parse_ero_object(input)?;
+    pub fn new(intended_path: IntendedPathList, intended_attr_lst: IntendedAttrList) -> Self {
+        Path {
+            intended_path,
+            intended_attr_lst,
+        }
+    }
+
+    pub(crate) fn parse_path(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, intended_path) = IntendedPathList::parse_intended_path_list(input)?;
         let (remaining, intended_attr_lst) = IntendedAttrList::parse_intended_attr_list(remaining)?;
         let path = Path {
-            intended_path: ero_object,
+            intended_path,
             intended_attr_lst,
         };
         Ok((remaining, path))
     }
+
+    pub fn intended_path(&self) -> &IntendedPathList {
+        &self.intended_path
+    }
+
+    pub fn intended_attr_lst(&self) -> &IntendedAttrList {
+        &self.intended_attr_lst
+    }
+
+    // EROs then attributes, empty parts left out
+    pub fn summary(&self) -> String {
+        let mut fields = vec![
+            self.intended_path.summary(),
+            self.intended_attr_lst.summary(),
+        ];
+        fields.retain(|field| !field.is_empty());
+        fields.join(" ")
+    }
 }
 
 impl std::fmt::Display for Path {
@@ -145,3 +327,31 @@ impl std::fmt::Display for Path {
         )
     }
 }
+
+impl Encode for PcepUpdate {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = vec![];
+        for update_request in self.update_requests() {
+            update_request.encode(&mut body);
+        }
+        Encoder::encode_common_header(buf, self.common_header.flags, MessageType::PCUpd, &body);
+    }
+}
+
+impl Encode for UpdateRequest {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.srp_object.encode(buf);
+        self.unknown_objects.encode_gap(0, buf);
+        self.lsp_object.encode(buf);
+        self.unknown_objects.encode_gap(1, buf);
+        self.path.encode(buf);
+        self.unknown_objects.encode_gap(2, buf);
+    }
+}
+
+impl Encode for Path {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.intended_path.encode(buf);
+        self.intended_attr_lst.encode(buf);
+    }
+}
@@ -32,20 +32,32 @@ impl std::fmt::Display for MessageType {
     }
 }
 
-// TODO: seperate mod for errors
-#[derive(Debug)]
-pub enum MessageTypeError {
-    UnknownMessageTypeError(u8),
-}
-
 impl Default for MessageType {
     fn default() -> Self {
         MessageType::UnKnown(0)
     }
 }
 
+impl From<MessageType> for u8 {
+    fn from(value: MessageType) -> Self {
+        match value {
+            MessageType::Open => 1,
+            MessageType::Keepalive => 2,
+            MessageType::PCReq => 3,
+            MessageType::PCRep => 4,
+            MessageType::PCNtf => 5,
+            MessageType::PCErr => 6,
+            MessageType::PCClose => 7,
+            MessageType::PCRpt => 10,
+            MessageType::PCUpd => 11,
+            MessageType::PCInitiate => 12,
+            MessageType::UnKnown(x) => x,
+        }
+    }
+}
+
 impl TryFrom<u8> for MessageType {
-    type Error = MessageTypeError;
+    type Error = u8;
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
             1 => Ok(Self::Open),
@@ -58,7 +70,7 @@ impl TryFrom<u8> for MessageType {
             10 => Ok(Self::PCRpt),
             11 => Ok(Self::PCUpd),
             12 => Ok(Self::PCInitiate),
-            _ => Err(MessageTypeError::UnknownMessageTypeError(value)),
+            _ => Err(value),
         }
     }
 }
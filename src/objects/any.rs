@@ -0,0 +1,298 @@
+use colored::Colorize;
+use indoc::writedoc;
+use nom::bytes;
+use nom::IResult;
+
+use crate::borrowed::Items;
+use crate::error;
+use crate::objects::bandwidth::BandwidthObject;
+use crate::objects::classes::ObjectClassType;
+use crate::objects::classtype::ClassTypeObject;
+use crate::objects::close::CloseObject;
+use crate::objects::endpoints::EndPointsObject;
+use crate::objects::ero::EroObject;
+use crate::objects::error::ErrorObject;
+use crate::objects::header::CommonObject;
+use crate::objects::loadbalancing::LoadBalancingObject;
+use crate::objects::lsp::LspObject;
+use crate::objects::lspa::LspaObject;
+use crate::objects::metric::MetricObject;
+use crate::objects::nopath::NoPathObject;
+use crate::objects::of::OfObject;
+use crate::objects::open::OpenObject;
+use crate::objects::pathattrib::PathAttribObject;
+use crate::objects::rp::RpObject;
+use crate::objects::srp::SrpObject;
+use crate::objects::svec::SvecObject;
+use crate::objects::unknown::UnknownObject;
+use crate::registry::CustomObject;
+
+pub type PcepObjectIter<'a> = Items<'a, PcepObject>;
+
+// Any PCEP object, decoded from its class alone whatever message it
+// sits in
+#[derive(Debug, PartialEq, Eq)]
+pub enum PcepObject {
+    Open(OpenObject),
+    Rp(RpObject),
+    NoPath(NoPathObject),
+    EndPoints(EndPointsObject),
+    Bandwidth(BandwidthObject),
+    Metric(MetricObject),
+    Ero(EroObject),
+    Lspa(LspaObject),
+    Svec(SvecObject),
+    Error(ErrorObject),
+    LoadBalancing(LoadBalancingObject),
+    Close(CloseObject),
+    Of(OfObject),
+    ClassType(ClassTypeObject),
+    Lsp(LspObject),
+    Srp(SrpObject),
+    PathAttrib(PathAttribObject),
+    Unknown(UnknownObject),
+    // Unknown class decoded by a registered ObjectDecoder
+    Custom(CommonObject, Box<dyn CustomObject>),
+    // Known class whose body the typed parser rejects, e.g. an object
+    // type without a decoder yet
+    Raw(CommonObject, Vec<u8>),
+}
+
+impl PcepObject {
+    // Objects of a message body in wire order. Only the object framing
+    // can fail the walk, error offsets are relative to the body.
+    pub fn objects(body: &[u8]) -> PcepObjectIter<'_> {
+        Items::new(body, Self::parse_object)
+    }
+
+    pub fn parse_object(input: &[u8]) -> IResult<&[u8], Self> {
+        let (_remaining, common_object) = CommonObject::parse_common_object(input)?;
+        let (remaining, object) =
+            bytes::streaming::take(common_object.object_length as usize)(input)?;
+        let object_class_type = common_object.object_class_type;
+        match error::complete(object, |object| {
+            Self::parse_typed(object_class_type, object)
+        }) {
+            Ok((_object, typed)) => Ok((remaining, typed)),
+            Err(_err) => {
+                let raw_body = object[4..].to_vec();
+                Ok((remaining, Self::Raw(common_object, raw_body)))
+            }
+        }
+    }
+
+    fn parse_typed(object_class_type: ObjectClassType, object: &[u8]) -> IResult<&[u8], Self> {
+        let typed = match object_class_type {
+            ObjectClassType::Open(_) => {
+                let (object, open) = OpenObject::parse_open_object(object)?;
+                (object, Self::Open(open))
+            }
+            ObjectClassType::Rp(_) => {
+                let (object, rp) = RpObject::parse_rp_object(object)?;
+                (object, Self::Rp(rp))
+            }
+            ObjectClassType::NoPath(_) => {
+                let (object, no_path) = NoPathObject::parse_no_path_object(object)?;
+                (object, Self::NoPath(no_path))
+            }
+            ObjectClassType::EndPoints(_) => {
+                let (object, endpoints) = EndPointsObject::parse_endpoints_object(object)?;
+                (object, Self::EndPoints(endpoints))
+            }
+            ObjectClassType::Bandwidth(_) => {
+                let (object, bandwidth) = BandwidthObject::parse_bandwidth_object(object)?;
+                (object, Self::Bandwidth(bandwidth))
+            }
+            ObjectClassType::Metric(_) => {
+                let (object, metric) = MetricObject::parse_metric_object(object)?;
+                (object, Self::Metric(metric))
+            }
+            ObjectClassType::Ero(_) => {
+                let (object, ero) = EroObject::parse_ero_object(object)?;
+                (object, Self::Ero(ero))
+            }
+            ObjectClassType::Lspa(_) => {
+                let (object, lspa) = LspaObject::parse_lspa_object(object)?;
+                (object, Self::Lspa(lspa))
+            }
+            ObjectClassType::Svec(_) => {
+                let (object, svec) = SvecObject::parse_svec_object(object)?;
+                (object, Self::Svec(svec))
+            }
+            ObjectClassType::Error(_) => {
+                let (object, error) = ErrorObject::parse_error_object(object)?;
+                (object, Self::Error(error))
+            }
+            ObjectClassType::LoadBalancing(_) => {
+                let (object, load_balancing) =
+                    LoadBalancingObject::parse_load_balancing_object(object)?;
+                (object, Self::LoadBalancing(load_balancing))
+            }
+            ObjectClassType::Close(_) => {
+                let (object, close) = CloseObject::parse_close_object(object)?;
+                (object, Self::Close(close))
+            }
+            ObjectClassType::Of(_) => {
+                let (object, of) = OfObject::parse_of_object(object)?;
+                (object, Self::Of(of))
+            }
+            ObjectClassType::ClassType(_) => {
+                let (object, class_type) = ClassTypeObject::parse_class_type_object(object)?;
+                (object, Self::ClassType(class_type))
+            }
+            ObjectClassType::Lsp(_) => {
+                let (object, lsp) = LspObject::parse_lsp_object(object)?;
+                (object, Self::Lsp(lsp))
+            }
+            ObjectClassType::Srp(_) => {
+                let (object, srp) = SrpObject::parse_srp_object(object)?;
+                (object, Self::Srp(srp))
+            }
+            ObjectClassType::PathAttrib(_) => {
+                let (object, path_attrib) = PathAttribObject::parse_path_attrib_object(object)?;
+                (object, Self::PathAttrib(path_attrib))
+            }
+            ObjectClassType::Unknown(_) => {
+                let (object, unknown) = UnknownObject::parse_unknown_object(object)?;
+                match unknown.decode() {
+                    Some(custom) => (object, Self::Custom(unknown.common_object, custom)),
+                    None => (object, Self::Unknown(unknown)),
+                }
+            }
+        };
+        Ok(typed)
+    }
+
+    pub fn common_object(&self) -> &CommonObject {
+        match self {
+            Self::Open(x) => &x.common_object,
+            Self::Rp(x) => &x.common_object,
+            Self::NoPath(x) => &x.common_object,
+            Self::EndPoints(x) => x.common_object(),
+            Self::Bandwidth(x) => &x.common_object,
+            Self::Metric(x) => x.common_object(),
+            Self::Ero(x) => &x.common_object,
+            Self::Lspa(x) => x.common_object(),
+            Self::Svec(x) => &x.common_object,
+            Self::Error(x) => &x.common_object,
+            Self::LoadBalancing(x) => &x.common_object,
+            Self::Close(x) => &x.common_object,
+            Self::Of(x) => &x.common_object,
+            Self::ClassType(x) => &x.common_object,
+            Self::Lsp(x) => &x.common_object,
+            Self::Srp(x) => &x.common_object,
+            Self::PathAttrib(x) => &x.common_object,
+            Self::Unknown(x) => &x.common_object,
+            Self::Custom(common_object, _) | Self::Raw(common_object, _) => common_object,
+        }
+    }
+
+    // PLSP-ID of an LSP object
+    pub fn plsp_id(&self) -> Option<u32> {
+        match self {
+            Self::Lsp(lsp) => Some(lsp.plsp_id),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PcepObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Open(x) => write!(f, "{}", x),
+            Self::Rp(x) => write!(f, "{}", x),
+            Self::NoPath(x) => write!(f, "{}", x),
+            Self::EndPoints(x) => write!(f, "{}", x),
+            Self::Bandwidth(x) => write!(f, "{}", x),
+            Self::Metric(x) => write!(f, "{}", x),
+            Self::Ero(x) => write!(f, "{}", x),
+            Self::Lspa(x) => write!(f, "{}", x),
+            Self::Svec(x) => write!(f, "{}", x),
+            Self::Error(x) => write!(f, "{}", x),
+            Self::LoadBalancing(x) => write!(f, "{}", x),
+            Self::Close(x) => write!(f, "{}", x),
+            Self::Of(x) => write!(f, "{}", x),
+            Self::ClassType(x) => write!(f, "{}", x),
+            Self::Lsp(x) => write!(f, "{}", x),
+            Self::Srp(x) => write!(f, "{}", x),
+            Self::PathAttrib(x) => write!(f, "{}", x),
+            Self::Unknown(x) => write!(f, "{}", x),
+            Self::Custom(common_object, custom) => {
+                let title = "==[Custom Object]==".green().bold();
+                writedoc!(
+                    f,
+                    r#"
+                    {title}
+                        {common_object}
+                        {custom}
+                    "#,
+                    title = title,
+                    common_object = common_object,
+                    custom = custom
+                )
+            }
+            Self::Raw(common_object, raw_body) => {
+                let title = "==[Raw Object]==".green().bold();
+                writedoc!(
+                    f,
+                    r#"
+                    {title}
+                        {common_object}
+                        raw_body               = {raw_body:02x?}
+                    "#,
+                    title = title,
+                    common_object = common_object,
+                    raw_body = raw_body
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::borrowed::MessageView;
+
+    #[test]
+    fn test_pcep_object_iteration() {
+        // PCUpd: SRP, LSP, ERO, BANDWIDTH with a generic bandwidth type
+        // the typed parser rejects, then an unknown object
+        let input: &[u8] = &[
+            0x20, 0x0b, 0x00, 0x38, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x0c, 0x20, 0x10, 0x00, 0x08, 0x00, 0x0e, 0xb0, 0x09, 0x07, 0x10, 0x00, 0x0c,
+            0x01, 0x08, 0x0a, 0x00, 0x00, 0x01, 0x20, 0x00, 0x05, 0x30, 0x00, 0x0c, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80, 0x10, 0x00, 0x08, 0xde, 0xad, 0xbe, 0xef,
+            0x00, 0x00, 0x00, 0x00,
+        ];
+        let (_remaining, view) =
+            MessageView::parse(&input[..56]).expect("[!!] Error while parsing pcep message");
+        let objects: Vec<PcepObject> = PcepObject::objects(view.body())
+            .collect::<Result<_, _>>()
+            .expect("[!!] Error while walking objects");
+        assert_eq!(objects.len(), 5);
+        assert!(matches!(objects[0], PcepObject::Srp(ref srp) if srp.srp_id == 12));
+        assert_eq!(objects[1].plsp_id(), Some(235));
+        assert!(matches!(objects[2], PcepObject::Ero(_)));
+        match objects[3] {
+            PcepObject::Raw(ref common_object, ref raw_body) => {
+                assert!(matches!(
+                    common_object.object_class_type,
+                    ObjectClassType::Bandwidth(_)
+                ));
+                assert_eq!(raw_body.len(), 8);
+            }
+            _ => panic!("[!!] Expected a raw object"),
+        }
+        assert!(matches!(objects[4], PcepObject::Unknown(_)));
+        assert_eq!(objects[4].common_object().object_length, 8);
+
+        // Broken framing ends the walk with the offset in the body
+        let mut objects = PcepObject::objects(&input[4..58]);
+        let error = objects
+            .find_map(Result::err)
+            .expect("[!!] Expected an object error");
+        assert_eq!(error.offset, 54);
+        assert!(error.is_truncated());
+    }
+}
@@ -5,6 +5,7 @@ use nom::error::{Error, ErrorKind};
 use nom::number;
 use nom::{Err, IResult};
 
+use crate::encoder::{Encode, Encoder};
 use crate::objects::classes::ObjectClassType;
 use crate::objects::header::CommonObject;
 use crate::objects::types::BandwidthObjectType;
@@ -25,6 +26,31 @@ impl PartialEq for BandwidthObject {
 }
 
 impl BandwidthObject {
+    // Requested bandwidth in bytes per second
+    pub fn new(bandwidth: f32) -> Self {
+        BandwidthObject {
+            common_object: CommonObject::new(ObjectClassType::Bandwidth(
+                BandwidthObjectType::Requested,
+            )),
+            bandwidth,
+        }
+    }
+
+    // One line form in bits per second, e.g. bw=1Mbps
+    pub fn summary(&self) -> String {
+        let bits = self.bandwidth as f64 * 8.0;
+        let (value, unit) = if bits >= 1e9 {
+            (bits / 1e9, "Gbps")
+        } else if bits >= 1e6 {
+            (bits / 1e6, "Mbps")
+        } else if bits >= 1e3 {
+            (bits / 1e3, "kbps")
+        } else {
+            (bits, "bps")
+        };
+        format!("bw={}{}", (value * 1000.0).round() / 1000.0, unit)
+    }
+
     pub fn parse_bandwidth_object(input: &[u8]) -> IResult<&[u8], Self> {
         let (remaining, cobj) = CommonObject::parse_common_object(input)?;
         match cobj.object_class_type {
@@ -40,9 +66,8 @@ impl BandwidthObject {
                     };
                     Ok((remaining, bandwidth_obj))
                 }
-                _ => {
-                    unimplemented!("[!!] Unimplementd for other types of bandwidth")
-                }
+                // Generic bandwidth objects are not decoded yet
+                _ => Err(Err::Failure(Error::new(input, ErrorKind::Switch))),
             },
             _ => Err(Err::Error(Error::new(input, ErrorKind::Fail))),
         }
@@ -65,6 +90,22 @@ impl std::fmt::Display for BandwidthObject {
         )
     }
 }
+impl Encode for BandwidthObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let object_type = match self.common_object.object_class_type {
+            ObjectClassType::Bandwidth(BandwidthObjectType::RequestedOpt) => 2,
+            _ => 1,
+        };
+        Encoder::encode_object(
+            buf,
+            5,
+            object_type,
+            &self.common_object,
+            &self.bandwidth.to_be_bytes(),
+        );
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -90,4 +131,14 @@ pub mod tests {
         );
         assert_eq!(expected_bandwidth_object, bandwidth_object);
     }
+
+    #[test]
+    fn test_bandwidth_object_malformed() {
+        // Object length shorter than the common object header
+        let input: &[u8] = &[0x05, 0x10, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00];
+        assert!(BandwidthObject::parse_bandwidth_object(input).is_err());
+        // Generic bandwidth, not decoded
+        let input: &[u8] = &[0x05, 0x30, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00];
+        assert!(BandwidthObject::parse_bandwidth_object(input).is_err());
+    }
 }
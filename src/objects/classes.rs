@@ -1,11 +1,20 @@
 use crate::objects::types::BandwidthObjectType;
+use crate::objects::types::ClassTypeObjectType;
+use crate::objects::types::CloseObjectType;
 use crate::objects::types::EndpointsObjectType;
 use crate::objects::types::EroObjectType;
+use crate::objects::types::ErrorObjectType;
+use crate::objects::types::LoadBalancingObjectType;
 use crate::objects::types::LspObjectType;
 use crate::objects::types::LspaObjectType;
 use crate::objects::types::MetricObjectType;
+use crate::objects::types::NoPathObjectType;
+use crate::objects::types::OfObjectType;
 use crate::objects::types::OpenObjectType;
+use crate::objects::types::PathAttribObjectType;
+use crate::objects::types::RpObjectType;
 use crate::objects::types::SrpObjectType;
+use crate::objects::types::SvecObjectType;
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum ObjectClassType {
     Open(OpenObjectType),
@@ -16,6 +25,15 @@ pub enum ObjectClassType {
     Metric(MetricObjectType),
     Ero(EroObjectType),
     EndPoints(EndpointsObjectType),
+    Of(OfObjectType),
+    Rp(RpObjectType),
+    NoPath(NoPathObjectType),
+    Svec(SvecObjectType),
+    LoadBalancing(LoadBalancingObjectType),
+    ClassType(ClassTypeObjectType),
+    PathAttrib(PathAttribObjectType),
+    Error(ErrorObjectType),
+    Close(CloseObjectType),
     Unknown((u8, u8)),
 }
 
@@ -25,13 +43,22 @@ impl From<(u8, u8)> for ObjectClassType {
         let object_type = value.1;
         match object_class {
             1 => Self::Open(object_type.into()),
+            2 => Self::Rp(object_type.into()),
+            3 => Self::NoPath(object_type.into()),
             4 => Self::EndPoints(object_type.into()),
             5 => Self::Bandwidth(object_type.into()),
             6 => Self::Metric(object_type.into()),
             7 => Self::Ero(object_type.into()),
             9 => Self::Lspa(object_type.into()),
+            11 => Self::Svec(object_type.into()),
+            13 => Self::Error(object_type.into()),
+            14 => Self::LoadBalancing(object_type.into()),
+            15 => Self::Close(object_type.into()),
+            21 => Self::Of(object_type.into()),
+            22 => Self::ClassType(object_type.into()),
             32 => Self::Lsp(object_type.into()),
             33 => Self::Srp(object_type.into()),
+            45 => Self::PathAttrib(object_type.into()),
             _ => Self::Unknown((object_class, object_type)),
         }
     }
@@ -182,6 +209,140 @@ impl std::fmt::Display for ObjectClassType {
                     write!(f, "(ObjectClassType::SRP, SRPObjectType::UnAssigned)")
                 }
             },
+            Self::Of(of_obj_type) => match of_obj_type {
+                OfObjectType::Reserved => {
+                    write!(f, "(ObjectClassType::OF, OFObjectType::Reserved)")
+                }
+                OfObjectType::Of => {
+                    write!(f, "(ObjectClassType::OF, OFObjectType::OF)")
+                }
+                OfObjectType::Unassigned => {
+                    write!(f, "(ObjectClassType::OF, OFObjectType::Unassigned)")
+                }
+            },
+            Self::Rp(rp_obj_type) => match rp_obj_type {
+                RpObjectType::Reserved => {
+                    write!(f, "(ObjectClassType::RP, RPObjectType::Reserved)")
+                }
+                RpObjectType::Rp => {
+                    write!(f, "(ObjectClassType::RP, RPObjectType::RP)")
+                }
+                RpObjectType::Unassigned => {
+                    write!(f, "(ObjectClassType::RP, RPObjectType::Unassigned)")
+                }
+            },
+            Self::NoPath(no_path_obj_type) => match no_path_obj_type {
+                NoPathObjectType::Reserved => {
+                    write!(f, "(ObjectClassType::NO-PATH, NoPathObjectType::Reserved)")
+                }
+                NoPathObjectType::NoPath => {
+                    write!(f, "(ObjectClassType::NO-PATH, NoPathObjectType::NO-PATH)")
+                }
+                NoPathObjectType::Unassigned => {
+                    write!(
+                        f,
+                        "(ObjectClassType::NO-PATH, NoPathObjectType::Unassigned)"
+                    )
+                }
+            },
+            Self::Svec(svec_obj_type) => match svec_obj_type {
+                SvecObjectType::Reserved => {
+                    write!(f, "(ObjectClassType::SVEC, SvecObjectType::Reserved)")
+                }
+                SvecObjectType::Svec => {
+                    write!(f, "(ObjectClassType::SVEC, SvecObjectType::SVEC)")
+                }
+                SvecObjectType::Unassigned => {
+                    write!(f, "(ObjectClassType::SVEC, SvecObjectType::Unassigned)")
+                }
+            },
+            Self::LoadBalancing(load_balancing_obj_type) => {
+                match load_balancing_obj_type {
+                    LoadBalancingObjectType::Reserved => {
+                        write!(
+                            f,
+                            "(ObjectClassType::LOAD-BALANCING, LoadBalancingObjectType::Reserved)"
+                        )
+                    }
+                    LoadBalancingObjectType::LoadBalancing => {
+                        write!(f, "(ObjectClassType::LOAD-BALANCING, LoadBalancingObjectType::LOAD-BALANCING)")
+                    }
+                    LoadBalancingObjectType::Unassigned => {
+                        write!(f, "(ObjectClassType::LOAD-BALANCING, LoadBalancingObjectType::Unassigned)")
+                    }
+                }
+            }
+            Self::ClassType(class_type_obj_type) => match class_type_obj_type {
+                ClassTypeObjectType::Reserved => {
+                    write!(
+                        f,
+                        "(ObjectClassType::CLASSTYPE, ClassTypeObjectType::Reserved)"
+                    )
+                }
+                ClassTypeObjectType::ClassType => {
+                    write!(
+                        f,
+                        "(ObjectClassType::CLASSTYPE, ClassTypeObjectType::CLASSTYPE)"
+                    )
+                }
+                ClassTypeObjectType::Unassigned => {
+                    write!(
+                        f,
+                        "(ObjectClassType::CLASSTYPE, ClassTypeObjectType::Unassigned)"
+                    )
+                }
+            },
+            Self::PathAttrib(path_attrib_obj_type) => match path_attrib_obj_type {
+                PathAttribObjectType::Reserved => {
+                    write!(
+                        f,
+                        "(ObjectClassType::PATH-ATTRIB, PathAttribObjectType::Reserved)"
+                    )
+                }
+                PathAttribObjectType::PathAttrib => {
+                    write!(
+                        f,
+                        "(ObjectClassType::PATH-ATTRIB, PathAttribObjectType::PATH-ATTRIB)"
+                    )
+                }
+                PathAttribObjectType::Unassigned => {
+                    write!(
+                        f,
+                        "(ObjectClassType::PATH-ATTRIB, PathAttribObjectType::Unassigned)"
+                    )
+                }
+            },
+            Self::Error(error_obj_type) => match error_obj_type {
+                ErrorObjectType::Reserved => {
+                    write!(
+                        f,
+                        "(ObjectClassType::PCEP-ERROR, ErrorObjectType::Reserved)"
+                    )
+                }
+                ErrorObjectType::Error => {
+                    write!(
+                        f,
+                        "(ObjectClassType::PCEP-ERROR, ErrorObjectType::PCEP-ERROR)"
+                    )
+                }
+                ErrorObjectType::Unassigned => {
+                    write!(
+                        f,
+                        "(ObjectClassType::PCEP-ERROR, ErrorObjectType::Unassigned)"
+                    )
+                }
+            },
+            Self::Close(close_obj_type) => match close_obj_type {
+                CloseObjectType::Reserved => {
+                    write!(f, "(ObjectClassType::CLOSE, CloseObjectType::Reserved)")
+                }
+                CloseObjectType::Close => {
+                    write!(f, "(ObjectClassType::CLOSE, CloseObjectType::CLOSE)")
+                }
+                CloseObjectType::Unassigned => {
+                    write!(f, "(ObjectClassType::CLOSE, CloseObjectType::Unassigned)")
+                }
+            },
             Self::Unknown(x) => {
                 write!(f, "[!!] Unknown class and type: {:?}", *x)
             }
@@ -0,0 +1,98 @@
+use colored::Colorize;
+use indoc::writedoc;
+use nom::bits;
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::sequence::tuple;
+use nom::{Err, IResult};
+
+use crate::encoder::{Encode, Encoder};
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+use crate::objects::types::ClassTypeObjectType;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ClassTypeObject {
+    pub common_object: CommonObject,
+    pub reserved: u32,
+    pub class_type: u8,
+}
+
+impl ClassTypeObject {
+    fn parse_res_ct(input: &[u8]) -> IResult<&[u8], (u32, u8)> {
+        bits::bits::<_, _, Error<_>, _, _>(tuple((
+            bits::streaming::take(29u32),
+            bits::streaming::take(3u8),
+        )))(input)
+    }
+
+    pub fn parse_class_type_object(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, common_object) = CommonObject::parse_common_object(input)?;
+        if let ObjectClassType::ClassType(ClassTypeObjectType::ClassType) =
+            common_object.object_class_type
+        {
+            let object_body_len = common_object.object_length - 4;
+            let (remaining, object_body) =
+                bytes::streaming::take(object_body_len as usize)(remaining)?;
+            let (_object_body, res_ct) = Self::parse_res_ct(object_body)?;
+            let class_type_object = ClassTypeObject {
+                common_object,
+                reserved: res_ct.0,
+                class_type: res_ct.1,
+            };
+            return Ok((remaining, class_type_object));
+        }
+        Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
+    }
+}
+
+impl std::fmt::Display for ClassTypeObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = "==[CLASSTYPE Object]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_object}
+                reserved               = {reserved}
+                class_type             = CT{class_type}
+            "#,
+            title = title,
+            common_object = self.common_object,
+            reserved = self.reserved,
+            class_type = self.class_type
+        )
+    }
+}
+
+impl Encode for ClassTypeObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let res_ct = self.reserved << 3 | (self.class_type & 0b111) as u32;
+        Encoder::encode_object(buf, 22, 1, &self.common_object, &res_ct.to_be_bytes());
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[test]
+    fn test_class_type_object_parsing() {
+        let input: &[u8] = &[0x16, 0x10, 0x00, 0x08, 0x00, 0x00, 0x00, 0x03];
+        let (remaining, class_type_object) = ClassTypeObject::parse_class_type_object(input)
+            .expect("[!!] Failed to parse classtype object");
+        let expected_cobj = CommonObject {
+            object_class_type: ObjectClassType::ClassType(ClassTypeObjectType::ClassType),
+            reserved: 0,
+            flag_process: false,
+            flag_ignore: false,
+            object_length: 8,
+        };
+        let expected_class_type_object = ClassTypeObject {
+            common_object: expected_cobj,
+            reserved: 0,
+            class_type: 3,
+        };
+        assert!(remaining.is_empty(), "[!!] Nope, object not eaten fully");
+        assert_eq!(class_type_object, expected_class_type_object);
+    }
+}
@@ -0,0 +1,176 @@
+use colored::Colorize;
+use indoc::writedoc;
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::number;
+use nom::{Err, IResult};
+
+use crate::encoder::{Encode, Encoder};
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+use crate::objects::types::CloseObjectType;
+use crate::tlvs::tlv_parser::Parser;
+use crate::tlvs::types::Tlv;
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CloseReason {
+    NoExplanation,
+    DeadTimerExpired,
+    MalformedMessage,
+    UnknownRequestsReplies,
+    UnsupportedMessages,
+    Unknown(u8),
+}
+
+impl From<u8> for CloseReason {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::NoExplanation,
+            2 => Self::DeadTimerExpired,
+            3 => Self::MalformedMessage,
+            4 => Self::UnknownRequestsReplies,
+            5 => Self::UnsupportedMessages,
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+impl From<CloseReason> for u8 {
+    fn from(value: CloseReason) -> Self {
+        match value {
+            CloseReason::NoExplanation => 1,
+            CloseReason::DeadTimerExpired => 2,
+            CloseReason::MalformedMessage => 3,
+            CloseReason::UnknownRequestsReplies => 4,
+            CloseReason::UnsupportedMessages => 5,
+            CloseReason::Unknown(x) => x,
+        }
+    }
+}
+
+impl std::fmt::Display for CloseReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoExplanation => write!(f, "CloseReason::NoExplanation"),
+            Self::DeadTimerExpired => write!(f, "CloseReason::DeadTimerExpired"),
+            Self::MalformedMessage => write!(f, "CloseReason::MalformedMessage"),
+            Self::UnknownRequestsReplies => write!(f, "CloseReason::UnknownRequestsReplies"),
+            Self::UnsupportedMessages => write!(f, "CloseReason::UnsupportedMessages"),
+            Self::Unknown(x) => write!(f, "CloseReason::Unknown({})", *x),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CloseObject {
+    pub common_object: CommonObject,
+    pub reserved: u16,
+    pub flags: u8,
+    pub reason: CloseReason,
+    pub tlvs: Option<Vec<Tlv>>,
+}
+
+impl CloseObject {
+    pub fn new(reason: CloseReason) -> Self {
+        CloseObject {
+            common_object: CommonObject::new(ObjectClassType::Close(CloseObjectType::Close)),
+            reserved: 0,
+            flags: 0,
+            reason,
+            tlvs: None,
+        }
+    }
+
+    pub fn parse_close_object(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, common_object) = CommonObject::parse_common_object(input)?;
+        if let ObjectClassType::Close(CloseObjectType::Close) = common_object.object_class_type {
+            let object_body_len = common_object.object_length - 4;
+            let (remaining, object_body) =
+                bytes::streaming::take(object_body_len as usize)(remaining)?;
+            let (object_body, reserved) = number::streaming::be_u16(object_body)?;
+            let (object_body, flags) = number::streaming::be_u8(object_body)?;
+            let (object_body, reason) = number::streaming::be_u8(object_body)?;
+            let mut close_object = CloseObject {
+                common_object,
+                reserved,
+                flags,
+                reason: reason.into(),
+                tlvs: None,
+            };
+            if !object_body.is_empty() {
+                let (_object_body, tlvs) = Parser::parse_tlvs(object_body)?;
+                close_object.tlvs = Some(tlvs);
+            }
+            return Ok((remaining, close_object));
+        }
+        Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
+    }
+}
+
+impl std::fmt::Display for CloseObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tlvs_str = String::new();
+        if let Some(ref tlvs) = self.tlvs {
+            for t in tlvs {
+                let output = format!("{}", t);
+                tlvs_str.push_str(&output)
+            }
+        }
+        let title = "==[CLOSE Object]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_object}
+                reserved               = {reserved}
+                flags                  = {flags}
+                reason                 = {reason}
+            {tlv_str}
+            "#,
+            title = title,
+            common_object = self.common_object,
+            reserved = self.reserved,
+            flags = self.flags,
+            reason = self.reason,
+            tlv_str = tlvs_str
+        )
+    }
+}
+
+impl Encode for CloseObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = self.reserved.to_be_bytes().to_vec();
+        body.push(self.flags);
+        body.push(self.reason.into());
+        Encoder::encode_tlvs(&mut body, &self.tlvs);
+        Encoder::encode_object(buf, 15, 1, &self.common_object, &body);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[test]
+    fn test_close_object_parsing() {
+        let input: &[u8] = &[0x0f, 0x10, 0x00, 0x08, 0x00, 0x00, 0x00, 0x02];
+        let (remaining, close_object) =
+            CloseObject::parse_close_object(input).expect("[!!] Failed to parse close object");
+        let expected_cobj = CommonObject {
+            object_class_type: ObjectClassType::Close(CloseObjectType::Close),
+            reserved: 0,
+            flag_process: false,
+            flag_ignore: false,
+            object_length: 8,
+        };
+        let expected_close_object = CloseObject {
+            common_object: expected_cobj,
+            reserved: 0,
+            flags: 0,
+            reason: CloseReason::DeadTimerExpired,
+            tlvs: None,
+        };
+        assert!(remaining.is_empty(), "[!!] Nope, object not eaten fully");
+        assert_eq!(close_object, expected_close_object);
+    }
+}
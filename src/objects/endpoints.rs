@@ -1,3 +1,4 @@
+use crate::encoder::{Encode, Encoder};
 use crate::objects::classes::ObjectClassType;
 use crate::objects::header::CommonObject;
 use crate::objects::types::EndpointsObjectType;
@@ -39,6 +40,37 @@ impl std::fmt::Display for EndPoints {
 }
 
 impl EndPointsObject {
+    pub fn new_ipv4(source_ipv4: Ipv4Addr, destination_ipv4: Ipv4Addr) -> Self {
+        EndPointsObject {
+            common_object: CommonObject::new(ObjectClassType::EndPoints(
+                EndpointsObjectType::Ipv4Addresses,
+            )),
+            end_points: EndPoints::Ipv4Addresses(Ipv4AddressesEndPoint {
+                source_ipv4,
+                destination_ipv4,
+            }),
+        }
+    }
+
+    pub(crate) fn common_object_mut(&mut self) -> &mut CommonObject {
+        &mut self.common_object
+    }
+
+    // Source and destination when the endpoints are IPv4 addresses
+    pub fn ipv4_addresses(&self) -> Option<(Ipv4Addr, Ipv4Addr)> {
+        match self.end_points {
+            EndPoints::Ipv4Addresses(ref ep) => Some((ep.source_ipv4, ep.destination_ipv4)),
+        }
+    }
+
+    // One line form, e.g. 10.0.0.1->10.0.0.2
+    pub fn summary(&self) -> String {
+        match self.ipv4_addresses() {
+            Some((source, destination)) => format!("{}->{}", source, destination),
+            None => String::from("endpoints"),
+        }
+    }
+
     pub fn parse_endpoints_object(input: &[u8]) -> IResult<&[u8], Self> {
         let (remaining, common_obj) = CommonObject::parse_common_object(input)?;
         if let ObjectClassType::EndPoints(EndpointsObjectType::Ipv4Addresses) =
@@ -57,6 +89,10 @@ impl EndPointsObject {
         }
         Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
     }
+
+    pub fn common_object(&self) -> &CommonObject {
+        &self.common_object
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -115,6 +151,18 @@ impl std::fmt::Display for EndPointsObject {
     }
 }
 
+impl Encode for EndPointsObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self.end_points {
+            EndPoints::Ipv4Addresses(ref ipv4_addresses_ep) => {
+                let mut body = ipv4_addresses_ep.source_ipv4.octets().to_vec();
+                body.extend_from_slice(&ipv4_addresses_ep.destination_ipv4.octets());
+                Encoder::encode_object(buf, 4, 1, &self.common_object, &body);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
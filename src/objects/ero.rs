@@ -4,19 +4,72 @@ use nom::bytes;
 use nom::error::{Error, ErrorKind};
 use nom::{Err, IResult};
 
+use crate::encoder::{Encode, Encoder};
 use crate::objects::classes::ObjectClassType;
 use crate::objects::header::CommonObject;
 use crate::objects::types::EroObjectType;
 use crate::subobjects::header::SubObject;
 use crate::subobjects::parser::Parser;
+use crate::subobjects::prefix::Ipv4PrefixSubobject;
+use crate::subobjects::types::SubObjectTypes;
 
-#[derive(Debug, PartialEq, Eq)]
+use std::net::Ipv4Addr;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct EroObject {
     pub common_object: CommonObject,
     pub subobjects: Option<Vec<SubObject>>,
 }
 
 impl EroObject {
+    pub fn new(subobjects: Vec<SubObject>) -> Self {
+        EroObject {
+            common_object: CommonObject::new(ObjectClassType::Ero(EroObjectType::Ero)),
+            subobjects: if subobjects.is_empty() {
+                None
+            } else {
+                Some(subobjects)
+            },
+        }
+    }
+
+    // Strict hops through the given IPv4 addresses
+    pub fn from_ipv4_hops(hops: &[Ipv4Addr]) -> Self {
+        Self::new(
+            hops.iter()
+                .map(|hop| {
+                    SubObject::new(
+                        false,
+                        SubObjectTypes::Ipv4Prefix(Ipv4PrefixSubobject::new(*hop, 32)),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    // One line form, e.g. ero=[24001(10.104.105.2->10.104.105.1)]
+    pub fn summary(&self) -> String {
+        let hops: Vec<String> = self
+            .subobjects
+            .iter()
+            .flatten()
+            .map(SubObject::summary)
+            .collect();
+        format!("ero=[{}]", hops.join(" "))
+    }
+
+    // Addresses of the IPv4 prefix subobjects, in order
+    pub fn ipv4_hops(&self) -> Vec<Ipv4Addr> {
+        self.subobjects
+            .iter()
+            .flatten()
+            .filter_map(|subobject| match subobject.subobject_type {
+                SubObjectTypes::Ipv4Prefix(ref prefix) => Some(prefix.ipv4_addr),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn parse_ero_object(input: &[u8]) -> IResult<&[u8], Self> {
         let (remaining, common_object) = CommonObject::parse_common_object(input)?;
         if let ObjectClassType::Ero(EroObjectType::Ero) = common_object.object_class_type {
@@ -61,6 +114,18 @@ impl std::fmt::Display for EroObject {
     }
 }
 
+impl Encode for EroObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = vec![];
+        if let Some(ref subobjects) = self.subobjects {
+            for subobject in subobjects {
+                subobject.encode(&mut body);
+            }
+        }
+        Encoder::encode_object(buf, 7, 1, &self.common_object, &body);
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -104,4 +169,41 @@ pub mod tests {
         assert!(remaining.is_empty());
         assert_eq!(expected_ero_object, ero_object);
     }
+
+    #[test]
+    fn test_ero_object_sid_absent_hop() {
+        // Second hop has the S flag set, its NAI follows the flags directly
+        let input: &[u8] = &[
+            0x07, 0x10, 0x00, 0x20, 0x24, 0x10, 0x30, 0x01, 0x05, 0xdc, 0x30, 0x00, 0x0a, 0x68,
+            0x69, 0x02, 0x0a, 0x68, 0x69, 0x01, 0x24, 0x0c, 0x30, 0x04, 0x0a, 0x68, 0x69, 0x01,
+            0x0a, 0x68, 0x69, 0x03,
+        ];
+        let (remaining, ero_object) =
+            EroObject::parse_ero_object(input).expect("[!!] Error while parsing ero object");
+        assert!(remaining.is_empty());
+        let subobjects = ero_object
+            .subobjects
+            .as_ref()
+            .expect("[!!] Expected ero subobjects");
+        assert_eq!(subobjects.len(), 2);
+        assert_eq!(
+            subobjects[1],
+            SubObject {
+                flag_l: false,
+                subobject_len: 12,
+                subobject_type: SubObjectTypes::Sr(SrSubobject {
+                    flag_s: true,
+                    sid: 0,
+                    nai_type: NaiType::Ipv4Adj(Ipv4AdjNAI {
+                        local_ipv4: Ipv4Addr::new(10, 104, 105, 1),
+                        remote_ipv4: Ipv4Addr::new(10, 104, 105, 3),
+                    }),
+                    ..Default::default()
+                }),
+            }
+        );
+        let mut buf = Vec::new();
+        ero_object.encode(&mut buf);
+        assert_eq!(buf, input, "[!!] S flag hop did not re-encode as received");
+    }
 }
@@ -0,0 +1,136 @@
+use colored::Colorize;
+use indoc::writedoc;
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::number;
+use nom::{Err, IResult};
+
+use crate::encoder::{Encode, Encoder};
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+use crate::objects::types::ErrorObjectType;
+use crate::tlvs::tlv_parser::Parser;
+use crate::tlvs::types::Tlv;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ErrorObject {
+    pub common_object: CommonObject,
+    pub reserved: u8,
+    pub flags: u8,
+    pub error_type: u8,
+    pub error_value: u8,
+    pub tlvs: Option<Vec<Tlv>>,
+}
+
+impl ErrorObject {
+    pub fn new(error_type: u8, error_value: u8) -> Self {
+        ErrorObject {
+            common_object: CommonObject::new(ObjectClassType::Error(ErrorObjectType::Error)),
+            reserved: 0,
+            flags: 0,
+            error_type,
+            error_value,
+            tlvs: None,
+        }
+    }
+
+    // One line form, e.g. error=19/3
+    pub fn summary(&self) -> String {
+        format!("error={}/{}", self.error_type, self.error_value)
+    }
+
+    pub fn parse_error_object(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, common_object) = CommonObject::parse_common_object(input)?;
+        if let ObjectClassType::Error(ErrorObjectType::Error) = common_object.object_class_type {
+            let object_body_len = common_object.object_length - 4;
+            let (remaining, object_body) =
+                bytes::streaming::take(object_body_len as usize)(remaining)?;
+            let (object_body, reserved) = number::streaming::be_u8(object_body)?;
+            let (object_body, flags) = number::streaming::be_u8(object_body)?;
+            let (object_body, error_type) = number::streaming::be_u8(object_body)?;
+            let (object_body, error_value) = number::streaming::be_u8(object_body)?;
+            let mut error_object = ErrorObject {
+                common_object,
+                reserved,
+                flags,
+                error_type,
+                error_value,
+                tlvs: None,
+            };
+            if !object_body.is_empty() {
+                let (_object_body, tlvs) = Parser::parse_tlvs(object_body)?;
+                error_object.tlvs = Some(tlvs);
+            }
+            return Ok((remaining, error_object));
+        }
+        Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
+    }
+}
+
+impl std::fmt::Display for ErrorObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tlvs_str = String::new();
+        if let Some(ref tlvs) = self.tlvs {
+            for t in tlvs {
+                let output = format!("{}", t);
+                tlvs_str.push_str(&output)
+            }
+        }
+        let title = "==[PCEP-ERROR Object]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_object}
+                reserved               = {reserved}
+                flags                  = {flags}
+                error_type             = {error_type}
+                error_value            = {error_value}
+            {tlv_str}
+            "#,
+            title = title,
+            common_object = self.common_object,
+            reserved = self.reserved,
+            flags = self.flags,
+            error_type = self.error_type,
+            error_value = self.error_value,
+            tlv_str = tlvs_str
+        )
+    }
+}
+
+impl Encode for ErrorObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = vec![self.reserved, self.flags, self.error_type, self.error_value];
+        Encoder::encode_tlvs(&mut body, &self.tlvs);
+        Encoder::encode_object(buf, 13, 1, &self.common_object, &body);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[test]
+    fn test_error_object_parsing() {
+        let input: &[u8] = &[0x0d, 0x10, 0x00, 0x08, 0x00, 0x00, 0x01, 0x02];
+        let (remaining, error_object) =
+            ErrorObject::parse_error_object(input).expect("[!!] Failed to parse error object");
+        let expected_cobj = CommonObject {
+            object_class_type: ObjectClassType::Error(ErrorObjectType::Error),
+            reserved: 0,
+            flag_process: false,
+            flag_ignore: false,
+            object_length: 8,
+        };
+        let expected_error_object = ErrorObject {
+            common_object: expected_cobj,
+            reserved: 0,
+            flags: 0,
+            error_type: 1,
+            error_value: 2,
+            tlvs: None,
+        };
+        assert!(remaining.is_empty(), "[!!] Nope, object not eaten fully");
+        assert_eq!(error_object, expected_error_object);
+    }
+}
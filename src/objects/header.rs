@@ -1,14 +1,14 @@
 use colored::Colorize;
 use indoc::writedoc;
 use nom::bits;
-use nom::error::Error;
+use nom::error::{Error, ErrorKind};
 use nom::number;
 use nom::sequence::tuple;
-use nom::IResult;
+use nom::{Err, IResult};
 
 use crate::objects::classes::ObjectClassType;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct CommonObject {
     pub object_class_type: ObjectClassType,
     pub reserved: u8,
@@ -18,6 +18,17 @@ pub struct CommonObject {
 }
 
 impl CommonObject {
+    // Header for a locally built object, object_length is set when encoding
+    pub fn new(object_class_type: ObjectClassType) -> Self {
+        CommonObject {
+            object_class_type,
+            reserved: 0,
+            flag_process: false,
+            flag_ignore: false,
+            object_length: 0,
+        }
+    }
+
     fn parse_typ_res_p_i(input: &[u8]) -> IResult<&[u8], (u8, u8, u8, u8)> {
         bits::bits::<_, _, Error<_>, _, _>(tuple((
             bits::streaming::take(4u8),
@@ -27,12 +38,17 @@ impl CommonObject {
         )))(input)
     }
 
+    // object_length covers this header, so anything below 4 is rejected
+    // here and object parsers can take object_length - 4 body bytes
     pub fn parse_common_object(input: &[u8]) -> IResult<&[u8], CommonObject> {
+        let start = input;
         let (input, object_class) = number::streaming::be_u8(input)?;
         let (input, typ_res_p_i) = Self::parse_typ_res_p_i(input)?;
         let (input, object_length) = number::streaming::be_u16(input)?;
+        if object_length < 4 {
+            return Err(Err::Failure(Error::new(start, ErrorKind::LengthValue)));
+        }
 
-        let object_class = object_class;
         let object_type = typ_res_p_i.0;
 
         let object_class_type: ObjectClassType = (object_class, object_type).into();
@@ -46,6 +62,16 @@ impl CommonObject {
         };
         Ok((input, cobj))
     }
+
+    // Class of the next object without consuming it. None at the end of
+    // a complete body, Incomplete if the header itself is cut short.
+    pub fn peek_object_class(input: &[u8]) -> IResult<&[u8], Option<ObjectClassType>> {
+        if input.is_empty() {
+            return Ok((input, None));
+        }
+        let (_remaining, cobj) = Self::parse_common_object(input)?;
+        Ok((input, Some(cobj.object_class_type)))
+    }
 }
 
 impl std::fmt::Display for CommonObject {
@@ -0,0 +1,124 @@
+use colored::Colorize;
+use indoc::writedoc;
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::number;
+use nom::{Err, IResult};
+
+use crate::encoder::{Encode, Encoder};
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+use crate::objects::types::LoadBalancingObjectType;
+
+#[derive(Debug)]
+pub struct LoadBalancingObject {
+    pub common_object: CommonObject,
+    pub reserved: u16,
+    pub flags: u8,
+    pub max_lsp: u8,
+    pub min_bandwidth: f32,
+}
+
+impl Eq for LoadBalancingObject {}
+impl PartialEq for LoadBalancingObject {
+    fn eq(&self, other: &Self) -> bool {
+        let t1 = self.common_object.eq(&other.common_object);
+        let t2 = self.reserved.eq(&other.reserved);
+        let t3 = self.flags.eq(&other.flags);
+        let t4 = self.max_lsp.eq(&other.max_lsp);
+        // TODO: Use strong float comparsion heuristics.
+        let t5 = (other.min_bandwidth - self.min_bandwidth) as u32 == 0;
+        t1 && t2 && t3 && t4 && t5
+    }
+}
+
+impl LoadBalancingObject {
+    pub fn parse_load_balancing_object(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, common_object) = CommonObject::parse_common_object(input)?;
+        if let ObjectClassType::LoadBalancing(LoadBalancingObjectType::LoadBalancing) =
+            common_object.object_class_type
+        {
+            let object_body_len = common_object.object_length - 4;
+            let (remaining, object_body) =
+                bytes::streaming::take(object_body_len as usize)(remaining)?;
+            let (object_body, reserved) = number::streaming::be_u16(object_body)?;
+            let (object_body, flags) = number::streaming::be_u8(object_body)?;
+            let (object_body, max_lsp) = number::streaming::be_u8(object_body)?;
+            let (_object_body, min_bandwidth) = number::streaming::be_f32(object_body)?;
+            let load_balancing_object = LoadBalancingObject {
+                common_object,
+                reserved,
+                flags,
+                max_lsp,
+                min_bandwidth,
+            };
+            return Ok((remaining, load_balancing_object));
+        }
+        Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
+    }
+}
+
+impl std::fmt::Display for LoadBalancingObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = "==[LOAD-BALANCING Object]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_object}
+                reserved               = {reserved}
+                flags                  = {flags}
+                max_lsp                = {max_lsp}
+                min_bandwidth          = {min_bandwidth}
+            "#,
+            title = title,
+            common_object = self.common_object,
+            reserved = self.reserved,
+            flags = self.flags,
+            max_lsp = self.max_lsp,
+            min_bandwidth = self.min_bandwidth
+        )
+    }
+}
+
+impl Encode for LoadBalancingObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = self.reserved.to_be_bytes().to_vec();
+        body.push(self.flags);
+        body.push(self.max_lsp);
+        body.extend_from_slice(&self.min_bandwidth.to_be_bytes());
+        Encoder::encode_object(buf, 14, 1, &self.common_object, &body);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[test]
+    fn test_load_balancing_object_parsing() {
+        let input: &[u8] = &[
+            0x0e, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x04, 0x49, 0x74, 0x24, 0x00,
+        ];
+        let (remaining, load_balancing_object) =
+            LoadBalancingObject::parse_load_balancing_object(input)
+                .expect("[!!] Failed to parse load-balancing object");
+        let expected_cobj = CommonObject {
+            object_class_type: ObjectClassType::LoadBalancing(
+                LoadBalancingObjectType::LoadBalancing,
+            ),
+            reserved: 0,
+            flag_process: false,
+            flag_ignore: false,
+            object_length: 12,
+        };
+        let expected_load_balancing_object = LoadBalancingObject {
+            common_object: expected_cobj,
+            reserved: 0,
+            flags: 0,
+            max_lsp: 4,
+            min_bandwidth: 1_000_000_f32,
+        };
+        assert!(remaining.is_empty(), "[!!] Nope, object not eaten fully");
+        assert_eq!(load_balancing_object, expected_load_balancing_object);
+    }
+}
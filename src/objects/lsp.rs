@@ -7,8 +7,10 @@ use nom::sequence::tuple;
 use nom::{Err, IResult};
 
 use crate::common::OperationalStatus;
+use crate::encoder::{Encode, Encoder};
 use crate::objects::header::CommonObject;
 use crate::tlvs::tlv_parser::Parser;
+use crate::tlvs::tlv_set::{Ipv4LSPIndetifiersTLV, LspErrorCode};
 use crate::tlvs::types::Tlv;
 
 use super::classes::ObjectClassType;
@@ -30,6 +32,21 @@ pub struct LspObject {
 type PlspIdResOperFlagsARSDTuple = (u32, u8, u8, u8, u8, u8, u8, u8);
 
 impl LspObject {
+    // All flags cleared, callers set the ones they need
+    pub fn new(plsp_id: u32, operational_status: OperationalStatus, tlvs: Vec<Tlv>) -> Self {
+        LspObject {
+            common_object: CommonObject::new(ObjectClassType::Lsp(LspObjectType::Lsp)),
+            plsp_id,
+            flag_create: false,
+            operational_status,
+            flag_administrative: false,
+            flag_remove: false,
+            flag_sync: false,
+            flag_delegate: false,
+            tlvs: if tlvs.is_empty() { None } else { Some(tlvs) },
+        }
+    }
+
     // Parse
     // plsp-id : 20 bits
     // reserved: 4 bits
@@ -82,6 +99,65 @@ impl LspObject {
         }
         Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
     }
+
+    pub fn lsp_db_version(&self) -> Option<u64> {
+        self.tlvs.as_ref()?.iter().find_map(|t| match t {
+            Tlv::LspDbVersion(ldv) => Some(ldv.lsp_state_db_version),
+            _ => None,
+        })
+    }
+
+    pub fn lsp_error_code(&self) -> Option<LspErrorCode> {
+        self.tlvs.as_ref()?.iter().find_map(|t| match t {
+            Tlv::LspErrorCode(lec) => Some(lec.error_code),
+            _ => None,
+        })
+    }
+
+    // One line form, e.g. plsp=235 name=cfg_PSADEL-5 D A
+    pub fn summary(&self) -> String {
+        let mut fields = vec![format!("plsp={}", self.plsp_id)];
+        if let Some(name) = self.symbolic_path_name() {
+            fields.push(format!("name={}", name));
+        }
+        for (flag, letter) in [
+            (self.flag_delegate, "D"),
+            (self.flag_sync, "S"),
+            (self.flag_remove, "R"),
+            (self.flag_administrative, "A"),
+            (self.flag_create, "C"),
+        ] {
+            if flag {
+                fields.push(String::from(letter));
+            }
+        }
+        fields.join(" ")
+    }
+
+    pub fn ipv4_lsp_identifiers(&self) -> Option<&Ipv4LSPIndetifiersTLV> {
+        self.tlvs.as_ref()?.iter().find_map(|t| match t {
+            Tlv::Ipv4LSPIndetifiers(lsp_identifiers) => Some(lsp_identifiers),
+            _ => None,
+        })
+    }
+
+    // IPV4-LSP-IDENTIFIERS TLV, or the IPV6 one (type 19) which is only
+    // kept undecoded or by a registered TlvDecoder
+    pub fn has_lsp_identifiers(&self) -> bool {
+        self.tlvs.iter().flatten().any(|t| match t {
+            Tlv::Ipv4LSPIndetifiers(_) => true,
+            Tlv::Unknown(unknown) => unknown.tlv_type == 19,
+            Tlv::Custom(custom) => custom.tlv_type() == 19,
+            _ => false,
+        })
+    }
+
+    pub fn symbolic_path_name(&self) -> Option<&str> {
+        self.tlvs.as_ref()?.iter().find_map(|t| match t {
+            Tlv::SymbolicPathName(spn) => Some(spn.symbolic_path_name.as_str()),
+            _ => None,
+        })
+    }
 }
 
 impl std::fmt::Display for LspObject {
@@ -123,10 +199,25 @@ impl std::fmt::Display for LspObject {
     }
 }
 
+impl Encode for LspObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let plsp_id_flags: u32 = (self.plsp_id << 12)
+            | (self.flag_create as u32) << 7
+            | (u8::from(self.operational_status) as u32 & 0b111) << 4
+            | (self.flag_administrative as u32) << 3
+            | (self.flag_remove as u32) << 2
+            | (self.flag_sync as u32) << 1
+            | self.flag_delegate as u32;
+        let mut body = plsp_id_flags.to_be_bytes().to_vec();
+        Encoder::encode_tlvs(&mut body, &self.tlvs);
+        Encoder::encode_object(buf, 32, 1, &self.common_object, &body);
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
-    use crate::tlvs::tlv_set::{Ipv4LSPIndetifiersTLV, SymbolicPathNameTLV};
+    use crate::tlvs::tlv_set::SymbolicPathNameTLV;
     use crate::tlvs::types::Tlv;
     use std::net::Ipv4Addr;
     #[test]
@@ -7,6 +7,7 @@ use nom::number;
 use nom::sequence::tuple;
 use nom::{Err, IResult};
 
+use crate::encoder::{Encode, Encoder};
 use crate::objects::classes::ObjectClassType;
 use crate::objects::header::CommonObject;
 use crate::objects::types::LspaObjectType;
@@ -66,6 +67,10 @@ impl LspaObject {
         }
         Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
     }
+
+    pub fn common_object(&self) -> &CommonObject {
+        &self.common_object
+    }
 }
 
 impl std::fmt::Display for LspaObject {
@@ -106,6 +111,20 @@ impl std::fmt::Display for LspaObject {
     }
 }
 
+impl Encode for LspaObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = self.exclude_any.to_be_bytes().to_vec();
+        body.extend_from_slice(&self.include_any.to_be_bytes());
+        body.extend_from_slice(&self.include_all.to_be_bytes());
+        body.push(self.setup_priority);
+        body.push(self.holding_priority);
+        body.push(self.flag_local_protection as u8);
+        body.push(self.reserved);
+        Encoder::encode_tlvs(&mut body, &self.tlvs);
+        Encoder::encode_object(buf, 9, 1, &self.common_object, &body);
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
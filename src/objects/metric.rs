@@ -7,17 +7,37 @@ use nom::number;
 use nom::sequence::tuple;
 use nom::{Err, IResult};
 
+use crate::encoder::{Encode, Encoder};
 use crate::objects::classes::ObjectClassType;
 use crate::objects::header::CommonObject;
 use crate::objects::types::MetricObjectType;
 
+// METRIC object T field, as per IANA "METRIC Object T Field" registry
+#[non_exhaustive]
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum MetricType {
     Igp,
     Te,
     HopCount,
+    AggregateBandwidthConsumption,
+    MostLoadedLink,
+    CumulativeIgp,
+    CumulativeTe,
+    P2mpIgp,
+    P2mpTe,
+    P2mpHopCount,
+    // Also carries the SRv6 SID depth (RFC 9603), bounded by the MSD
     SidDepth,
     PathDelay,
+    PathDelayVariation,
+    PathLoss,
+    P2mpPathDelay,
+    P2mpPathDelayVariation,
+    P2mpPathLoss,
+    NumberOfAdaptations,
+    NumberOfLayers,
+    DomainCount,
+    BorderNodeCount,
     Unknown(u8),
 }
 
@@ -27,27 +47,158 @@ impl From<u8> for MetricType {
             1 => MetricType::Igp,
             2 => MetricType::Te,
             3 => MetricType::HopCount,
+            4 => MetricType::AggregateBandwidthConsumption,
+            5 => MetricType::MostLoadedLink,
+            6 => MetricType::CumulativeIgp,
+            7 => MetricType::CumulativeTe,
+            8 => MetricType::P2mpIgp,
+            9 => MetricType::P2mpTe,
+            10 => MetricType::P2mpHopCount,
             11 => MetricType::SidDepth,
             12 => MetricType::PathDelay,
+            13 => MetricType::PathDelayVariation,
+            14 => MetricType::PathLoss,
+            15 => MetricType::P2mpPathDelay,
+            16 => MetricType::P2mpPathDelayVariation,
+            17 => MetricType::P2mpPathLoss,
+            18 => MetricType::NumberOfAdaptations,
+            19 => MetricType::NumberOfLayers,
+            20 => MetricType::DomainCount,
+            21 => MetricType::BorderNodeCount,
             _ => MetricType::Unknown(value),
         }
     }
 }
 
+impl From<MetricType> for u8 {
+    fn from(value: MetricType) -> Self {
+        match value {
+            MetricType::Igp => 1,
+            MetricType::Te => 2,
+            MetricType::HopCount => 3,
+            MetricType::AggregateBandwidthConsumption => 4,
+            MetricType::MostLoadedLink => 5,
+            MetricType::CumulativeIgp => 6,
+            MetricType::CumulativeTe => 7,
+            MetricType::P2mpIgp => 8,
+            MetricType::P2mpTe => 9,
+            MetricType::P2mpHopCount => 10,
+            MetricType::SidDepth => 11,
+            MetricType::PathDelay => 12,
+            MetricType::PathDelayVariation => 13,
+            MetricType::PathLoss => 14,
+            MetricType::P2mpPathDelay => 15,
+            MetricType::P2mpPathDelayVariation => 16,
+            MetricType::P2mpPathLoss => 17,
+            MetricType::NumberOfAdaptations => 18,
+            MetricType::NumberOfLayers => 19,
+            MetricType::DomainCount => 20,
+            MetricType::BorderNodeCount => 21,
+            MetricType::Unknown(x) => x,
+        }
+    }
+}
+
+impl MetricType {
+    // Unit in which metric_value is expressed for this metric type
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Self::Igp | Self::Te | Self::CumulativeIgp | Self::CumulativeTe => "",
+            Self::P2mpIgp | Self::P2mpTe => "",
+            Self::HopCount | Self::P2mpHopCount => "hops",
+            Self::AggregateBandwidthConsumption => "bytes/sec",
+            Self::MostLoadedLink => "%",
+            Self::SidDepth => "SIDs",
+            Self::PathDelay | Self::P2mpPathDelay => "us",
+            Self::PathDelayVariation | Self::P2mpPathDelayVariation => "us",
+            Self::PathLoss | Self::P2mpPathLoss => "%",
+            Self::NumberOfAdaptations => "adaptations",
+            Self::NumberOfLayers => "layers",
+            Self::DomainCount => "domains",
+            Self::BorderNodeCount => "border nodes",
+            Self::Unknown(_) => "",
+        }
+    }
+
+    // Lowercase name used in one line summaries, e.g. "igp" or "path-delay"
+    pub fn short_name(&self) -> String {
+        match self {
+            Self::Igp => String::from("igp"),
+            Self::Te => String::from("te"),
+            Self::HopCount => String::from("hopcount"),
+            Self::AggregateBandwidthConsumption => String::from("aggregate-bandwidth-consumption"),
+            Self::MostLoadedLink => String::from("most-loaded-link"),
+            Self::CumulativeIgp => String::from("cumulative-igp"),
+            Self::CumulativeTe => String::from("cumulative-te"),
+            Self::P2mpIgp => String::from("p2mp-igp"),
+            Self::P2mpTe => String::from("p2mp-te"),
+            Self::P2mpHopCount => String::from("p2mp-hopcount"),
+            Self::SidDepth => String::from("sid-depth"),
+            Self::PathDelay => String::from("path-delay"),
+            Self::PathDelayVariation => String::from("path-delay-variation"),
+            Self::PathLoss => String::from("path-loss"),
+            Self::P2mpPathDelay => String::from("p2mp-path-delay"),
+            Self::P2mpPathDelayVariation => String::from("p2mp-path-delay-variation"),
+            Self::P2mpPathLoss => String::from("p2mp-path-loss"),
+            Self::NumberOfAdaptations => String::from("number-of-adaptations"),
+            Self::NumberOfLayers => String::from("number-of-layers"),
+            Self::DomainCount => String::from("domain-count"),
+            Self::BorderNodeCount => String::from("border-node-count"),
+            Self::Unknown(x) => format!("unknown{}", x),
+        }
+    }
+}
+
 impl std::fmt::Display for MetricType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Igp => write!(f, "MetricType::IGP"),
             Self::Te => write!(f, "MetricType::TE"),
             Self::HopCount => write!(f, "MetricType::HopCount"),
+            Self::AggregateBandwidthConsumption => {
+                write!(f, "MetricType::Aggregate-Bandwidth-Consumption")
+            }
+            Self::MostLoadedLink => write!(f, "MetricType::Most-Loaded-Link"),
+            Self::CumulativeIgp => write!(f, "MetricType::Cumulative-IGP"),
+            Self::CumulativeTe => write!(f, "MetricType::Cumulative-TE"),
+            Self::P2mpIgp => write!(f, "MetricType::P2MP-IGP"),
+            Self::P2mpTe => write!(f, "MetricType::P2MP-TE"),
+            Self::P2mpHopCount => write!(f, "MetricType::P2MP-HopCount"),
             Self::SidDepth => write!(f, "MetricType::SID-Depth"),
             Self::PathDelay => write!(f, "MetricType::Path-Delay"),
+            Self::PathDelayVariation => write!(f, "MetricType::Path-Delay-Variation"),
+            Self::PathLoss => write!(f, "MetricType::Path-Loss"),
+            Self::P2mpPathDelay => write!(f, "MetricType::P2MP-Path-Delay"),
+            Self::P2mpPathDelayVariation => write!(f, "MetricType::P2MP-Path-Delay-Variation"),
+            Self::P2mpPathLoss => write!(f, "MetricType::P2MP-Path-Loss"),
+            Self::NumberOfAdaptations => write!(f, "MetricType::Number-Of-Adaptations"),
+            Self::NumberOfLayers => write!(f, "MetricType::Number-Of-Layers"),
+            Self::DomainCount => write!(f, "MetricType::Domain-Count"),
+            Self::BorderNodeCount => write!(f, "MetricType::Border-Node-Count"),
             Self::Unknown(x) => write!(f, "Unknown{}", *x),
         }
     }
 }
 
-#[derive(Debug)]
+// How the metric value should be read, derived from the B flag
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MetricInterpretation {
+    // B flag set: metric_value is an upper bound the path must not exceed
+    Bound,
+    // B flag clear: metric is an objective to optimize (or the cost of a computed path)
+    Optimized,
+}
+
+impl std::fmt::Display for MetricInterpretation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bound => write!(f, "MetricInterpretation::Bound"),
+            Self::Optimized => write!(f, "MetricInterpretation::Optimized"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct MetricObject {
     common_object: CommonObject,
     reserved: u16,
@@ -72,6 +223,32 @@ impl PartialEq for MetricObject {
 }
 
 impl MetricObject {
+    pub fn new(metric_type: MetricType, metric_value: f32, flag_bound: bool) -> Self {
+        MetricObject {
+            common_object: CommonObject::new(ObjectClassType::Metric(MetricObjectType::Metric)),
+            reserved: 0,
+            flag_compute: false,
+            flag_bound,
+            metric_type,
+            metric_value,
+        }
+    }
+
+    pub(crate) fn common_object_mut(&mut self) -> &mut CommonObject {
+        &mut self.common_object
+    }
+
+    // One line form, e.g. igp=10, or igp<=10 for a bound
+    pub fn summary(&self) -> String {
+        let operator = if self.flag_bound { "<=" } else { "=" };
+        format!(
+            "{}{}{}",
+            self.metric_type.short_name(),
+            operator,
+            self.metric_value
+        )
+    }
+
     fn parse_res_flag_c_b(input: &[u8]) -> IResult<&[u8], (u8, u8, u8)> {
         bits::bits::<_, _, Error<_>, _, _>(tuple((
             bits::streaming::take(6u8),
@@ -94,7 +271,7 @@ impl MetricObject {
             let metric_object = MetricObject {
                 common_object: cobj,
                 reserved,
-                flag_compute: res_flag_c_b.0 & 0b1 == 0b1,
+                flag_compute: res_flag_c_b.1 & 0b1 == 0b1,
                 flag_bound: res_flag_c_b.2 & 0b1 == 0b1,
                 metric_type: metric_type.into(),
                 metric_value,
@@ -103,6 +280,31 @@ impl MetricObject {
         }
         Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
     }
+
+    pub fn metric_type(&self) -> MetricType {
+        self.metric_type
+    }
+
+    pub fn metric_value(&self) -> f32 {
+        self.metric_value
+    }
+
+    pub fn interpretation(&self) -> MetricInterpretation {
+        if self.flag_bound {
+            MetricInterpretation::Bound
+        } else {
+            MetricInterpretation::Optimized
+        }
+    }
+
+    // C flag: the PCE is asked to return the computed metric value
+    pub fn is_computed(&self) -> bool {
+        self.flag_compute
+    }
+
+    pub fn common_object(&self) -> &CommonObject {
+        &self.common_object
+    }
 }
 
 impl std::fmt::Display for MetricObject {
@@ -117,19 +319,34 @@ impl std::fmt::Display for MetricObject {
                 flag_compute = {flag_compute}
                 flag_bound = {flag_bound}
                 metric_type = {metric_type}
-                metric_value = {metric_value}
+                metric_value = {metric_value} {unit}
+                interpretation = {interpretation}
+                computed = {computed}
             "#,
             title = title,
             common_object = self.common_object,
             reserved = self.reserved,
             flag_compute = self.flag_compute,
             flag_bound = self.flag_bound,
-            metric_type = self.metric_type,
-            metric_value = self.metric_value
+            metric_type = self.metric_type(),
+            metric_value = self.metric_value(),
+            unit = self.metric_type().unit(),
+            interpretation = self.interpretation(),
+            computed = self.is_computed()
         )
     }
 }
 
+impl Encode for MetricObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = self.reserved.to_be_bytes().to_vec();
+        body.push((self.flag_compute as u8) << 1 | self.flag_bound as u8);
+        body.push(self.metric_type.into());
+        body.extend_from_slice(&self.metric_value.to_be_bytes());
+        Encoder::encode_object(buf, 6, 1, &self.common_object, &body);
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -153,7 +370,7 @@ pub mod tests {
             flag_bound: true,
             flag_compute: false,
             metric_type: MetricType::SidDepth,
-            metric_value: 10 as f32,
+            metric_value: 10_f32,
         };
         assert!(remaining.is_empty());
         assert_eq!(expected_metric_object, metric_object);
@@ -178,9 +395,41 @@ pub mod tests {
             flag_bound: false,
             flag_compute: false,
             metric_type: MetricType::PathDelay,
-            metric_value: 0 as f32,
+            metric_value: 0_f32,
         };
         assert!(remaining.is_empty());
         assert_eq!(expected_metric_object, metric_object);
     }
+
+    #[test]
+    fn test_metric_object_flags_and_units() {
+        // Path loss metric, C and B set, 0.5%
+        let input: &[u8] = &[
+            0x06, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x03, 0x0e, 0x3f, 0x00, 0x00, 0x00,
+        ];
+        let (remaining, metric_object) =
+            MetricObject::parse_metric_object(input).expect("[!!] Error parsing the metric object");
+        assert!(remaining.is_empty());
+        assert_eq!(metric_object.metric_type(), MetricType::PathLoss);
+        assert_eq!(metric_object.metric_type().unit(), "%");
+        assert_eq!(metric_object.interpretation(), MetricInterpretation::Bound);
+        assert!(metric_object.is_computed());
+        assert_eq!(metric_object.metric_value(), 0.5_f32);
+        assert_eq!(metric_object.summary(), "path-loss<=0.5");
+    }
+
+    #[test]
+    fn test_metric_type_registry() {
+        assert_eq!(
+            MetricType::from(4),
+            MetricType::AggregateBandwidthConsumption
+        );
+        assert_eq!(MetricType::from(5), MetricType::MostLoadedLink);
+        assert_eq!(MetricType::from(10), MetricType::P2mpHopCount);
+        assert_eq!(MetricType::from(13), MetricType::PathDelayVariation);
+        assert_eq!(MetricType::from(21), MetricType::BorderNodeCount);
+        assert_eq!(MetricType::from(22), MetricType::Unknown(22));
+        assert_eq!(MetricType::from(200), MetricType::Unknown(200));
+        assert_eq!(MetricType::Unknown(200).short_name(), "unknown200");
+    }
 }
@@ -1,11 +1,22 @@
+pub mod any;
 pub mod bandwidth;
 pub mod classes;
+pub mod classtype;
+pub mod close;
 pub mod endpoints;
 pub mod ero;
+pub mod error;
 pub mod header;
+pub mod loadbalancing;
 pub mod lsp;
 pub mod lspa;
 pub mod metric;
+pub mod nopath;
+pub mod of;
 pub mod open;
+pub mod pathattrib;
+pub mod rp;
 pub mod srp;
+pub mod svec;
 pub mod types;
+pub mod unknown;
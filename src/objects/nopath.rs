@@ -0,0 +1,177 @@
+use colored::Colorize;
+use indoc::writedoc;
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::number;
+use nom::{Err, IResult};
+
+use crate::encoder::{Encode, Encoder};
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+use crate::objects::types::NoPathObjectType;
+use crate::tlvs::tlv_parser::Parser;
+use crate::tlvs::types::Tlv;
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NatureOfIssue {
+    NoPathFound,
+    PceChainBroken,
+    Unknown(u8),
+}
+
+impl From<u8> for NatureOfIssue {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::NoPathFound,
+            1 => Self::PceChainBroken,
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+impl From<NatureOfIssue> for u8 {
+    fn from(value: NatureOfIssue) -> Self {
+        match value {
+            NatureOfIssue::NoPathFound => 0,
+            NatureOfIssue::PceChainBroken => 1,
+            NatureOfIssue::Unknown(x) => x,
+        }
+    }
+}
+
+impl std::fmt::Display for NatureOfIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoPathFound => write!(f, "NatureOfIssue::NoPathFound"),
+            Self::PceChainBroken => write!(f, "NatureOfIssue::PceChainBroken"),
+            Self::Unknown(x) => write!(f, "NatureOfIssue::Unknown({})", *x),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NoPathObject {
+    pub common_object: CommonObject,
+    pub nature_of_issue: NatureOfIssue,
+    pub flags: u16,
+    pub flag_constraints: bool,
+    pub reserved: u8,
+    pub tlvs: Option<Vec<Tlv>>,
+}
+
+impl NoPathObject {
+    pub fn parse_no_path_object(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, common_object) = CommonObject::parse_common_object(input)?;
+        if let ObjectClassType::NoPath(NoPathObjectType::NoPath) = common_object.object_class_type {
+            let object_body_len = common_object.object_length - 4;
+            let (remaining, object_body) =
+                bytes::streaming::take(object_body_len as usize)(remaining)?;
+            let (object_body, nature_of_issue) = number::streaming::be_u8(object_body)?;
+            let (object_body, flags) = number::streaming::be_u16(object_body)?;
+            let (object_body, reserved) = number::streaming::be_u8(object_body)?;
+            let mut no_path_object = NoPathObject {
+                common_object,
+                nature_of_issue: nature_of_issue.into(),
+                flags,
+                flag_constraints: flags & 0x8000 == 0x8000,
+                reserved,
+                tlvs: None,
+            };
+            if !object_body.is_empty() {
+                let (_object_body, tlvs) = Parser::parse_tlvs(object_body)?;
+                no_path_object.tlvs = Some(tlvs);
+            }
+            return Ok((remaining, no_path_object));
+        }
+        Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
+    }
+}
+
+impl std::fmt::Display for NoPathObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tlvs_str = String::new();
+        if let Some(ref tlvs) = self.tlvs {
+            for t in tlvs {
+                let output = format!("{}", t);
+                tlvs_str.push_str(&output)
+            }
+        }
+        let title = "==[NO-PATH Object]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_object}
+                nature_of_issue        = {nature_of_issue}
+                flags                  = {flags}
+                flag_constraints       = {flag_c}
+                reserved               = {reserved}
+            {tlv_str}
+            "#,
+            title = title,
+            common_object = self.common_object,
+            nature_of_issue = self.nature_of_issue,
+            flags = self.flags,
+            flag_c = self.flag_constraints,
+            reserved = self.reserved,
+            tlv_str = tlvs_str
+        )
+    }
+}
+
+impl Encode for NoPathObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let flags = (self.flags & !0x8000) | (self.flag_constraints as u16) << 15;
+        let mut body = vec![u8::from(self.nature_of_issue)];
+        body.extend_from_slice(&flags.to_be_bytes());
+        body.push(self.reserved);
+        Encoder::encode_tlvs(&mut body, &self.tlvs);
+        Encoder::encode_object(buf, 3, 1, &self.common_object, &body);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::tlvs::tlv_set::NoPathVectorTLV;
+    #[test]
+    fn test_no_path_object_parsing() {
+        let input: &[u8] = &[
+            0x03, 0x10, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0x00, 0x01, 0x00, 0x04, 0x00, 0x00,
+            0x00, 0x03,
+        ];
+        let (remaining, no_path_object) =
+            NoPathObject::parse_no_path_object(input).expect("[!!] Failed to parse no-path object");
+        let expected_cobj = CommonObject {
+            object_class_type: ObjectClassType::NoPath(NoPathObjectType::NoPath),
+            reserved: 0,
+            flag_process: false,
+            flag_ignore: false,
+            object_length: 16,
+        };
+        let expected_no_path_vector_tlv = NoPathVectorTLV {
+            tlv_type: 1,
+            tlv_len: 4,
+            flags: 3,
+            flag_pce_unavailable: true,
+            flag_unknown_destination: true,
+            flag_unknown_source: false,
+            flag_brpc_chain_unavailable: false,
+            flag_pks_expansion_failure: false,
+            flag_no_gco_migration_path: false,
+            flag_no_gco_solution: false,
+            flag_p2mp_reachability: false,
+        };
+        let expected_no_path_object = NoPathObject {
+            common_object: expected_cobj,
+            nature_of_issue: NatureOfIssue::NoPathFound,
+            flags: 0x8000,
+            flag_constraints: true,
+            reserved: 0,
+            tlvs: Some(vec![Tlv::NoPathVector(expected_no_path_vector_tlv)]),
+        };
+        assert!(remaining.is_empty(), "[!!] Nope, object not eaten fully");
+        assert_eq!(no_path_object, expected_no_path_object);
+    }
+}
@@ -0,0 +1,225 @@
+use colored::Colorize;
+use indoc::writedoc;
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::number;
+use nom::{Err, IResult};
+
+use crate::encoder::{Encode, Encoder};
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+use crate::objects::types::OfObjectType;
+use crate::tlvs::tlv_parser::Parser;
+use crate::tlvs::types::Tlv;
+
+// Objective function codes, as per IANA "Objective Function" registry
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ObjectiveFunction {
+    // Minimum Cost Path
+    Mcp,
+    // Minimum Load Path
+    Mlp,
+    // Maximum residual Bandwidth Path
+    Mbp,
+    // Minimize aggregate Bandwidth Consumption
+    Mbc,
+    // Minimize the Load of the most loaded Link
+    Mll,
+    // Minimize the Cumulative Cost of a set of paths
+    Mcc,
+    // Shortest Path Tree
+    Spt,
+    // Minimum Cost Tree
+    Mct,
+    // Minimum Packet Loss Path
+    Mplp,
+    // Maximum Under-Utilized Path
+    Mup,
+    // Maximum Reserved Under-Utilized Path
+    Mrup,
+    // Minimize the number of Transit Domains
+    Mtd,
+    // Minimize the number of Border Nodes
+    Mbn,
+    // Minimize the number of Common Transit Domains
+    Mctd,
+    // Minimize the number of Shared Links
+    Msl,
+    // Minimize the number of Shared SRLGs
+    Mss,
+    // Minimize the number of Shared Nodes
+    Msn,
+    Unknown(u16),
+}
+
+impl From<u16> for ObjectiveFunction {
+    fn from(value: u16) -> Self {
+        match value {
+            1 => Self::Mcp,
+            2 => Self::Mlp,
+            3 => Self::Mbp,
+            4 => Self::Mbc,
+            5 => Self::Mll,
+            6 => Self::Mcc,
+            7 => Self::Spt,
+            8 => Self::Mct,
+            9 => Self::Mplp,
+            10 => Self::Mup,
+            11 => Self::Mrup,
+            12 => Self::Mtd,
+            13 => Self::Mbn,
+            14 => Self::Mctd,
+            15 => Self::Msl,
+            16 => Self::Mss,
+            17 => Self::Msn,
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+impl From<ObjectiveFunction> for u16 {
+    fn from(value: ObjectiveFunction) -> Self {
+        match value {
+            ObjectiveFunction::Mcp => 1,
+            ObjectiveFunction::Mlp => 2,
+            ObjectiveFunction::Mbp => 3,
+            ObjectiveFunction::Mbc => 4,
+            ObjectiveFunction::Mll => 5,
+            ObjectiveFunction::Mcc => 6,
+            ObjectiveFunction::Spt => 7,
+            ObjectiveFunction::Mct => 8,
+            ObjectiveFunction::Mplp => 9,
+            ObjectiveFunction::Mup => 10,
+            ObjectiveFunction::Mrup => 11,
+            ObjectiveFunction::Mtd => 12,
+            ObjectiveFunction::Mbn => 13,
+            ObjectiveFunction::Mctd => 14,
+            ObjectiveFunction::Msl => 15,
+            ObjectiveFunction::Mss => 16,
+            ObjectiveFunction::Msn => 17,
+            ObjectiveFunction::Unknown(x) => x,
+        }
+    }
+}
+
+impl std::fmt::Display for ObjectiveFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mcp => write!(f, "ObjectiveFunction::MCP"),
+            Self::Mlp => write!(f, "ObjectiveFunction::MLP"),
+            Self::Mbp => write!(f, "ObjectiveFunction::MBP"),
+            Self::Mbc => write!(f, "ObjectiveFunction::MBC"),
+            Self::Mll => write!(f, "ObjectiveFunction::MLL"),
+            Self::Mcc => write!(f, "ObjectiveFunction::MCC"),
+            Self::Spt => write!(f, "ObjectiveFunction::SPT"),
+            Self::Mct => write!(f, "ObjectiveFunction::MCT"),
+            Self::Mplp => write!(f, "ObjectiveFunction::MPLP"),
+            Self::Mup => write!(f, "ObjectiveFunction::MUP"),
+            Self::Mrup => write!(f, "ObjectiveFunction::MRUP"),
+            Self::Mtd => write!(f, "ObjectiveFunction::MTD"),
+            Self::Mbn => write!(f, "ObjectiveFunction::MBN"),
+            Self::Mctd => write!(f, "ObjectiveFunction::MCTD"),
+            Self::Msl => write!(f, "ObjectiveFunction::MSL"),
+            Self::Mss => write!(f, "ObjectiveFunction::MSS"),
+            Self::Msn => write!(f, "ObjectiveFunction::MSN"),
+            Self::Unknown(x) => write!(f, "ObjectiveFunction::Unknown({})", *x),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct OfObject {
+    pub common_object: CommonObject,
+    pub of_code: ObjectiveFunction,
+    pub reserved: u16,
+    pub tlvs: Option<Vec<Tlv>>,
+}
+
+impl OfObject {
+    pub fn parse_of_object(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, common_object) = CommonObject::parse_common_object(input)?;
+        if let ObjectClassType::Of(OfObjectType::Of) = common_object.object_class_type {
+            let object_body_len = common_object.object_length - 4;
+            let (remaining, object_body) =
+                bytes::streaming::take(object_body_len as usize)(remaining)?;
+            let (object_body, of_code) = number::streaming::be_u16(object_body)?;
+            let (object_body, reserved) = number::streaming::be_u16(object_body)?;
+            let mut of_object = OfObject {
+                common_object,
+                of_code: of_code.into(),
+                reserved,
+                tlvs: None,
+            };
+            if !object_body.is_empty() {
+                let (_object_body, tlvs) = Parser::parse_tlvs(object_body)?;
+                of_object.tlvs = Some(tlvs);
+            }
+            return Ok((remaining, of_object));
+        }
+        Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
+    }
+}
+
+impl std::fmt::Display for OfObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tlvs_str = String::new();
+        if let Some(ref tlvs) = self.tlvs {
+            for t in tlvs {
+                let output = format!("{}", t);
+                tlvs_str.push_str(&output)
+            }
+        }
+        let title = "==[OF Object]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_object}
+                of_code                = {of_code}
+                reserved               = {reserved}
+            {tlv_str}
+            "#,
+            title = title,
+            common_object = self.common_object,
+            of_code = self.of_code,
+            reserved = self.reserved,
+            tlv_str = tlvs_str
+        )
+    }
+}
+
+impl Encode for OfObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = u16::from(self.of_code).to_be_bytes().to_vec();
+        body.extend_from_slice(&self.reserved.to_be_bytes());
+        Encoder::encode_tlvs(&mut body, &self.tlvs);
+        Encoder::encode_object(buf, 21, 1, &self.common_object, &body);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[test]
+    fn test_of_object_parsing() {
+        let input: &[u8] = &[0x15, 0x10, 0x00, 0x08, 0x00, 0x01, 0x00, 0x00];
+        let (remaining, of_object) =
+            OfObject::parse_of_object(input).expect("[!!] Failed to parse OF object");
+        let expected_cobj = CommonObject {
+            object_class_type: ObjectClassType::Of(OfObjectType::Of),
+            reserved: 0,
+            flag_process: false,
+            flag_ignore: false,
+            object_length: 8,
+        };
+        let expected_of_object = OfObject {
+            common_object: expected_cobj,
+            of_code: ObjectiveFunction::Mcp,
+            reserved: 0,
+            tlvs: None,
+        };
+        assert!(remaining.is_empty(), "[!!] Nope, object not eaten fully");
+        assert_eq!(of_object, expected_of_object);
+    }
+}
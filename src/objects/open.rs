@@ -9,13 +9,16 @@ use colored::Colorize;
 use indoc::writedoc;
 
 use crate::common::Version;
+use crate::encoder::{Encode, Encoder};
 use crate::objects::classes::ObjectClassType;
 use crate::objects::header::CommonObject;
+use crate::objects::of::ObjectiveFunction;
 use crate::objects::types::OpenObjectType;
 use crate::tlvs::tlv_parser::Parser;
+use crate::tlvs::tlv_set::{SrPCECapabilityTLV, StatefulPCECapabilityTLV};
 use crate::tlvs::types::Tlv;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct OpenObject {
     pub common_object: CommonObject,
     pub version: Version,
@@ -27,6 +30,18 @@ pub struct OpenObject {
 }
 
 impl OpenObject {
+    pub fn new(keepalive: u8, deadtimer: u8, sid: u8, tlvs: Vec<Tlv>) -> Self {
+        OpenObject {
+            common_object: CommonObject::new(ObjectClassType::Open(OpenObjectType::Open)),
+            version: Version::One,
+            flags: 0,
+            keepalive,
+            deadtimer,
+            sid,
+            tlvs: if tlvs.is_empty() { None } else { Some(tlvs) },
+        }
+    }
+
     fn parse_ver_flags(input: &[u8]) -> IResult<&[u8], (u8, u8)> {
         bits::bits::<_, _, Error<_>, _, _>(tuple((
             bits::streaming::take(3u8),
@@ -41,12 +56,14 @@ impl OpenObject {
             let (remaining, object_body) =
                 bytes::streaming::take(object_body_len as usize)(remaining)?;
             let (object_body, ver_flags) = Self::parse_ver_flags(object_body)?;
+            let version = Version::try_from(ver_flags.0)
+                .map_err(|_version| Err::Failure(Error::new(input, ErrorKind::Verify)))?;
             let (object_body, keepalive) = number::streaming::be_u8(object_body)?;
             let (object_body, deadtimer) = number::streaming::be_u8(object_body)?;
             let (object_body, sid) = number::streaming::be_u8(object_body)?;
             let mut open_obj = OpenObject {
                 common_object: cobj,
-                version: ver_flags.0.into(),
+                version,
                 flags: ver_flags.1,
                 keepalive,
                 deadtimer,
@@ -64,6 +81,40 @@ impl OpenObject {
         }
         Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
     }
+
+    // Objective functions advertised through the OF-List TLV, if any
+    pub fn objective_functions(&self) -> Vec<ObjectiveFunction> {
+        let mut of_codes = vec![];
+        if let Some(ref tlvs) = self.tlvs {
+            for t in tlvs {
+                if let Tlv::OfList(of_list) = t {
+                    of_codes.extend(of_list.of_codes.iter().copied());
+                }
+            }
+        }
+        of_codes
+    }
+
+    pub fn lsp_db_version(&self) -> Option<u64> {
+        self.tlvs.as_ref()?.iter().find_map(|t| match t {
+            Tlv::LspDbVersion(ldv) => Some(ldv.lsp_state_db_version),
+            _ => None,
+        })
+    }
+
+    pub fn stateful_capability(&self) -> Option<&StatefulPCECapabilityTLV> {
+        self.tlvs.as_ref()?.iter().find_map(|t| match t {
+            Tlv::StatefulPCECapability(spc) => Some(spc),
+            _ => None,
+        })
+    }
+
+    pub fn sr_capability(&self) -> Option<&SrPCECapabilityTLV> {
+        self.tlvs.as_ref()?.iter().find_map(|t| match t {
+            Tlv::SrPCECapability(srpc) => Some(srpc),
+            _ => None,
+        })
+    }
 }
 
 impl std::fmt::Display for OpenObject {
@@ -100,3 +151,53 @@ impl std::fmt::Display for OpenObject {
         )
     }
 }
+
+impl Encode for OpenObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = vec![
+            (u8::from(self.version) << 5) | (self.flags & 0b1_1111),
+            self.keepalive,
+            self.deadtimer,
+            self.sid,
+        ];
+        Encoder::encode_tlvs(&mut body, &self.tlvs);
+        Encoder::encode_object(buf, 1, 1, &self.common_object, &body);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::tlvs::tlv_set::OfListTLV;
+    #[test]
+    fn test_open_object_with_of_list_tlv() {
+        let input: &[u8] = &[
+            0x01, 0x10, 0x00, 0x14, 0x20, 0x1e, 0x78, 0x01, 0x00, 0x04, 0x00, 0x06, 0x00, 0x01,
+            0x00, 0x02, 0x00, 0x07, 0x00, 0x00,
+        ];
+        let (remaining, open_object) =
+            OpenObject::parse_open_object(input).expect("[!!] Error while parsing open object");
+        let expected_of_list_tlv = OfListTLV {
+            tlv_type: 4,
+            tlv_len: 6,
+            of_codes: vec![
+                ObjectiveFunction::Mcp,
+                ObjectiveFunction::Mlp,
+                ObjectiveFunction::Spt,
+            ],
+        };
+        assert!(remaining.is_empty());
+        assert_eq!(
+            open_object.tlvs,
+            Some(vec![Tlv::OfList(expected_of_list_tlv)])
+        );
+        assert_eq!(
+            open_object.objective_functions(),
+            vec![
+                ObjectiveFunction::Mcp,
+                ObjectiveFunction::Mlp,
+                ObjectiveFunction::Spt
+            ]
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use colored::Colorize;
+use indoc::writedoc;
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::number;
+use nom::{Err, IResult};
+
+use crate::common::OperationalStatus;
+use crate::encoder::{Encode, Encoder};
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+use crate::objects::types::PathAttribObjectType;
+use crate::tlvs::tlv_parser::Parser;
+use crate::tlvs::tlv_set::{MultipathBackupTLV, MultipathWeightTLV};
+use crate::tlvs::types::Tlv;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PathAttribObject {
+    pub common_object: CommonObject,
+    pub flags: u32,
+    pub operational_status: OperationalStatus,
+    pub flag_reverse: bool,
+    pub path_id: u32,
+    pub tlvs: Option<Vec<Tlv>>,
+}
+
+impl PathAttribObject {
+    // Flags field
+    // bits 28-30 : operational status of the path
+    // bit 31 : R flag (path in the reverse direction)
+    pub fn parse_path_attrib_object(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, common_object) = CommonObject::parse_common_object(input)?;
+        if let ObjectClassType::PathAttrib(PathAttribObjectType::PathAttrib) =
+            common_object.object_class_type
+        {
+            let object_body_len = common_object.object_length - 4;
+            let (remaining, object_body) =
+                bytes::streaming::take(object_body_len as usize)(remaining)?;
+            let (object_body, flags) = number::streaming::be_u32(object_body)?;
+            let (object_body, path_id) = number::streaming::be_u32(object_body)?;
+            let mut path_attrib_object = PathAttribObject {
+                common_object,
+                flags,
+                operational_status: (((flags >> 1) & 0b111) as u8).into(),
+                flag_reverse: flags & 0b1 == 0b1,
+                path_id,
+                tlvs: None,
+            };
+            if !object_body.is_empty() {
+                let (_object_body, tlvs) = Parser::parse_tlvs(object_body)?;
+                path_attrib_object.tlvs = Some(tlvs);
+            }
+            return Ok((remaining, path_attrib_object));
+        }
+        Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
+    }
+
+    // Weight of the path from the MULTIPATH-WEIGHT TLV, if present
+    pub fn weight(&self) -> Option<u32> {
+        self.tlvs.as_ref()?.iter().find_map(|t| match t {
+            Tlv::MultipathWeight(MultipathWeightTLV { weight, .. }) => Some(*weight),
+            _ => None,
+        })
+    }
+
+    // MULTIPATH-BACKUP TLV, if this path is protected by (or is) a backup
+    pub fn backup(&self) -> Option<&MultipathBackupTLV> {
+        self.tlvs.as_ref()?.iter().find_map(|t| match t {
+            Tlv::MultipathBackup(backup) => Some(backup),
+            _ => None,
+        })
+    }
+}
+
+impl std::fmt::Display for PathAttribObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tlvs_str = String::new();
+        if let Some(ref tlvs) = self.tlvs {
+            for t in tlvs {
+                let output = format!("{}", t);
+                tlvs_str.push_str(&output)
+            }
+        }
+        let title = "==[PATH-ATTRIB Object]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_object}
+                flags                  = {flags}
+                operational_status     = {operational_status}
+                flag_reverse           = {flag_r}
+                path_id                = {path_id}
+            {tlv_str}
+            "#,
+            title = title,
+            common_object = self.common_object,
+            flags = self.flags,
+            operational_status = self.operational_status,
+            flag_r = self.flag_reverse,
+            path_id = self.path_id,
+            tlv_str = tlvs_str
+        )
+    }
+}
+
+impl Encode for PathAttribObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let flags = (self.flags & !0b1111)
+            | (u8::from(self.operational_status) as u32 & 0b111) << 1
+            | self.flag_reverse as u32;
+        let mut body = flags.to_be_bytes().to_vec();
+        body.extend_from_slice(&self.path_id.to_be_bytes());
+        Encoder::encode_tlvs(&mut body, &self.tlvs);
+        Encoder::encode_object(buf, 45, 1, &self.common_object, &body);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[test]
+    fn test_path_attrib_object_parsing() {
+        let input: &[u8] = &[
+            0x2d, 0x10, 0x00, 0x14, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01, 0x00, 0x3d,
+            0x00, 0x04, 0x00, 0x00, 0x00, 0x03,
+        ];
+        let (remaining, path_attrib_object) = PathAttribObject::parse_path_attrib_object(input)
+            .expect("[!!] Failed to parse path-attrib object");
+        let expected_cobj = CommonObject {
+            object_class_type: ObjectClassType::PathAttrib(PathAttribObjectType::PathAttrib),
+            reserved: 0,
+            flag_process: false,
+            flag_ignore: false,
+            object_length: 20,
+        };
+        let expected_path_attrib_object = PathAttribObject {
+            common_object: expected_cobj,
+            flags: 2,
+            operational_status: OperationalStatus::Up,
+            flag_reverse: false,
+            path_id: 1,
+            tlvs: Some(vec![Tlv::MultipathWeight(MultipathWeightTLV {
+                tlv_type: 61,
+                tlv_len: 4,
+                weight: 3,
+            })]),
+        };
+        assert!(remaining.is_empty(), "[!!] Nope, object not eaten fully");
+        assert_eq!(path_attrib_object, expected_path_attrib_object);
+        assert_eq!(path_attrib_object.weight(), Some(3));
+    }
+}
@@ -0,0 +1,201 @@
+use colored::Colorize;
+use indoc::writedoc;
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::number;
+use nom::{Err, IResult};
+
+use crate::encoder::{Encode, Encoder};
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+use crate::objects::types::RpObjectType;
+use crate::tlvs::tlv_parser::Parser;
+use crate::tlvs::types::Tlv;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RpObject {
+    pub common_object: CommonObject,
+    pub flags: u32,
+    pub priority: u8,
+    pub flag_reoptimization: bool,
+    pub flag_bidirectional: bool,
+    pub flag_strict: bool,
+    pub flag_vspt: bool,
+    pub flag_supply_of: bool,
+    pub flag_make_before_break: bool,
+    pub flag_report_order: bool,
+    pub flag_fragmentation: bool,
+    pub flag_p2mp: bool,
+    pub flag_ero_compression: bool,
+    pub request_id: u32,
+    pub tlvs: Option<Vec<Tlv>>,
+}
+
+impl RpObject {
+    // Flags field
+    // bits 29-31 : priority
+    // bit 28 : R flag, bit 27 : B flag, bit 26 : O flag
+    // bit 25 : V flag, bit 24 : S flag, bit 23 : M flag
+    // bit 22 : D flag, bit 18 : F flag, bit 19 : N flag
+    // bit 20 : E flag
+    pub fn parse_rp_object(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, common_object) = CommonObject::parse_common_object(input)?;
+        if let ObjectClassType::Rp(RpObjectType::Rp) = common_object.object_class_type {
+            let object_body_len = common_object.object_length - 4;
+            let (remaining, object_body) =
+                bytes::streaming::take(object_body_len as usize)(remaining)?;
+            let (object_body, flags) = number::streaming::be_u32(object_body)?;
+            let (object_body, request_id) = number::streaming::be_u32(object_body)?;
+            let mut rp_object = RpObject {
+                common_object,
+                flags,
+                priority: (flags & 0b111) as u8,
+                flag_reoptimization: flags & (1 << 3) != 0,
+                flag_bidirectional: flags & (1 << 4) != 0,
+                flag_strict: flags & (1 << 5) != 0,
+                flag_vspt: flags & (1 << 6) != 0,
+                flag_supply_of: flags & (1 << 7) != 0,
+                flag_make_before_break: flags & (1 << 8) != 0,
+                flag_report_order: flags & (1 << 9) != 0,
+                flag_fragmentation: flags & (1 << 13) != 0,
+                flag_p2mp: flags & (1 << 12) != 0,
+                flag_ero_compression: flags & (1 << 11) != 0,
+                request_id,
+                tlvs: None,
+            };
+            if !object_body.is_empty() {
+                let (_object_body, tlvs) = Parser::parse_tlvs(object_body)?;
+                rp_object.tlvs = Some(tlvs);
+            }
+            return Ok((remaining, rp_object));
+        }
+        Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
+    }
+
+    pub fn request_id(&self) -> u32 {
+        self.request_id
+    }
+
+    // A reply answers a request when both carry the same Request-ID-number
+    pub fn is_reply_to(&self, request: &RpObject) -> bool {
+        self.request_id == request.request_id
+    }
+}
+
+impl std::fmt::Display for RpObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tlvs_str = String::new();
+        if let Some(ref tlvs) = self.tlvs {
+            for t in tlvs {
+                let output = format!("{}", t);
+                tlvs_str.push_str(&output)
+            }
+        }
+        let title = "==[RP Object]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_object}
+                flags                  = {flags}
+                priority               = {priority}
+                flag_reoptimization    = {flag_r}
+                flag_bidirectional     = {flag_b}
+                flag_strict            = {flag_o}
+                flag_vspt              = {flag_v}
+                flag_supply_of         = {flag_s}
+                flag_make_before_break = {flag_m}
+                flag_report_order      = {flag_d}
+                flag_fragmentation     = {flag_f}
+                flag_p2mp              = {flag_n}
+                flag_ero_compression   = {flag_e}
+                request_id             = {request_id}
+            {tlv_str}
+            "#,
+            title = title,
+            common_object = self.common_object,
+            flags = self.flags,
+            priority = self.priority,
+            flag_r = self.flag_reoptimization,
+            flag_b = self.flag_bidirectional,
+            flag_o = self.flag_strict,
+            flag_v = self.flag_vspt,
+            flag_s = self.flag_supply_of,
+            flag_m = self.flag_make_before_break,
+            flag_d = self.flag_report_order,
+            flag_f = self.flag_fragmentation,
+            flag_n = self.flag_p2mp,
+            flag_e = self.flag_ero_compression,
+            request_id = self.request_id,
+            tlv_str = tlvs_str
+        )
+    }
+}
+
+impl Encode for RpObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let flags = (self.flags & !0b0011_1011_1111_1111)
+            | (self.priority as u32 & 0b111)
+            | (self.flag_reoptimization as u32) << 3
+            | (self.flag_bidirectional as u32) << 4
+            | (self.flag_strict as u32) << 5
+            | (self.flag_vspt as u32) << 6
+            | (self.flag_supply_of as u32) << 7
+            | (self.flag_make_before_break as u32) << 8
+            | (self.flag_report_order as u32) << 9
+            | (self.flag_fragmentation as u32) << 13
+            | (self.flag_p2mp as u32) << 12
+            | (self.flag_ero_compression as u32) << 11;
+        let mut body = flags.to_be_bytes().to_vec();
+        body.extend_from_slice(&self.request_id.to_be_bytes());
+        Encoder::encode_tlvs(&mut body, &self.tlvs);
+        Encoder::encode_object(buf, 2, 1, &self.common_object, &body);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[test]
+    fn test_rp_object_parsing() {
+        let input: &[u8] = &[
+            0x02, 0x10, 0x00, 0x0c, 0x00, 0x00, 0x18, 0xb3, 0x00, 0x00, 0x00, 0x2a,
+        ];
+        let (remaining, rp_object) =
+            RpObject::parse_rp_object(input).expect("[!!] Failed to parse rp object");
+        let expected_cobj = CommonObject {
+            object_class_type: ObjectClassType::Rp(RpObjectType::Rp),
+            reserved: 0,
+            flag_process: false,
+            flag_ignore: false,
+            object_length: 12,
+        };
+        let expected_rp_object = RpObject {
+            common_object: expected_cobj,
+            flags: 0x18b3,
+            priority: 3,
+            flag_reoptimization: false,
+            flag_bidirectional: true,
+            flag_strict: true,
+            flag_vspt: false,
+            flag_supply_of: true,
+            flag_make_before_break: false,
+            flag_report_order: false,
+            flag_fragmentation: false,
+            flag_p2mp: true,
+            flag_ero_compression: true,
+            request_id: 42,
+            tlvs: None,
+        };
+        assert!(remaining.is_empty(), "[!!] Nope, object not eaten fully");
+        assert_eq!(rp_object, expected_rp_object);
+        assert_eq!(rp_object.request_id(), 42);
+
+        let mut buf = Vec::new();
+        rp_object.encode(&mut buf);
+        assert_eq!(
+            buf, input,
+            "[!!] RP flags not re-encoded at their bit positions"
+        );
+    }
+}
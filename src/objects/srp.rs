@@ -7,6 +7,7 @@ use nom::number;
 use nom::sequence::tuple;
 use nom::{Err, IResult};
 
+use crate::encoder::{Encode, Encoder};
 use crate::objects::header::CommonObject;
 use crate::tlvs::tlv_parser::Parser;
 use crate::tlvs::types::Tlv;
@@ -24,6 +25,29 @@ pub struct SrpObject {
 }
 
 impl SrpObject {
+    pub fn new(srp_id: u32, flag_remove: bool) -> Self {
+        SrpObject {
+            common_object: CommonObject::new(ObjectClassType::Srp(SrpObjectType::Srp)),
+            flags: 0,
+            flag_remove,
+            srp_id,
+            tlvs: None,
+        }
+    }
+
+    // SRP-ID values 0x00000000 and 0xFFFFFFFF are reserved (RFC 8231)
+    pub fn is_reserved_srp_id(srp_id: u32) -> bool {
+        srp_id == 0 || srp_id == u32::MAX
+    }
+
+    // One line form, e.g. srp=12 R
+    pub fn summary(&self) -> String {
+        match self.flag_remove {
+            true => format!("srp={} R", self.srp_id),
+            false => format!("srp={}", self.srp_id),
+        }
+    }
+
     pub fn parse_flag_r(input: &[u8]) -> IResult<&[u8], (u32, u8)> {
         bits::bits::<_, _, Error<_>, _, _>(tuple((
             bits::streaming::take(31u32),
@@ -85,6 +109,16 @@ impl std::fmt::Display for SrpObject {
     }
 }
 
+impl Encode for SrpObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let flags = (self.flags << 1) | self.flag_remove as u32;
+        let mut body = flags.to_be_bytes().to_vec();
+        body.extend_from_slice(&self.srp_id.to_be_bytes());
+        Encoder::encode_tlvs(&mut body, &self.tlvs);
+        Encoder::encode_object(buf, 33, 1, &self.common_object, &body);
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
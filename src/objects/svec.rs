@@ -0,0 +1,131 @@
+use colored::Colorize;
+use indoc::writedoc;
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::number;
+use nom::{Err, IResult};
+
+use crate::encoder::{Encode, Encoder};
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+use crate::objects::types::SvecObjectType;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SvecObject {
+    pub common_object: CommonObject,
+    pub reserved: u8,
+    pub flags: u32,
+    pub flag_link_diverse: bool,
+    pub flag_node_diverse: bool,
+    pub flag_srlg_diverse: bool,
+    pub request_ids: Vec<u32>,
+}
+
+impl SvecObject {
+    // Flags field (24 bits)
+    // bit 23 : L flag (link diverse)
+    // bit 22 : N flag (node diverse)
+    // bit 21 : S flag (SRLG diverse)
+    pub fn parse_svec_object(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, common_object) = CommonObject::parse_common_object(input)?;
+        if let ObjectClassType::Svec(SvecObjectType::Svec) = common_object.object_class_type {
+            let object_body_len = common_object.object_length - 4;
+            let (remaining, object_body) =
+                bytes::streaming::take(object_body_len as usize)(remaining)?;
+            let (object_body, reserved) = number::streaming::be_u8(object_body)?;
+            let (mut object_body, flags) = number::streaming::be_u24(object_body)?;
+            let mut request_ids = vec![];
+            while !object_body.is_empty() {
+                let (left, request_id) = number::streaming::be_u32(object_body)?;
+                request_ids.push(request_id);
+                object_body = left;
+            }
+            let svec_object = SvecObject {
+                common_object,
+                reserved,
+                flags,
+                flag_link_diverse: flags & 0b001 == 0b001,
+                flag_node_diverse: flags & 0b010 == 0b010,
+                flag_srlg_diverse: flags & 0b100 == 0b100,
+                request_ids,
+            };
+            return Ok((remaining, svec_object));
+        }
+        Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
+    }
+}
+
+impl std::fmt::Display for SvecObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = "==[SVEC Object]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_object}
+                reserved               = {reserved}
+                flags                  = {flags}
+                flag_link_diverse      = {flag_l}
+                flag_node_diverse      = {flag_n}
+                flag_srlg_diverse      = {flag_s}
+                request_ids            = {request_ids:?}
+            "#,
+            title = title,
+            common_object = self.common_object,
+            reserved = self.reserved,
+            flags = self.flags,
+            flag_l = self.flag_link_diverse,
+            flag_n = self.flag_node_diverse,
+            flag_s = self.flag_srlg_diverse,
+            request_ids = self.request_ids
+        )
+    }
+}
+
+impl Encode for SvecObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let flags = (self.flags & 0x00ff_fff8)
+            | self.flag_link_diverse as u32
+            | (self.flag_node_diverse as u32) << 1
+            | (self.flag_srlg_diverse as u32) << 2;
+        let mut body = ((self.reserved as u32) << 24 | flags)
+            .to_be_bytes()
+            .to_vec();
+        for request_id in &self.request_ids {
+            body.extend_from_slice(&request_id.to_be_bytes());
+        }
+        Encoder::encode_object(buf, 11, 1, &self.common_object, &body);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[test]
+    fn test_svec_object_parsing() {
+        let input: &[u8] = &[
+            0x0b, 0x10, 0x00, 0x10, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00,
+            0x00, 0x02,
+        ];
+        let (remaining, svec_object) =
+            SvecObject::parse_svec_object(input).expect("[!!] Failed to parse svec object");
+        let expected_cobj = CommonObject {
+            object_class_type: ObjectClassType::Svec(SvecObjectType::Svec),
+            reserved: 0,
+            flag_process: false,
+            flag_ignore: false,
+            object_length: 16,
+        };
+        let expected_svec_object = SvecObject {
+            common_object: expected_cobj,
+            reserved: 0,
+            flags: 5,
+            flag_link_diverse: true,
+            flag_node_diverse: false,
+            flag_srlg_diverse: true,
+            request_ids: vec![1, 2],
+        };
+        assert!(remaining.is_empty(), "[!!] Nope, object not eaten fully");
+        assert_eq!(svec_object, expected_svec_object);
+    }
+}
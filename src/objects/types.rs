@@ -162,3 +162,174 @@ impl From<u8> for EndpointsObjectType {
         }
     }
 }
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OfObjectType {
+    Reserved,
+    Of,
+    Unassigned,
+}
+
+impl From<u8> for OfObjectType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Reserved,
+            1 => Self::Of,
+            2..=15 => Self::Unassigned,
+            _ => panic!("[!!] Invalid Object type value for OF object"),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RpObjectType {
+    Reserved,
+    Rp,
+    Unassigned,
+}
+
+impl From<u8> for RpObjectType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Reserved,
+            1 => Self::Rp,
+            2..=15 => Self::Unassigned,
+            _ => panic!("[!!] Invalid Object type value for RP object"),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NoPathObjectType {
+    Reserved,
+    NoPath,
+    Unassigned,
+}
+
+impl From<u8> for NoPathObjectType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Reserved,
+            1 => Self::NoPath,
+            2..=15 => Self::Unassigned,
+            _ => panic!("[!!] Invalid Object type value for NO-PATH object"),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LoadBalancingObjectType {
+    Reserved,
+    LoadBalancing,
+    Unassigned,
+}
+
+impl From<u8> for LoadBalancingObjectType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Reserved,
+            1 => Self::LoadBalancing,
+            2..=15 => Self::Unassigned,
+            _ => panic!("[!!] Invalid Object type value for LOAD-BALANCING object"),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SvecObjectType {
+    Reserved,
+    Svec,
+    Unassigned,
+}
+
+impl From<u8> for SvecObjectType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Reserved,
+            1 => Self::Svec,
+            2..=15 => Self::Unassigned,
+            _ => panic!("[!!] Invalid Object type value for SVEC object"),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ClassTypeObjectType {
+    Reserved,
+    ClassType,
+    Unassigned,
+}
+
+impl From<u8> for ClassTypeObjectType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Reserved,
+            1 => Self::ClassType,
+            2..=15 => Self::Unassigned,
+            _ => panic!("[!!] Invalid Object type value for CLASSTYPE object"),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PathAttribObjectType {
+    Reserved,
+    PathAttrib,
+    Unassigned,
+}
+
+impl From<u8> for PathAttribObjectType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Reserved,
+            1 => Self::PathAttrib,
+            2..=15 => Self::Unassigned,
+            _ => panic!("[!!] Invalid Object type value for PATH-ATTRIB object"),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ErrorObjectType {
+    Reserved,
+    Error,
+    Unassigned,
+}
+
+impl From<u8> for ErrorObjectType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Reserved,
+            1 => Self::Error,
+            2..=15 => Self::Unassigned,
+            _ => panic!("[!!] Invalid Object type value for PCEP-ERROR object"),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CloseObjectType {
+    Reserved,
+    Close,
+    Unassigned,
+}
+
+impl From<u8> for CloseObjectType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Reserved,
+            1 => Self::Close,
+            2..=15 => Self::Unassigned,
+            _ => panic!("[!!] Invalid Object type value for CLOSE object"),
+        }
+    }
+}
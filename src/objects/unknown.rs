@@ -0,0 +1,196 @@
+use colored::Colorize;
+use indoc::writedoc;
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::{Err, IResult};
+
+use crate::encoder::{Encode, Encoder};
+use crate::objects::header::CommonObject;
+use crate::registry::{self, CustomObject};
+
+use super::classes::ObjectClassType;
+
+// Object of a class the crate has no decoder for, kept as raw body so
+// the message can still be decoded and re-encoded
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UnknownObject {
+    pub common_object: CommonObject,
+    pub raw_body: Vec<u8>,
+}
+
+impl UnknownObject {
+    pub fn new(object_class: u8, object_type: u8, raw_body: Vec<u8>) -> Self {
+        UnknownObject {
+            common_object: CommonObject::new(ObjectClassType::Unknown((object_class, object_type))),
+            raw_body,
+        }
+    }
+
+    pub fn parse_unknown_object(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, common_object) = CommonObject::parse_common_object(input)?;
+        if let ObjectClassType::Unknown(_) = common_object.object_class_type {
+            let object_body_len = common_object.object_length - 4;
+            let (remaining, object_body) =
+                bytes::streaming::take(object_body_len as usize)(remaining)?;
+            let unknown_object = UnknownObject {
+                common_object,
+                raw_body: object_body.to_vec(),
+            };
+            return Ok((remaining, unknown_object));
+        }
+        Err(Err::Failure(Error::new(input, ErrorKind::Fail)))
+    }
+
+    // Unknown objects at the start of input, up to the next object of a
+    // known class. RFC 5440 7.2: the P flag asks the receiver to process
+    // the object, which it cannot do for an unrecognized class, so such
+    // an object fails the message (PCErr type 3, value 1) unless the
+    // sender also flagged it with I as already ignored.
+    pub fn parse_unknown_objects(input: &[u8]) -> IResult<&[u8], Vec<Self>> {
+        let mut unknown_objects = vec![];
+        let mut left = input;
+        while let (_, Some(ObjectClassType::Unknown(_))) = CommonObject::peek_object_class(left)? {
+            let (remaining, unknown_object) = Self::parse_unknown_object(left)?;
+            if unknown_object.is_fatal() {
+                return Err(Err::Failure(Error::new(left, ErrorKind::Verify)));
+            }
+            left = remaining;
+            unknown_objects.push(unknown_object);
+        }
+        Ok((left, unknown_objects))
+    }
+
+    pub fn object_class(&self) -> u8 {
+        match self.common_object.object_class_type {
+            ObjectClassType::Unknown((object_class, _)) => object_class,
+            _ => 0,
+        }
+    }
+
+    pub fn object_type(&self) -> u8 {
+        match self.common_object.object_class_type {
+            ObjectClassType::Unknown((_, object_type)) => object_type,
+            _ => 0,
+        }
+    }
+
+    // A registered ObjectDecoder makes the class processable after all
+    pub fn is_fatal(&self) -> bool {
+        self.common_object.flag_process
+            && !self.common_object.flag_ignore
+            && self.decode().is_none()
+    }
+
+    // Body decoded by the ObjectDecoder registered for the class and
+    // type, raw_body stays what gets encoded
+    pub fn decode(&self) -> Option<Box<dyn CustomObject>> {
+        registry::decode_object(&self.common_object, &self.raw_body)
+    }
+}
+
+impl std::fmt::Display for UnknownObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = "==[Unknown Object]==".green().bold();
+        writedoc!(
+            f,
+            r#"
+            {title}
+                {common_object}
+                raw_body               = {raw_body:02x?}
+            "#,
+            title = title,
+            common_object = self.common_object,
+            raw_body = self.raw_body
+        )
+    }
+}
+
+impl Encode for UnknownObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        Encoder::encode_object(
+            buf,
+            self.object_class(),
+            self.object_type(),
+            &self.common_object,
+            &self.raw_body,
+        );
+    }
+}
+
+// Unknown objects of a request, each with the gap between the request's
+// known objects it was received in, so that re-encoding puts it back
+// where it was. Gap n follows the n-th part of the request grammar.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct UnknownObjects {
+    objects: Vec<UnknownObject>,
+    gaps: Vec<usize>,
+}
+
+impl UnknownObjects {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub(crate) fn push(&mut self, gap: usize, unknown_objects: Vec<UnknownObject>) {
+        self.gaps
+            .extend(std::iter::repeat_n(gap, unknown_objects.len()));
+        self.objects.extend(unknown_objects);
+    }
+
+    pub fn objects(&self) -> &[UnknownObject] {
+        &self.objects
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    // Encode the unknown objects received in gap
+    pub(crate) fn encode_gap(&self, gap: usize, buf: &mut Vec<u8>) {
+        for (unknown_object, _gap) in self
+            .objects
+            .iter()
+            .zip(self.gaps.iter())
+            .filter(|(_unknown_object, object_gap)| **object_gap == gap)
+        {
+            unknown_object.encode(buf);
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    #[test]
+    fn test_unknown_object_parsing() {
+        let input: &[u8] = &[
+            0x80, 0x10, 0x00, 0x08, 0xde, 0xad, 0xbe, 0xef, 0x21, 0x10, 0x00, 0x0c, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+        ];
+        let (remaining, unknown_objects) = UnknownObject::parse_unknown_objects(input)
+            .expect("[!!] Failed to parse unknown objects");
+        assert_eq!(remaining, &input[8..]);
+        assert_eq!(unknown_objects.len(), 1);
+        assert_eq!(unknown_objects[0].object_class(), 128);
+        assert_eq!(unknown_objects[0].object_type(), 1);
+        assert_eq!(unknown_objects[0].raw_body, vec![0xde, 0xad, 0xbe, 0xef]);
+        assert!(!unknown_objects[0].is_fatal());
+        assert_eq!(unknown_objects[0].to_bytes(), &input[..8]);
+
+        // P flag: the sender requires the object to be processed
+        let input: &[u8] = &[0x80, 0x12, 0x00, 0x04];
+        assert!(matches!(
+            UnknownObject::parse_unknown_objects(input),
+            Err(Err::Failure(_))
+        ));
+        // P and I flags: already ignored by the sender
+        let input: &[u8] = &[0x80, 0x13, 0x00, 0x04];
+        let (_remaining, unknown_objects) = UnknownObject::parse_unknown_objects(input)
+            .expect("[!!] Failed to parse unknown objects");
+        assert!(unknown_objects[0].common_object.flag_ignore);
+    }
+}
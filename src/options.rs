@@ -0,0 +1,173 @@
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+use crate::objects::unknown::UnknownObject;
+use crate::validate::LengthIssueKind;
+
+use nom::bytes;
+use nom::error::{Error, ErrorKind};
+use nom::{Err, IResult};
+
+// How far the parsers bend for peers that do not follow the RFCs.
+// Lenient mode decodes what it can and records a Diagnostic for each
+// violation, strict mode fails on the first one.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ParseOptions {
+    pub strict: bool,
+    // Run the length field validation over each message
+    pub check_lengths: bool,
+}
+
+impl ParseOptions {
+    pub fn strict() -> Self {
+        ParseOptions {
+            strict: true,
+            check_lengths: true,
+        }
+    }
+
+    pub fn lenient() -> Self {
+        ParseOptions {
+            strict: false,
+            check_lengths: true,
+        }
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::lenient()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DiagnosticKind {
+    // Object class the crate has no decoder for, skipped
+    UnknownObject { object_class: u8, object_type: u8 },
+    // Known object in a place the message grammar does not allow it,
+    // skipped
+    UnexpectedObject(ObjectClassType),
+    // Reserved field or unassigned flags set by the sender
+    ReservedBits { field: &'static str, value: u32 },
+    // Length field inconsistency, see validate::check_lengths
+    Length(LengthIssueKind),
+}
+
+// A violation found while parsing, offset is counted from the start of
+// the message
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Diagnostic {
+    pub offset: usize,
+    pub kind: DiagnosticKind,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            DiagnosticKind::UnknownObject {
+                object_class,
+                object_type,
+            } => write!(
+                f,
+                "offset {}: unknown object class {} type {}",
+                self.offset, object_class, object_type
+            ),
+            DiagnosticKind::UnexpectedObject(object_class_type) => write!(
+                f,
+                "offset {}: unexpected {:?} object",
+                self.offset, object_class_type
+            ),
+            DiagnosticKind::ReservedBits { field, value } => write!(
+                f,
+                "offset {}: reserved {} set to {:#x}",
+                self.offset, field, value
+            ),
+            DiagnosticKind::Length(kind) => write!(
+                f,
+                "{}",
+                crate::validate::LengthIssue {
+                    offset: self.offset,
+                    kind
+                }
+            ),
+        }
+    }
+}
+
+// Diagnostics of the message starting at base, handed down the parsers
+// of that message
+#[derive(Debug)]
+pub struct Diagnostics<'a> {
+    base: &'a [u8],
+    options: ParseOptions,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Diagnostics<'a> {
+    pub fn new(base: &'a [u8], options: ParseOptions) -> Self {
+        Diagnostics {
+            base,
+            options,
+            diagnostics: vec![],
+        }
+    }
+
+    pub fn options(&self) -> ParseOptions {
+        self.options
+    }
+
+    // Record a violation found at the start of at. In strict mode it is
+    // a Failure pointing at the same bytes instead.
+    pub fn report(
+        &mut self,
+        at: &'a [u8],
+        kind: DiagnosticKind,
+    ) -> Result<(), Err<Error<&'a [u8]>>> {
+        if self.options.strict {
+            let code = match kind {
+                DiagnosticKind::Length(_) => ErrorKind::LengthValue,
+                _ => ErrorKind::Verify,
+            };
+            return Err(Err::Failure(Error::new(at, code)));
+        }
+        let offset = (at.as_ptr() as usize).saturating_sub(self.base.as_ptr() as usize);
+        self.diagnostics.push(Diagnostic { offset, kind });
+        Ok(())
+    }
+
+    // Reserved field or unassigned flags of an object starting at at,
+    // reported only when not zero
+    pub(crate) fn check_reserved(
+        &mut self,
+        at: &'a [u8],
+        field: &'static str,
+        value: u32,
+    ) -> Result<(), Err<Error<&'a [u8]>>> {
+        if value != 0 {
+            self.report(at, DiagnosticKind::ReservedBits { field, value })?;
+        }
+        Ok(())
+    }
+
+    // Step over an object the grammar has no place for. Unknown objects
+    // are already reported with the object headers, a known one is
+    // reported as unexpected here.
+    pub(crate) fn skip_object(&mut self, input: &'a [u8]) -> IResult<&'a [u8], ()> {
+        let (_remaining, cobj) = CommonObject::parse_common_object(input)?;
+        if let ObjectClassType::Unknown(_) = cobj.object_class_type {
+            let (remaining, _unknown_objects) = UnknownObject::parse_unknown_objects(input)?;
+            return Ok((remaining, ()));
+        }
+        let kind = DiagnosticKind::UnexpectedObject(cobj.object_class_type);
+        self.report(input, kind)?;
+        let (remaining, _object) = bytes::streaming::take(cobj.object_length)(input)?;
+        Ok((remaining, ()))
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn into_vec(self) -> Vec<Diagnostic> {
+        self.diagnostics
+    }
+}
@@ -0,0 +1,303 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::encoder::{Encode, Encoder};
+use crate::objects::classes::ObjectClassType;
+use crate::objects::header::CommonObject;
+
+// Decoders registered by downstream crates for TLV types and object
+// classes the crate does not know. They only apply to codes that would
+// otherwise end up as Tlv::Unknown or an UnknownObject, the built-in
+// decoders cannot be replaced.
+
+// Equality of the boxed values, implemented for any PartialEq type
+pub trait DynValue: Any + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn dyn_eq(&self, other: &dyn Any) -> bool;
+}
+
+impl<T: Any + PartialEq + Send + Sync> DynValue for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn dyn_eq(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<T>() == Some(self)
+    }
+}
+
+pub trait CloneTlv {
+    fn clone_box(&self) -> Box<dyn CustomTlv>;
+}
+
+impl<T: CustomTlv + Clone> CloneTlv for T {
+    fn clone_box(&self) -> Box<dyn CustomTlv> {
+        Box::new(self.clone())
+    }
+}
+
+// Value of a vendor TLV, kept in Tlv::Custom
+pub trait CustomTlv: DynValue + CloneTlv + std::fmt::Debug + std::fmt::Display {
+    fn tlv_type(&self) -> u16;
+    // Value without the type, length and padding
+    fn encode_value(&self, buf: &mut Vec<u8>);
+}
+
+impl PartialEq for dyn CustomTlv {
+    fn eq(&self, other: &Self) -> bool {
+        self.tlv_type() == other.tlv_type() && self.dyn_eq(other.as_any())
+    }
+}
+
+impl Eq for dyn CustomTlv {}
+
+impl Clone for Box<dyn CustomTlv> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl Encode for Box<dyn CustomTlv> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut value = vec![];
+        self.encode_value(&mut value);
+        Encoder::encode_tlv(buf, self.tlv_type(), &value);
+    }
+}
+
+// Body of a vendor object, the header stays with the caller
+pub trait CustomObject: DynValue + std::fmt::Debug + std::fmt::Display {
+    fn object_class(&self) -> u8;
+    fn object_type(&self) -> u8;
+    // Body without the common object header
+    fn encode_body(&self, buf: &mut Vec<u8>);
+}
+
+impl PartialEq for dyn CustomObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.object_class() == other.object_class()
+            && self.object_type() == other.object_type()
+            && self.dyn_eq(other.as_any())
+    }
+}
+
+impl Eq for dyn CustomObject {}
+
+impl dyn CustomObject {
+    pub fn encode(&self, buf: &mut Vec<u8>, common_object: &CommonObject) {
+        let mut body = vec![];
+        self.encode_body(&mut body);
+        Encoder::encode_object(
+            buf,
+            self.object_class(),
+            self.object_type(),
+            common_object,
+            &body,
+        );
+    }
+}
+
+// None leaves the TLV as Tlv::Unknown, e.g. for a value too short
+pub trait TlvDecoder: Send + Sync {
+    fn decode_tlv(&self, tlv_type: u16, value: &[u8]) -> Option<Box<dyn CustomTlv>>;
+}
+
+impl<F> TlvDecoder for F
+where
+    F: Fn(u16, &[u8]) -> Option<Box<dyn CustomTlv>> + Send + Sync,
+{
+    fn decode_tlv(&self, tlv_type: u16, value: &[u8]) -> Option<Box<dyn CustomTlv>> {
+        self(tlv_type, value)
+    }
+}
+
+// None leaves the object as an UnknownObject
+pub trait ObjectDecoder: Send + Sync {
+    fn decode_object(
+        &self,
+        common_object: &CommonObject,
+        body: &[u8],
+    ) -> Option<Box<dyn CustomObject>>;
+}
+
+impl<F> ObjectDecoder for F
+where
+    F: Fn(&CommonObject, &[u8]) -> Option<Box<dyn CustomObject>> + Send + Sync,
+{
+    fn decode_object(
+        &self,
+        common_object: &CommonObject,
+        body: &[u8],
+    ) -> Option<Box<dyn CustomObject>> {
+        self(common_object, body)
+    }
+}
+
+type TlvDecoders = RwLock<HashMap<u16, Arc<dyn TlvDecoder>>>;
+type ObjectDecoders = RwLock<HashMap<(u8, u8), Arc<dyn ObjectDecoder>>>;
+
+fn tlv_decoders() -> &'static TlvDecoders {
+    static TLV_DECODERS: OnceLock<TlvDecoders> = OnceLock::new();
+    TLV_DECODERS.get_or_init(Default::default)
+}
+
+fn object_decoders() -> &'static ObjectDecoders {
+    static OBJECT_DECODERS: OnceLock<ObjectDecoders> = OnceLock::new();
+    OBJECT_DECODERS.get_or_init(Default::default)
+}
+
+// Replaces any decoder already registered for tlv_type
+pub fn register_tlv_decoder<D: TlvDecoder + 'static>(tlv_type: u16, decoder: D) {
+    let mut decoders = tlv_decoders().write().unwrap_or_else(|e| e.into_inner());
+    decoders.insert(tlv_type, Arc::new(decoder));
+}
+
+pub fn unregister_tlv_decoder(tlv_type: u16) {
+    let mut decoders = tlv_decoders().write().unwrap_or_else(|e| e.into_inner());
+    decoders.remove(&tlv_type);
+}
+
+// Replaces any decoder already registered for the class and type
+pub fn register_object_decoder<D: ObjectDecoder + 'static>(
+    object_class: u8,
+    object_type: u8,
+    decoder: D,
+) {
+    let mut decoders = object_decoders().write().unwrap_or_else(|e| e.into_inner());
+    decoders.insert((object_class, object_type), Arc::new(decoder));
+}
+
+pub fn unregister_object_decoder(object_class: u8, object_type: u8) {
+    let mut decoders = object_decoders().write().unwrap_or_else(|e| e.into_inner());
+    decoders.remove(&(object_class, object_type));
+}
+
+pub(crate) fn decode_tlv(tlv_type: u16, value: &[u8]) -> Option<Box<dyn CustomTlv>> {
+    // The lock is not held while the decoder runs
+    let decoder = tlv_decoders()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&tlv_type)
+        .cloned()?;
+    decoder.decode_tlv(tlv_type, value)
+}
+
+pub(crate) fn decode_object(
+    common_object: &CommonObject,
+    body: &[u8],
+) -> Option<Box<dyn CustomObject>> {
+    let ObjectClassType::Unknown(class_type) = common_object.object_class_type else {
+        return None;
+    };
+    let decoder = object_decoders()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&class_type)
+        .cloned()?;
+    decoder.decode_object(common_object, body)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::objects::any::PcepObject;
+    use crate::tlvs::tlv_parser::Parser;
+    use crate::tlvs::types::Tlv;
+
+    #[derive(Debug, PartialEq, Eq, Clone)]
+    struct VendorTlv {
+        enterprise: u32,
+    }
+
+    impl std::fmt::Display for VendorTlv {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "enterprise = {}", self.enterprise)
+        }
+    }
+
+    impl CustomTlv for VendorTlv {
+        fn tlv_type(&self) -> u16 {
+            65500
+        }
+
+        fn encode_value(&self, buf: &mut Vec<u8>) {
+            buf.extend_from_slice(&self.enterprise.to_be_bytes());
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct VendorObject {
+        value: u16,
+    }
+
+    impl std::fmt::Display for VendorObject {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "value = {}", self.value)
+        }
+    }
+
+    impl CustomObject for VendorObject {
+        fn object_class(&self) -> u8 {
+            250
+        }
+
+        fn object_type(&self) -> u8 {
+            1
+        }
+
+        fn encode_body(&self, buf: &mut Vec<u8>) {
+            buf.extend_from_slice(&self.value.to_be_bytes());
+            buf.extend_from_slice(&[0, 0]);
+        }
+    }
+
+    #[test]
+    fn test_registry_tlv_decoder() {
+        let input: &[u8] = &[0xff, 0xdc, 0x00, 0x04, 0x00, 0x00, 0x00, 0x09];
+        let (_remaining, tlv) = Parser::parse_tlv(input).expect("[!!] Failed to parse tlv");
+        assert!(matches!(tlv, Tlv::Unknown(_)));
+
+        register_tlv_decoder(65500, |_tlv_type: u16, value: &[u8]| {
+            let enterprise = u32::from_be_bytes(value.try_into().ok()?);
+            Some(Box::new(VendorTlv { enterprise }) as Box<dyn CustomTlv>)
+        });
+        let (_remaining, tlv) = Parser::parse_tlv(input).expect("[!!] Failed to parse tlv");
+        unregister_tlv_decoder(65500);
+        let expected = Tlv::Custom(Box::new(VendorTlv { enterprise: 9 }));
+        assert_eq!(tlv, expected);
+        assert_eq!(tlv.clone(), expected);
+        assert_ne!(tlv, Tlv::Custom(Box::new(VendorTlv { enterprise: 8 })));
+        assert_eq!(tlv.to_bytes(), input);
+        assert!(format!("{}", tlv).contains("enterprise = 9"));
+    }
+
+    #[test]
+    fn test_registry_object_decoder() {
+        let input: &[u8] = &[0xfa, 0x12, 0x00, 0x08, 0x00, 0x2a, 0x00, 0x00];
+        register_object_decoder(250, 1, |_cobj: &CommonObject, body: &[u8]| {
+            let value = u16::from_be_bytes([*body.first()?, *body.get(1)?]);
+            Some(Box::new(VendorObject { value }) as Box<dyn CustomObject>)
+        });
+        let objects: Vec<PcepObject> = PcepObject::objects(input).flatten().collect();
+        let unknown = crate::objects::unknown::UnknownObject::parse_unknown_object(input)
+            .expect("[!!] Failed to parse unknown object")
+            .1;
+        // The P flag is no reason to fail once the class has a decoder
+        let is_fatal = unknown.is_fatal();
+        unregister_object_decoder(250, 1);
+        assert!(!is_fatal);
+        assert!(unknown.is_fatal());
+        match &objects[..] {
+            [PcepObject::Custom(common_object, custom)] => {
+                assert!(common_object.flag_process);
+                let expected: Box<dyn CustomObject> = Box::new(VendorObject { value: 42 });
+                assert_eq!(custom, &expected);
+                let mut buf = vec![];
+                custom.encode(&mut buf, common_object);
+                assert_eq!(buf, input);
+            }
+            _ => panic!("[!!] Expected a custom object"),
+        }
+    }
+}
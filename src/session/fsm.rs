@@ -0,0 +1,581 @@
+use crate::messages::pcerr::PcErr;
+use crate::objects::close::CloseReason;
+use crate::objects::open::OpenObject;
+use crate::session::types::{
+    NegotiatedParameters, SessionAction, SessionConfig, SessionEvent, SessionState, Timer,
+};
+
+// PCEP session state machine (RFC 5440 Section 6 and Appendix A)
+#[derive(Debug)]
+pub struct SessionFsm {
+    config: SessionConfig,
+    state: SessionState,
+    connect_retry: u8,
+    open_retry: u8,
+    local_ok: bool,
+    remote_ok: bool,
+    negotiated: NegotiatedParameters,
+}
+
+impl SessionFsm {
+    pub fn new(config: SessionConfig) -> Self {
+        SessionFsm {
+            config,
+            state: SessionState::Idle,
+            connect_retry: 0,
+            open_retry: 0,
+            local_ok: false,
+            remote_ok: false,
+            negotiated: NegotiatedParameters {
+                local_keepalive: config.keepalive,
+                local_deadtimer: config.deadtimer,
+                local_sid: config.sid,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn state(&self) -> SessionState {
+        self.state
+    }
+
+    pub fn config(&self) -> &SessionConfig {
+        &self.config
+    }
+
+    // Only meaningful once the session reached SessionState::Up
+    pub fn negotiated(&self) -> &NegotiatedParameters {
+        &self.negotiated
+    }
+
+    fn is_acceptable(&self, open_object: &OpenObject) -> bool {
+        let keepalive = open_object.keepalive;
+        keepalive >= self.config.min_acceptable_keepalive
+            && keepalive <= self.config.max_acceptable_keepalive
+    }
+
+    // Characteristics the peer proposes for our own Open: the keepalive
+    // within our acceptable range, a DeadTimer no shorter than it
+    fn is_acceptable_proposal(&self, proposal: &OpenObject) -> bool {
+        self.is_acceptable(proposal)
+            && (proposal.deadtimer == 0 || proposal.deadtimer >= proposal.keepalive)
+    }
+
+    fn reset(&mut self, actions: &mut Vec<SessionAction>) {
+        let was_up = self.state == SessionState::Up;
+        actions.push(SessionAction::StopTimer(Timer::Connect));
+        actions.push(SessionAction::StopTimer(Timer::OpenWait));
+        actions.push(SessionAction::StopTimer(Timer::KeepWait));
+        actions.push(SessionAction::StopTimer(Timer::Keepalive));
+        actions.push(SessionAction::StopTimer(Timer::DeadTimer));
+        actions.push(SessionAction::Disconnect);
+        if was_up {
+            actions.push(SessionAction::SessionDown);
+        }
+        self.state = SessionState::Idle;
+        self.connect_retry = 0;
+        self.open_retry = 0;
+        self.local_ok = false;
+        self.remote_ok = false;
+    }
+
+    fn enter_open_wait(&mut self, actions: &mut Vec<SessionAction>) {
+        actions.push(SessionAction::StopTimer(Timer::Connect));
+        actions.push(SessionAction::SendOpen);
+        actions.push(SessionAction::StartTimer(
+            Timer::OpenWait,
+            self.config.open_wait_timer,
+        ));
+        self.state = SessionState::OpenWait;
+    }
+
+    fn restart_keepalive(&self, actions: &mut Vec<SessionAction>) {
+        if self.negotiated.local_keepalive != 0 {
+            actions.push(SessionAction::StartTimer(
+                Timer::Keepalive,
+                self.negotiated.local_keepalive as u16,
+            ));
+        }
+    }
+
+    fn restart_deadtimer(&self, actions: &mut Vec<SessionAction>) {
+        if self.negotiated.remote_deadtimer != 0 {
+            actions.push(SessionAction::StartTimer(
+                Timer::DeadTimer,
+                self.negotiated.remote_deadtimer as u16,
+            ));
+        }
+    }
+
+    fn enter_up(&mut self, actions: &mut Vec<SessionAction>) {
+        actions.push(SessionAction::StopTimer(Timer::OpenWait));
+        actions.push(SessionAction::StopTimer(Timer::KeepWait));
+        self.restart_keepalive(actions);
+        self.restart_deadtimer(actions);
+        actions.push(SessionAction::SessionUp);
+        self.state = SessionState::Up;
+    }
+
+    fn reject_open(&mut self, actions: &mut Vec<SessionAction>) {
+        if self.open_retry != 0 {
+            actions.push(SessionAction::SendPcErr {
+                error_type: 1,
+                error_value: 5,
+            });
+            self.reset(actions);
+            return;
+        }
+        self.open_retry += 1;
+        actions.push(SessionAction::SendPcErr {
+            error_type: 1,
+            error_value: 4,
+        });
+        if self.local_ok {
+            // Our Open was already acknowledged, only the peer's is pending
+            actions.push(SessionAction::StopTimer(Timer::OpenWait));
+            actions.push(SessionAction::StartTimer(
+                Timer::KeepWait,
+                self.config.keep_wait_timer,
+            ));
+            self.state = SessionState::KeepWait;
+        } else {
+            if self.state == SessionState::KeepWait {
+                actions.push(SessionAction::StopTimer(Timer::KeepWait));
+            }
+            actions.push(SessionAction::StartTimer(
+                Timer::OpenWait,
+                self.config.open_wait_timer,
+            ));
+            self.state = SessionState::OpenWait;
+        }
+    }
+
+    fn handle_open(&mut self, open_object: &OpenObject, actions: &mut Vec<SessionAction>) {
+        if self.remote_ok {
+            // A second Open once the first one was accepted
+            actions.push(SessionAction::SendPcErr {
+                error_type: 1,
+                error_value: 1,
+            });
+            self.reset(actions);
+            return;
+        }
+        if !self.is_acceptable(open_object) {
+            self.reject_open(actions);
+            return;
+        }
+        self.remote_ok = true;
+        self.negotiated.remote_keepalive = open_object.keepalive;
+        self.negotiated.remote_deadtimer = open_object.deadtimer;
+        self.negotiated.remote_sid = open_object.sid;
+        actions.push(SessionAction::SendKeepalive);
+        if self.local_ok {
+            self.enter_up(actions);
+        } else {
+            actions.push(SessionAction::StopTimer(Timer::OpenWait));
+            actions.push(SessionAction::StartTimer(
+                Timer::KeepWait,
+                self.config.keep_wait_timer,
+            ));
+            self.state = SessionState::KeepWait;
+        }
+    }
+
+    fn handle_pcerr_proposal(&mut self, pcerr: &PcErr, actions: &mut Vec<SessionAction>) {
+        // The peer rejected our Open, only a 1/4 error carries a proposal
+        // of new session characteristics
+        let negotiable = pcerr
+            .error_objects
+            .iter()
+            .any(|error_object| error_object.error_type == 1 && error_object.error_value == 4);
+        if !negotiable {
+            self.reset(actions);
+            return;
+        }
+        match pcerr.open_object.as_ref() {
+            Some(proposal) if !self.local_ok && self.is_acceptable_proposal(proposal) => {
+                self.negotiated.local_keepalive = proposal.keepalive;
+                self.negotiated.local_deadtimer = proposal.deadtimer;
+                actions.push(SessionAction::SendOpen);
+                if self.remote_ok {
+                    actions.push(SessionAction::StartTimer(
+                        Timer::KeepWait,
+                        self.config.keep_wait_timer,
+                    ));
+                    self.state = SessionState::KeepWait;
+                } else {
+                    actions.push(SessionAction::StopTimer(Timer::KeepWait));
+                    actions.push(SessionAction::StartTimer(
+                        Timer::OpenWait,
+                        self.config.open_wait_timer,
+                    ));
+                    self.state = SessionState::OpenWait;
+                }
+            }
+            _ => {
+                actions.push(SessionAction::SendPcErr {
+                    error_type: 1,
+                    error_value: 6,
+                });
+                self.reset(actions);
+            }
+        }
+    }
+
+    pub fn handle(&mut self, event: SessionEvent) -> Vec<SessionAction> {
+        let mut actions = vec![];
+        match (self.state, event) {
+            (_, SessionEvent::Stop) => {
+                if self.state == SessionState::Up {
+                    actions.push(SessionAction::SendClose(CloseReason::NoExplanation));
+                }
+                self.reset(&mut actions);
+            }
+            (SessionState::Idle, SessionEvent::Start) => {
+                self.connect_retry = 0;
+                actions.push(SessionAction::Connect);
+                actions.push(SessionAction::StartTimer(
+                    Timer::Connect,
+                    self.config.connect_timer,
+                ));
+                self.state = SessionState::TcpPending;
+            }
+            (SessionState::Idle, SessionEvent::ConnectionEstablished)
+            | (SessionState::TcpPending, SessionEvent::ConnectionEstablished) => {
+                self.enter_open_wait(&mut actions);
+            }
+            (SessionState::TcpPending, SessionEvent::TimerExpired(Timer::Connect))
+            | (SessionState::TcpPending, SessionEvent::ConnectionFailed) => {
+                self.connect_retry += 1;
+                if self.connect_retry < self.config.max_connect_retry {
+                    actions.push(SessionAction::Connect);
+                    actions.push(SessionAction::StartTimer(
+                        Timer::Connect,
+                        self.config.connect_timer,
+                    ));
+                } else {
+                    self.reset(&mut actions);
+                }
+            }
+            (SessionState::OpenWait, SessionEvent::Open(open))
+            | (SessionState::KeepWait, SessionEvent::Open(open)) => {
+                self.handle_open(open.open_object(), &mut actions);
+            }
+            (SessionState::OpenWait, SessionEvent::OpenRejected)
+            | (SessionState::KeepWait, SessionEvent::OpenRejected) => {
+                // Undo the acceptance of the Open, the peer has to send another
+                self.remote_ok = false;
+                self.reject_open(&mut actions);
+            }
+            (SessionState::OpenWait, SessionEvent::Keepalive(_))
+            | (SessionState::KeepWait, SessionEvent::Keepalive(_)) => {
+                self.local_ok = true;
+                if self.remote_ok {
+                    self.enter_up(&mut actions);
+                }
+            }
+            (SessionState::OpenWait, SessionEvent::PcErr(pcerr))
+            | (SessionState::KeepWait, SessionEvent::PcErr(pcerr)) => {
+                self.handle_pcerr_proposal(pcerr, &mut actions);
+            }
+            (SessionState::OpenWait, SessionEvent::TimerExpired(Timer::OpenWait)) => {
+                actions.push(SessionAction::SendPcErr {
+                    error_type: 1,
+                    error_value: 2,
+                });
+                self.reset(&mut actions);
+            }
+            (SessionState::KeepWait, SessionEvent::TimerExpired(Timer::KeepWait)) => {
+                actions.push(SessionAction::SendPcErr {
+                    error_type: 1,
+                    error_value: 7,
+                });
+                self.reset(&mut actions);
+            }
+            (SessionState::OpenWait, SessionEvent::MessageReceived)
+            | (SessionState::KeepWait, SessionEvent::MessageReceived) => {
+                // Anything but Open/Keepalive/PCErr before the session is up
+                actions.push(SessionAction::SendPcErr {
+                    error_type: 1,
+                    error_value: 1,
+                });
+                self.reset(&mut actions);
+            }
+            (SessionState::Up, SessionEvent::TimerExpired(Timer::Keepalive)) => {
+                actions.push(SessionAction::SendKeepalive);
+                self.restart_keepalive(&mut actions);
+            }
+            (SessionState::Up, SessionEvent::TimerExpired(Timer::DeadTimer)) => {
+                actions.push(SessionAction::SendClose(CloseReason::DeadTimerExpired));
+                self.reset(&mut actions);
+            }
+            (SessionState::Up, SessionEvent::MessageSent) => {
+                self.restart_keepalive(&mut actions);
+            }
+            (SessionState::Up, SessionEvent::Open(_)) => {
+                actions.push(SessionAction::SendPcErr {
+                    error_type: 1,
+                    error_value: 1,
+                });
+                self.reset(&mut actions);
+            }
+            (SessionState::Up, SessionEvent::Keepalive(_))
+            | (SessionState::Up, SessionEvent::PcErr(_))
+            | (SessionState::Up, SessionEvent::MessageReceived) => {
+                self.restart_deadtimer(&mut actions);
+            }
+            (_, SessionEvent::Close(_))
+            | (_, SessionEvent::ConnectionClosed)
+            | (_, SessionEvent::ConnectionFailed) => {
+                if self.state != SessionState::Idle {
+                    self.reset(&mut actions);
+                }
+            }
+            // Events that have no effect in the current state
+            (_, _) => {}
+        }
+        actions
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::messages::header::CommonHeader;
+    use crate::messages::keepalive::KeepAlive;
+    use crate::messages::open::Open;
+
+    fn open_message(keepalive: u8, deadtimer: u8) -> Open {
+        let input: &[u8] = &[0x01, 0x10, 0x00, 0x08, 0x20, keepalive, deadtimer, 0x01];
+        let (_remaining, open_object) =
+            OpenObject::parse_open_object(input).expect("[!!] Error while parsing open object");
+        Open::new(Default::default(), open_object)
+    }
+
+    fn keepalive_message() -> KeepAlive {
+        let input: &[u8] = &[0x20, 0x02, 0x00, 0x04];
+        let (_remaining, common_header) = CommonHeader::parse_common_header(input)
+            .expect("[!!] Error while parsing common header");
+        common_header.into()
+    }
+
+    #[test]
+    fn test_session_handshake_passive() {
+        let mut fsm = SessionFsm::new(SessionConfig::default());
+        let actions = fsm.handle(SessionEvent::ConnectionEstablished);
+        assert!(actions.contains(&SessionAction::SendOpen));
+        assert_eq!(fsm.state(), SessionState::OpenWait);
+
+        let open = open_message(30, 120);
+        let actions = fsm.handle(SessionEvent::Open(&open));
+        assert!(actions.contains(&SessionAction::SendKeepalive));
+        assert_eq!(fsm.state(), SessionState::KeepWait);
+
+        let keepalive = keepalive_message();
+        let actions = fsm.handle(SessionEvent::Keepalive(&keepalive));
+        assert!(actions.contains(&SessionAction::SessionUp));
+        assert!(actions.contains(&SessionAction::StartTimer(Timer::DeadTimer, 120)));
+        assert!(actions.contains(&SessionAction::StartTimer(Timer::Keepalive, 30)));
+        assert_eq!(fsm.state(), SessionState::Up);
+        assert_eq!(fsm.negotiated().remote_sid, 1);
+    }
+
+    #[test]
+    fn test_session_active_connect_retries() {
+        let config = SessionConfig {
+            max_connect_retry: 2,
+            ..Default::default()
+        };
+        let mut fsm = SessionFsm::new(config);
+        fsm.handle(SessionEvent::Start);
+        assert_eq!(fsm.state(), SessionState::TcpPending);
+        let actions = fsm.handle(SessionEvent::TimerExpired(Timer::Connect));
+        assert!(actions.contains(&SessionAction::Connect));
+        fsm.handle(SessionEvent::TimerExpired(Timer::Connect));
+        assert_eq!(fsm.state(), SessionState::Idle);
+    }
+
+    #[test]
+    fn test_session_open_wait_expiry() {
+        let mut fsm = SessionFsm::new(SessionConfig::default());
+        fsm.handle(SessionEvent::ConnectionEstablished);
+        let actions = fsm.handle(SessionEvent::TimerExpired(Timer::OpenWait));
+        assert!(actions.contains(&SessionAction::SendPcErr {
+            error_type: 1,
+            error_value: 2
+        }));
+        assert_eq!(fsm.state(), SessionState::Idle);
+    }
+
+    #[test]
+    fn test_session_unacceptable_open_is_negotiated() {
+        let config = SessionConfig {
+            min_acceptable_keepalive: 10,
+            ..Default::default()
+        };
+        let mut fsm = SessionFsm::new(config);
+        fsm.handle(SessionEvent::ConnectionEstablished);
+        let open = open_message(5, 20);
+        let actions = fsm.handle(SessionEvent::Open(&open));
+        assert!(actions.contains(&SessionAction::SendPcErr {
+            error_type: 1,
+            error_value: 4
+        }));
+        assert_eq!(fsm.state(), SessionState::OpenWait);
+        let actions = fsm.handle(SessionEvent::Open(&open));
+        assert!(actions.contains(&SessionAction::SendPcErr {
+            error_type: 1,
+            error_value: 5
+        }));
+        assert_eq!(fsm.state(), SessionState::Idle);
+    }
+
+    #[test]
+    fn test_session_unacceptable_open_with_local_ok_restarts_keep_wait() {
+        let config = SessionConfig {
+            min_acceptable_keepalive: 10,
+            ..Default::default()
+        };
+        let mut fsm = SessionFsm::new(config);
+        fsm.handle(SessionEvent::ConnectionEstablished);
+        let keepalive = keepalive_message();
+        fsm.handle(SessionEvent::Keepalive(&keepalive));
+        let open = open_message(5, 20);
+        let actions = fsm.handle(SessionEvent::Open(&open));
+        assert!(actions.contains(&SessionAction::SendPcErr {
+            error_type: 1,
+            error_value: 4
+        }));
+        assert!(actions.contains(&SessionAction::StartTimer(
+            Timer::KeepWait,
+            config.keep_wait_timer
+        )));
+        assert!(!actions.contains(&SessionAction::StartTimer(
+            Timer::OpenWait,
+            config.open_wait_timer
+        )));
+        assert_eq!(fsm.state(), SessionState::KeepWait);
+
+        let open = open_message(30, 120);
+        let actions = fsm.handle(SessionEvent::Open(&open));
+        assert!(actions.contains(&SessionAction::SessionUp));
+        assert_eq!(fsm.state(), SessionState::Up);
+    }
+
+    #[test]
+    fn test_session_open_rejected_outside_the_fsm() {
+        let mut fsm = SessionFsm::new(SessionConfig::default());
+        fsm.handle(SessionEvent::ConnectionEstablished);
+        let open = open_message(30, 120);
+        fsm.handle(SessionEvent::Open(&open));
+        assert_eq!(fsm.state(), SessionState::KeepWait);
+        let actions = fsm.handle(SessionEvent::OpenRejected);
+        assert!(actions.contains(&SessionAction::SendPcErr {
+            error_type: 1,
+            error_value: 4
+        }));
+        assert!(actions.contains(&SessionAction::StopTimer(Timer::KeepWait)));
+        assert_eq!(fsm.state(), SessionState::OpenWait);
+        // The second Open is accepted, the session comes up once ours is
+        fsm.handle(SessionEvent::Open(&open));
+        let keepalive = keepalive_message();
+        fsm.handle(SessionEvent::Keepalive(&keepalive));
+        assert_eq!(fsm.state(), SessionState::Up);
+    }
+
+    fn pcerr_message(error_value: u8, keepalive: u8, deadtimer: u8) -> PcErr {
+        let input: &[u8] = &[
+            0x0d,
+            0x10,
+            0x00,
+            0x08,
+            0x00,
+            0x00,
+            0x01,
+            error_value,
+            0x01,
+            0x10,
+            0x00,
+            0x08,
+            0x20,
+            keepalive,
+            deadtimer,
+            0x01,
+        ];
+        let (_remaining, pcerr) =
+            PcErr::parse_error_message(input).expect("[!!] Failed to parse PCErr message");
+        pcerr
+    }
+
+    #[test]
+    fn test_session_pcerr_with_proposal_resends_open() {
+        let mut fsm = SessionFsm::new(SessionConfig::default());
+        fsm.handle(SessionEvent::ConnectionEstablished);
+        let pcerr = pcerr_message(4, 10, 40);
+        let actions = fsm.handle(SessionEvent::PcErr(&pcerr));
+        assert!(actions.contains(&SessionAction::SendOpen));
+        assert_eq!(fsm.negotiated().local_keepalive, 10);
+        assert_eq!(fsm.negotiated().local_deadtimer, 40);
+        // RemoteOK=0: waiting for the peer's Open again
+        assert!(actions.contains(&SessionAction::StopTimer(Timer::KeepWait)));
+        assert!(actions.contains(&SessionAction::StartTimer(Timer::OpenWait, 60)));
+        assert_eq!(fsm.state(), SessionState::OpenWait);
+    }
+
+    #[test]
+    fn test_session_pcerr_with_proposal_after_remote_open() {
+        let mut fsm = SessionFsm::new(SessionConfig::default());
+        fsm.handle(SessionEvent::ConnectionEstablished);
+        let open = open_message(30, 120);
+        fsm.handle(SessionEvent::Open(&open));
+        assert_eq!(fsm.state(), SessionState::KeepWait);
+        let pcerr = pcerr_message(4, 10, 40);
+        let actions = fsm.handle(SessionEvent::PcErr(&pcerr));
+        // RemoteOK=1: only our Open is pending, KeepWait restarts
+        assert!(actions.contains(&SessionAction::SendOpen));
+        assert!(actions.contains(&SessionAction::StartTimer(Timer::KeepWait, 60)));
+        assert!(!actions.contains(&SessionAction::StartTimer(Timer::OpenWait, 60)));
+        assert_eq!(fsm.state(), SessionState::KeepWait);
+    }
+
+    #[test]
+    fn test_session_pcerr_with_unacceptable_proposal() {
+        let config = SessionConfig {
+            min_acceptable_keepalive: 20,
+            ..Default::default()
+        };
+        let mut fsm = SessionFsm::new(config);
+        fsm.handle(SessionEvent::ConnectionEstablished);
+        let pcerr = pcerr_message(4, 10, 40);
+        let actions = fsm.handle(SessionEvent::PcErr(&pcerr));
+        assert!(actions.contains(&SessionAction::SendPcErr {
+            error_type: 1,
+            error_value: 6
+        }));
+        assert!(!actions.contains(&SessionAction::SendOpen));
+        assert_eq!(fsm.state(), SessionState::Idle);
+
+        // A 1/5 rejection carries no proposal to negotiate
+        let mut fsm = SessionFsm::new(SessionConfig::default());
+        fsm.handle(SessionEvent::ConnectionEstablished);
+        let pcerr = pcerr_message(5, 10, 40);
+        let actions = fsm.handle(SessionEvent::PcErr(&pcerr));
+        assert!(!actions.contains(&SessionAction::SendOpen));
+        assert_eq!(fsm.state(), SessionState::Idle);
+    }
+
+    #[test]
+    fn test_session_deadtimer_expiry() {
+        let mut fsm = SessionFsm::new(SessionConfig::default());
+        fsm.handle(SessionEvent::ConnectionEstablished);
+        let keepalive = keepalive_message();
+        fsm.handle(SessionEvent::Keepalive(&keepalive));
+        let open = open_message(30, 120);
+        fsm.handle(SessionEvent::Open(&open));
+        assert_eq!(fsm.state(), SessionState::Up);
+        let actions = fsm.handle(SessionEvent::TimerExpired(Timer::DeadTimer));
+        assert!(actions.contains(&SessionAction::SendClose(CloseReason::DeadTimerExpired)));
+        assert!(actions.contains(&SessionAction::SessionDown));
+        assert_eq!(fsm.state(), SessionState::Idle);
+    }
+}
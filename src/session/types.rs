@@ -0,0 +1,131 @@
+use crate::messages::keepalive::KeepAlive;
+use crate::messages::open::Open;
+use crate::messages::pcclose::PcClose;
+use crate::messages::pcerr::PcErr;
+use crate::objects::close::CloseReason;
+
+// PCEP session states, as per RFC 5440 Appendix A
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SessionState {
+    #[default]
+    Idle,
+    TcpPending,
+    OpenWait,
+    KeepWait,
+    Up,
+}
+
+impl std::fmt::Display for SessionState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Idle => write!(f, "SessionState::Idle"),
+            Self::TcpPending => write!(f, "SessionState::TCPPending"),
+            Self::OpenWait => write!(f, "SessionState::OpenWait"),
+            Self::KeepWait => write!(f, "SessionState::KeepWait"),
+            Self::Up => write!(f, "SessionState::UP"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Timer {
+    Connect,
+    OpenWait,
+    KeepWait,
+    Keepalive,
+    DeadTimer,
+}
+
+impl std::fmt::Display for Timer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Connect => write!(f, "Timer::Connect"),
+            Self::OpenWait => write!(f, "Timer::OpenWait"),
+            Self::KeepWait => write!(f, "Timer::KeepWait"),
+            Self::Keepalive => write!(f, "Timer::Keepalive"),
+            Self::DeadTimer => write!(f, "Timer::DeadTimer"),
+        }
+    }
+}
+
+// Inputs to the state machine. Timers are owned by the caller, which
+// reports their expiry back through TimerExpired.
+#[derive(Debug)]
+pub enum SessionEvent<'a> {
+    // Start connecting to the peer (active side)
+    Start,
+    Stop,
+    ConnectionEstablished,
+    ConnectionFailed,
+    ConnectionClosed,
+    Open(&'a Open),
+    Keepalive(&'a KeepAlive),
+    PcErr(&'a PcErr),
+    Close(&'a PcClose),
+    // Any other PCEP message received from the peer
+    MessageReceived,
+    // Any PCEP message sent to the peer
+    MessageSent,
+    // The peer's Open was judged unacceptable outside the state machine,
+    // as when replaying a capture where the PCErr sent back tells it
+    OpenRejected,
+    TimerExpired(Timer),
+}
+
+// Outputs of the state machine, to be carried out by the caller
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SessionAction {
+    Connect,
+    Disconnect,
+    SendOpen,
+    SendKeepalive,
+    // Error-Type and Error-value of the PCErr to send; error (1, 4)
+    // must carry an Open with acceptable session characteristics
+    SendPcErr { error_type: u8, error_value: u8 },
+    SendClose(CloseReason),
+    StartTimer(Timer, u16),
+    StopTimer(Timer),
+    SessionUp,
+    SessionDown,
+}
+
+// Local session parameters and acceptance policy for the peer's Open
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SessionConfig {
+    pub keepalive: u8,
+    pub deadtimer: u8,
+    pub sid: u8,
+    pub connect_timer: u16,
+    pub max_connect_retry: u8,
+    pub open_wait_timer: u16,
+    pub keep_wait_timer: u16,
+    pub min_acceptable_keepalive: u8,
+    pub max_acceptable_keepalive: u8,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        SessionConfig {
+            keepalive: 30,
+            deadtimer: 120,
+            sid: 0,
+            connect_timer: 60,
+            max_connect_retry: 5,
+            open_wait_timer: 60,
+            keep_wait_timer: 60,
+            min_acceptable_keepalive: 0,
+            max_acceptable_keepalive: 255,
+        }
+    }
+}
+
+// Parameters in force once both Open messages have been accepted
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct NegotiatedParameters {
+    pub local_keepalive: u8,
+    pub local_deadtimer: u8,
+    pub local_sid: u8,
+    pub remote_keepalive: u8,
+    pub remote_deadtimer: u8,
+    pub remote_sid: u8,
+}
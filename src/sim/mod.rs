@@ -0,0 +1,3 @@
+pub mod pcc;
+pub mod pce;
+pub mod transport;
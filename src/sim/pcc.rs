@@ -0,0 +1,508 @@
+use crate::common::{IntendedAttrList, IntendedPathList, OperationalStatus};
+use crate::messages::message::PcepMessage;
+use crate::messages::pcerr::PcErr;
+use crate::messages::pcinitiate::{
+    PceInitiateLspInstatiation, PceInitiatedLspDeletion, PceInitiatedLspRequest,
+};
+use crate::messages::pcrpt::{PcRpt, StateReport};
+use crate::messages::pcupdate::{Path, UpdateRequest};
+use crate::objects::ero::EroObject;
+use crate::objects::error::ErrorObject;
+use crate::objects::lsp::LspObject;
+use crate::objects::open::OpenObject;
+use crate::objects::srp::SrpObject;
+use crate::tlvs::tlv_set::{
+    Ipv4LSPIndetifiersTLV, SrPCECapabilityTLV, StatefulPCECapabilityTLV, SymbolicPathNameTLV,
+};
+use crate::tlvs::types::Tlv;
+
+use std::collections::BTreeMap;
+use std::net::Ipv4Addr;
+
+// Error-Type 19 (Invalid Operation) values, RFC 8231 and RFC 8281
+const INVALID_OPERATION: u8 = 19;
+const NON_DELEGATED_LSP: u8 = 1;
+const UNKNOWN_PLSP_ID: u8 = 3;
+const LSP_NOT_PCE_INITIATED: u8 = 9;
+
+// One LSP of the inventory reported at synchronization
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LspConfig {
+    pub name: String,
+    pub source: Ipv4Addr,
+    pub destination: Ipv4Addr,
+    pub hops: Vec<Ipv4Addr>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InventoryError {
+    pub line: usize,
+    pub reason: String,
+}
+
+impl std::fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "inventory line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for InventoryError {}
+
+impl LspConfig {
+    // One LSP per line: "name source destination [hop,hop,...]".
+    // Blank lines and lines starting with '#' are ignored.
+    pub fn parse_inventory(input: &str) -> Result<Vec<LspConfig>, InventoryError> {
+        let mut inventory = vec![];
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |reason: String| InventoryError {
+                line: index + 1,
+                reason,
+            };
+            let address = |field: &str| {
+                field
+                    .parse::<Ipv4Addr>()
+                    .map_err(|_e| error(format!("invalid IPv4 address '{}'", field)))
+            };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if !(3..=4).contains(&fields.len()) {
+                return Err(error(format!(
+                    "expected 'name source destination [hops]', got {} field(s)",
+                    fields.len()
+                )));
+            }
+            let hops = match fields.get(3) {
+                Some(hops) => hops
+                    .split(',')
+                    .map(address)
+                    .collect::<Result<Vec<_>, _>>()?,
+                None => vec![],
+            };
+            inventory.push(LspConfig {
+                name: fields[0].to_string(),
+                source: address(fields[1])?,
+                destination: address(fields[2])?,
+                hops,
+            });
+        }
+        Ok(inventory)
+    }
+
+    // Strict IPv4 hops, ending at the destination when no hops are given
+    pub fn ero_object(&self) -> EroObject {
+        if self.hops.is_empty() {
+            EroObject::from_ipv4_hops(&[self.destination])
+        } else {
+            EroObject::from_ipv4_hops(&self.hops)
+        }
+    }
+}
+
+// An LSP as currently held by the simulated PCC
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SimulatedLsp {
+    pub plsp_id: u32,
+    pub name: String,
+    pub source: Ipv4Addr,
+    pub destination: Ipv4Addr,
+    // LSP-ID of the current instance, bumped on every path change
+    pub lsp_id: u16,
+    pub pce_initiated: bool,
+    // Cleared once the PCE hands the delegation back
+    pub delegated: bool,
+    pub ero: EroObject,
+}
+
+impl SimulatedLsp {
+    fn lsp_object(&self) -> LspObject {
+        let mut lsp_object = LspObject::new(
+            self.plsp_id,
+            OperationalStatus::Up,
+            vec![
+                Tlv::Ipv4LSPIndetifiers(Ipv4LSPIndetifiersTLV {
+                    tlv_type: 18,
+                    tlv_len: 16,
+                    tunnel_sender_address: self.source,
+                    lsp_id: self.lsp_id,
+                    tunnel_id: self.plsp_id as u16,
+                    extended_tunnel_id: u32::from(self.source),
+                    tunnel_endpoint_address: self.destination,
+                }),
+                Tlv::SymbolicPathName(SymbolicPathNameTLV {
+                    tlv_type: 17,
+                    tlv_len: self.name.len() as u16,
+                    symbolic_path_name: self.name.clone(),
+                }),
+            ],
+        );
+        lsp_object.flag_create = self.pce_initiated;
+        lsp_object.flag_administrative = true;
+        lsp_object.flag_delegate = self.delegated;
+        lsp_object
+    }
+
+    fn path(&self) -> Path {
+        Path::new(
+            IntendedPathList {
+                paths: vec![(None, self.ero.clone())],
+            },
+            IntendedAttrList::new(None, None, vec![]),
+        )
+    }
+
+    fn state_report(&self, srp_id: Option<u32>) -> StateReport {
+        StateReport::new(
+            srp_id.map(|srp_id| SrpObject::new(srp_id, false)),
+            self.lsp_object(),
+            Some(self.path()),
+        )
+    }
+}
+
+// Stateful PCC behaviour of the simulator, independent of the transport:
+// every handled PCEP message yields the messages to send back.
+#[derive(Debug)]
+pub struct PccSimulator {
+    lsps: BTreeMap<u32, SimulatedLsp>,
+    next_plsp_id: u32,
+    max_sid_depth: u8,
+}
+
+impl PccSimulator {
+    pub fn new(inventory: Vec<LspConfig>, max_sid_depth: u8) -> Self {
+        let mut simulator = PccSimulator {
+            lsps: BTreeMap::new(),
+            next_plsp_id: 1,
+            max_sid_depth,
+        };
+        for lsp_config in inventory {
+            let ero = lsp_config.ero_object();
+            simulator.add_lsp(
+                lsp_config.name,
+                lsp_config.source,
+                lsp_config.destination,
+                ero,
+                false,
+            );
+        }
+        simulator
+    }
+
+    fn add_lsp(
+        &mut self,
+        name: String,
+        source: Ipv4Addr,
+        destination: Ipv4Addr,
+        ero: EroObject,
+        pce_initiated: bool,
+    ) -> &SimulatedLsp {
+        let plsp_id = self.next_plsp_id;
+        self.next_plsp_id += 1;
+        self.lsps.entry(plsp_id).or_insert(SimulatedLsp {
+            plsp_id,
+            name,
+            source,
+            destination,
+            lsp_id: 1,
+            pce_initiated,
+            delegated: true,
+            ero,
+        })
+    }
+
+    pub fn lsps(&self) -> impl Iterator<Item = &SimulatedLsp> {
+        self.lsps.values()
+    }
+
+    pub fn get(&self, plsp_id: u32) -> Option<&SimulatedLsp> {
+        self.lsps.get(&plsp_id)
+    }
+
+    // Stateful (update and instantiation) and SR capable PCC
+    pub fn open_object(&self, keepalive: u8, deadtimer: u8, sid: u8) -> OpenObject {
+        OpenObject::new(
+            keepalive,
+            deadtimer,
+            sid,
+            vec![
+                Tlv::StatefulPCECapability(StatefulPCECapabilityTLV {
+                    tlv_type: 16,
+                    tlv_len: 4,
+                    flag_lsp_update_capability: true,
+                    flag_lsp_instantiate_capability: true,
+                    ..Default::default()
+                }),
+                Tlv::SrPCECapability(SrPCECapabilityTLV {
+                    tlv_type: 26,
+                    tlv_len: 4,
+                    max_sid_depth: self.max_sid_depth,
+                    ..Default::default()
+                }),
+            ],
+        )
+    }
+
+    // One PCRpt per LSP with the S flag set, closed by the
+    // end-of-synchronization marker (PLSP-ID 0, empty ERO)
+    pub fn synchronization(&self) -> Vec<PcepMessage> {
+        let mut messages: Vec<PcepMessage> = self
+            .lsps
+            .values()
+            .map(|lsp| {
+                let mut lsp_object = lsp.lsp_object();
+                lsp_object.flag_sync = true;
+                let state_report = StateReport::new(None, lsp_object, Some(lsp.path()));
+                PcepMessage::PcRpt(PcRpt::new(vec![state_report]))
+            })
+            .collect();
+        let end_of_sync = StateReport::new(
+            None,
+            LspObject::new(0, OperationalStatus::Down, vec![]),
+            Some(Path::new(
+                IntendedPathList {
+                    paths: vec![(None, EroObject::new(vec![]))],
+                },
+                IntendedAttrList::new(None, None, vec![]),
+            )),
+        );
+        messages.push(PcepMessage::PcRpt(PcRpt::new(vec![end_of_sync])));
+        messages
+    }
+
+    fn pcerr(srp_id: u32, error_type: u8, error_value: u8) -> PcepMessage {
+        let mut pcerr = PcErr::new(vec![ErrorObject::new(error_type, error_value)]);
+        pcerr.srp_objects.push(SrpObject::new(srp_id, false));
+        PcepMessage::PcErr(pcerr)
+    }
+
+    fn handle_update(
+        &mut self,
+        update_request: &UpdateRequest,
+    ) -> Result<StateReport, PcepMessage> {
+        let srp_id = update_request.srp_object().srp_id;
+        let plsp_id = update_request.lsp_object().plsp_id;
+        let lsp = self
+            .lsps
+            .get_mut(&plsp_id)
+            .ok_or_else(|| Self::pcerr(srp_id, INVALID_OPERATION, UNKNOWN_PLSP_ID))?;
+        if !lsp.delegated {
+            return Err(Self::pcerr(srp_id, INVALID_OPERATION, NON_DELEGATED_LSP));
+        }
+        // D flag cleared: the PCE returns the delegation, the path is kept
+        if !update_request.lsp_object().flag_delegate {
+            lsp.delegated = false;
+            return Ok(lsp.state_report(Some(srp_id)));
+        }
+        if let Some((_path_attrib, ero_object)) =
+            update_request.path().intended_path().paths.first()
+        {
+            lsp.ero = ero_object.clone();
+            lsp.lsp_id = lsp.lsp_id.wrapping_add(1);
+        }
+        Ok(lsp.state_report(Some(srp_id)))
+    }
+
+    fn handle_instantiation(&mut self, request: &PceInitiateLspInstatiation) -> StateReport {
+        let srp_id = request.srp_object().srp_id;
+        let plsp_id = self.next_plsp_id;
+        let name = request
+            .lsp_object()
+            .symbolic_path_name()
+            .map(String::from)
+            .unwrap_or_else(|| format!("pce-initiated-{}", plsp_id));
+        let (source, destination) = request
+            .endpoints_object()
+            .and_then(|endpoints| endpoints.ipv4_addresses())
+            .unwrap_or((Ipv4Addr::UNSPECIFIED, Ipv4Addr::UNSPECIFIED));
+        let ero = match request.ero_paths().paths.first() {
+            Some((_path_attrib, ero_object)) => ero_object.clone(),
+            None => EroObject::new(vec![]),
+        };
+        self.add_lsp(name, source, destination, ero, true)
+            .state_report(Some(srp_id))
+    }
+
+    fn handle_deletion(
+        &mut self,
+        request: &PceInitiatedLspDeletion,
+    ) -> Result<StateReport, PcepMessage> {
+        let srp_id = request.srp_object().srp_id;
+        let plsp_id = request.lsp_object().plsp_id;
+        match self.lsps.get(&plsp_id) {
+            None => return Err(Self::pcerr(srp_id, INVALID_OPERATION, UNKNOWN_PLSP_ID)),
+            Some(lsp) if !lsp.pce_initiated => {
+                return Err(Self::pcerr(
+                    srp_id,
+                    INVALID_OPERATION,
+                    LSP_NOT_PCE_INITIATED,
+                ))
+            }
+            Some(_) => {}
+        }
+        let lsp = self.lsps.remove(&plsp_id).expect("[!!] LSP checked above");
+        let mut lsp_object = lsp.lsp_object();
+        lsp_object.flag_remove = true;
+        lsp_object.operational_status = OperationalStatus::Down;
+        Ok(StateReport::new(
+            Some(SrpObject::new(srp_id, false)),
+            lsp_object,
+            Some(lsp.path()),
+        ))
+    }
+
+    // React to a PCUpd or PCInitiate, other messages need no answer
+    pub fn handle(&mut self, message: &PcepMessage) -> Vec<PcepMessage> {
+        let results: Vec<Result<StateReport, PcepMessage>> = match message {
+            PcepMessage::PcUpd(update) => update
+                .update_requests()
+                .iter()
+                .map(|update_request| self.handle_update(update_request))
+                .collect(),
+            PcepMessage::PcInitiate(initiate) => initiate
+                .lsp_requests()
+                .iter()
+                .map(|request| match request {
+                    PceInitiatedLspRequest::LspInstantiation(instantiation) => {
+                        Ok(self.handle_instantiation(instantiation))
+                    }
+                    PceInitiatedLspRequest::LspDeletion(deletion) => self.handle_deletion(deletion),
+                })
+                .collect(),
+            _ => vec![],
+        };
+        let mut state_reports = vec![];
+        let mut replies = vec![];
+        for result in results {
+            match result {
+                Ok(state_report) => state_reports.push(state_report),
+                Err(pcerr) => replies.push(pcerr),
+            }
+        }
+        if !state_reports.is_empty() {
+            replies.insert(0, PcepMessage::PcRpt(PcRpt::new(state_reports)));
+        }
+        replies
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::encoder::Encode;
+    use crate::messages::pcupdate::PcepUpdate;
+
+    const INVENTORY: &str = "
+        # name source destination hops
+        lsp-a 10.0.0.1 10.0.0.4 10.0.0.2,10.0.0.3
+        lsp-b 10.0.0.1 10.0.0.5
+    ";
+
+    #[test]
+    fn test_pcc_synchronization() {
+        let inventory = LspConfig::parse_inventory(INVENTORY).expect("[!!] Invalid inventory");
+        assert_eq!(inventory.len(), 2);
+        assert_eq!(inventory[0].hops.len(), 2);
+        let simulator = PccSimulator::new(inventory, 10);
+        let messages = simulator.synchronization();
+        assert_eq!(messages.len(), 3);
+        for message in messages.iter() {
+            let bytes = message.to_bytes();
+            let (_remaining, parsed) =
+                PcepMessage::parse_message(&bytes).expect("[!!] Error while parsing pcep message");
+            assert_eq!(parsed.message_type(), message.message_type());
+        }
+        match messages.last() {
+            Some(PcepMessage::PcRpt(report)) => assert!(report.state_reports()[0].is_end_of_sync()),
+            _ => panic!("[!!] Expected an end-of-sync PCRpt"),
+        }
+        assert!(LspConfig::parse_inventory("lsp-c 10.0.0.1").is_err());
+    }
+
+    #[test]
+    fn test_pcc_update_reports_new_ero() {
+        let inventory = LspConfig::parse_inventory(INVENTORY).expect("[!!] Invalid inventory");
+        let mut simulator = PccSimulator::new(inventory, 10);
+        let new_ero = LspConfig {
+            name: "lsp-a".to_string(),
+            source: Ipv4Addr::new(10, 0, 0, 1),
+            destination: Ipv4Addr::new(10, 0, 0, 4),
+            hops: vec![Ipv4Addr::new(10, 0, 0, 6), Ipv4Addr::new(10, 0, 0, 4)],
+        }
+        .ero_object();
+        let mut lsp_object = LspObject::new(1, OperationalStatus::Up, vec![]);
+        lsp_object.flag_delegate = true;
+        let update = PcepMessage::PcUpd(PcepUpdate::new(vec![UpdateRequest::new(
+            SrpObject::new(7, false),
+            lsp_object,
+            Path::new(
+                IntendedPathList {
+                    paths: vec![(None, new_ero.clone())],
+                },
+                IntendedAttrList::new(None, None, vec![]),
+            ),
+        )]));
+        let replies = simulator.handle(&update);
+        assert_eq!(replies.len(), 1);
+        let bytes = replies[0].to_bytes();
+        let (_remaining, reply) =
+            PcepMessage::parse_message(&bytes).expect("[!!] Error while parsing pcep message");
+        let report = match reply {
+            PcepMessage::PcRpt(report) => report,
+            _ => panic!("[!!] Expected a PCRpt"),
+        };
+        let state_report = &report.state_reports()[0];
+        assert_eq!(state_report.srp_object().map(|srp| srp.srp_id), Some(7));
+        assert_eq!(
+            state_report.lsp_object().symbolic_path_name(),
+            Some("lsp-a")
+        );
+        let path = state_report.path().expect("[!!] Expected a reported path");
+        assert_eq!(
+            path.intended_path().paths[0].1.subobjects,
+            new_ero.subobjects
+        );
+        assert_eq!(simulator.get(1).map(|lsp| lsp.lsp_id), Some(2));
+    }
+
+    #[test]
+    fn test_pcc_update_returns_delegation() {
+        let inventory = LspConfig::parse_inventory(INVENTORY).expect("[!!] Invalid inventory");
+        let mut simulator = PccSimulator::new(inventory, 10);
+        let update = |srp_id: u32| {
+            PcepMessage::PcUpd(PcepUpdate::new(vec![UpdateRequest::new(
+                SrpObject::new(srp_id, false),
+                LspObject::new(2, OperationalStatus::Up, vec![]),
+                Path::new(
+                    IntendedPathList {
+                        paths: vec![(None, EroObject::new(vec![]))],
+                    },
+                    IntendedAttrList::new(None, None, vec![]),
+                ),
+            )]))
+        };
+        // D=0 hands lsp-b back, it is reported without the D flag
+        let replies = simulator.handle(&update(8));
+        match replies.as_slice() {
+            [PcepMessage::PcRpt(report)] => {
+                assert!(!report.state_reports()[0].lsp_object().flag_delegate)
+            }
+            _ => panic!("[!!] Expected a single PCRpt"),
+        }
+        let lsp = simulator.get(2).expect("[!!] Expected lsp-b");
+        assert!(!lsp.delegated);
+        assert_eq!(lsp.lsp_id, 1);
+
+        // Any later update targets a non delegated LSP
+        let replies = simulator.handle(&update(9));
+        match replies.as_slice() {
+            [PcepMessage::PcErr(pcerr)] => assert_eq!(
+                pcerr.error_objects[0],
+                ErrorObject::new(INVALID_OPERATION, NON_DELEGATED_LSP)
+            ),
+            _ => panic!("[!!] Expected a single PCErr"),
+        }
+    }
+}
@@ -0,0 +1,428 @@
+use crate::common::{IntendedAttrList, IntendedPathList, OperationalStatus};
+use crate::lspdb::database::LspDatabase;
+use crate::messages::message::PcepMessage;
+use crate::messages::pcinitiate::{
+    PCInitiate, PceInitiateLspInstatiation, PceInitiatedLspDeletion, PceInitiatedLspRequest,
+};
+use crate::messages::pcupdate::{Path, PcepUpdate, UpdateRequest};
+use crate::objects::endpoints::EndPointsObject;
+use crate::objects::ero::EroObject;
+use crate::objects::lsp::LspObject;
+use crate::objects::open::OpenObject;
+use crate::objects::srp::SrpObject;
+use crate::tlvs::tlv_set::{SrPCECapabilityTLV, StatefulPCECapabilityTLV, SymbolicPathNameTLV};
+use crate::tlvs::types::Tlv;
+
+use serde::Deserialize;
+use std::net::Ipv4Addr;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ScriptError(pub String);
+
+impl std::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid script: {}", self.0)
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+// What the PCC must answer to a scripted request
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum Expectation {
+    // PCRpt carrying the request's SRP-ID and the requested path
+    #[default]
+    Report,
+    // PCErr for the request's SRP-ID, optionally of a given type/value
+    Error {
+        error_type: Option<u8>,
+        error_value: Option<u8>,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Step {
+    // Wait for the PCC's end-of-synchronization marker
+    WaitSync,
+    Sleep {
+        seconds: u64,
+    },
+    Initiate {
+        srp_id: u32,
+        name: String,
+        source: Ipv4Addr,
+        destination: Ipv4Addr,
+        ero: Vec<Ipv4Addr>,
+        #[serde(default)]
+        expect: Expectation,
+    },
+    // LSPs are referred to by symbolic path name
+    Update {
+        srp_id: u32,
+        lsp: String,
+        ero: Vec<Ipv4Addr>,
+        #[serde(default)]
+        expect: Expectation,
+    },
+    Delete {
+        srp_id: u32,
+        lsp: String,
+        #[serde(default)]
+        expect: Expectation,
+    },
+}
+
+impl Step {
+    // SRP-ID and expectation of the steps that wait for an answer
+    pub fn expectation(&self) -> Option<(u32, Expectation)> {
+        match self {
+            Self::Initiate { srp_id, expect, .. }
+            | Self::Update { srp_id, expect, .. }
+            | Self::Delete { srp_id, expect, .. } => Some((*srp_id, *expect)),
+            Self::WaitSync | Self::Sleep { .. } => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WaitSync => write!(f, "wait_sync"),
+            Self::Sleep { seconds } => write!(f, "sleep {}s", seconds),
+            Self::Initiate { srp_id, name, .. } => {
+                write!(f, "initiate {} (srp_id = {})", name, srp_id)
+            }
+            Self::Update { srp_id, lsp, .. } => write!(f, "update {} (srp_id = {})", lsp, srp_id),
+            Self::Delete { srp_id, lsp, .. } => write!(f, "delete {} (srp_id = {})", lsp, srp_id),
+        }
+    }
+}
+
+fn default_timeout() -> u64 {
+    10
+}
+
+// Sequence of requests to send once the session is up
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct Script {
+    // Seconds to wait for each answer or for synchronization
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+    pub steps: Vec<Step>,
+}
+
+impl Script {
+    pub fn from_json(input: &str) -> Result<Self, ScriptError> {
+        serde_json::from_str(input).map_err(|e| ScriptError(e.to_string()))
+    }
+
+    pub fn from_yaml(input: &str) -> Result<Self, ScriptError> {
+        serde_yaml_ng::from_str(input).map_err(|e| ScriptError(e.to_string()))
+    }
+}
+
+// Stateful PCE behaviour of the simulator, independent of the transport
+#[derive(Debug, Default)]
+pub struct PceSimulator {
+    lspdb: LspDatabase,
+    synchronized: bool,
+}
+
+impl PceSimulator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn lspdb(&self) -> &LspDatabase {
+        &self.lspdb
+    }
+
+    pub fn is_synchronized(&self) -> bool {
+        self.synchronized
+    }
+
+    // Stateful (update and instantiation) and SR capable PCE
+    pub fn open_object(keepalive: u8, deadtimer: u8, sid: u8, max_sid_depth: u8) -> OpenObject {
+        OpenObject::new(
+            keepalive,
+            deadtimer,
+            sid,
+            vec![
+                Tlv::StatefulPCECapability(StatefulPCECapabilityTLV {
+                    tlv_type: 16,
+                    tlv_len: 4,
+                    flag_lsp_update_capability: true,
+                    flag_lsp_instantiate_capability: true,
+                    ..Default::default()
+                }),
+                Tlv::SrPCECapability(SrPCECapabilityTLV {
+                    tlv_type: 26,
+                    tlv_len: 4,
+                    max_sid_depth,
+                    ..Default::default()
+                }),
+            ],
+        )
+    }
+
+    // Record a message received from the PCC
+    pub fn process(&mut self, timestamp: Duration, message: &PcepMessage) {
+        if let PcepMessage::PcRpt(report_message) = message {
+            if report_message
+                .state_reports()
+                .iter()
+                .any(|state_report| state_report.is_end_of_sync())
+            {
+                self.synchronized = true;
+            }
+        }
+        self.lspdb.apply(timestamp, message);
+    }
+
+    fn plsp_id(&self, name: &str) -> Result<u32, String> {
+        self.lspdb
+            .get_by_name(name)
+            .map(|lsp| lsp.plsp_id)
+            .ok_or_else(|| format!("LSP {} is not in the LSP database", name))
+    }
+
+    fn path(ero: &[Ipv4Addr]) -> Path {
+        Path::new(
+            IntendedPathList {
+                paths: vec![(None, EroObject::from_ipv4_hops(ero))],
+            },
+            IntendedAttrList::new(None, None, vec![]),
+        )
+    }
+
+    // Message to send for a step, None for steps that send nothing
+    pub fn request(
+        &mut self,
+        timestamp: Duration,
+        step: &Step,
+    ) -> Result<Option<PcepMessage>, String> {
+        let message = match step {
+            Step::WaitSync | Step::Sleep { .. } => return Ok(None),
+            Step::Initiate {
+                srp_id,
+                name,
+                source,
+                destination,
+                ero,
+                ..
+            } => {
+                let mut lsp_object = LspObject::new(
+                    0,
+                    OperationalStatus::Down,
+                    vec![Tlv::SymbolicPathName(SymbolicPathNameTLV {
+                        tlv_type: 17,
+                        tlv_len: name.len() as u16,
+                        symbolic_path_name: name.clone(),
+                    })],
+                );
+                lsp_object.flag_delegate = true;
+                lsp_object.flag_administrative = true;
+                PcepMessage::PcInitiate(PCInitiate::new(vec![
+                    PceInitiatedLspRequest::LspInstantiation(PceInitiateLspInstatiation::new(
+                        SrpObject::new(*srp_id, false),
+                        lsp_object,
+                        Some(EndPointsObject::new_ipv4(*source, *destination)),
+                        IntendedPathList {
+                            paths: vec![(None, EroObject::from_ipv4_hops(ero))],
+                        },
+                        None,
+                    )),
+                ]))
+            }
+            Step::Update {
+                srp_id, lsp, ero, ..
+            } => {
+                let mut lsp_object =
+                    LspObject::new(self.plsp_id(lsp)?, OperationalStatus::Up, vec![]);
+                lsp_object.flag_delegate = true;
+                lsp_object.flag_administrative = true;
+                PcepMessage::PcUpd(PcepUpdate::new(vec![UpdateRequest::new(
+                    SrpObject::new(*srp_id, false),
+                    lsp_object,
+                    Self::path(ero),
+                )]))
+            }
+            Step::Delete { srp_id, lsp, .. } => {
+                let lsp_object =
+                    LspObject::new(self.plsp_id(lsp)?, OperationalStatus::Down, vec![]);
+                PcepMessage::PcInitiate(PCInitiate::new(vec![PceInitiatedLspRequest::LspDeletion(
+                    PceInitiatedLspDeletion::new(SrpObject::new(*srp_id, true), lsp_object),
+                )]))
+            }
+        };
+        self.lspdb.apply(timestamp, &message);
+        Ok(Some(message))
+    }
+
+    // Verdict on a message from the PCC for the step waiting for an
+    // answer, None when the message does not refer to the step's SRP-ID
+    pub fn check(&self, step: &Step, message: &PcepMessage) -> Option<Result<(), String>> {
+        let (srp_id, expect) = step.expectation()?;
+        match message {
+            PcepMessage::PcRpt(report_message) => {
+                let state_report = report_message.state_reports().iter().find(|state_report| {
+                    state_report.srp_object().map(|srp| srp.srp_id) == Some(srp_id)
+                })?;
+                if let Expectation::Error { .. } = expect {
+                    return Some(Err("expected a PCErr, got a PCRpt".to_string()));
+                }
+                if let Some(error_code) = state_report.lsp_object().lsp_error_code() {
+                    return Some(Err(format!("PCRpt reports {}", error_code)));
+                }
+                let reported_ero = state_report
+                    .path()
+                    .and_then(|path| path.intended_path().paths.first())
+                    .map(|(_path_attrib, ero)| ero.ipv4_hops())
+                    .unwrap_or_default();
+                let verdict = match step {
+                    Step::Delete { .. } if !state_report.lsp_object().flag_remove => {
+                        Err("PCRpt does not have the R flag set".to_string())
+                    }
+                    Step::Initiate { ero, .. } | Step::Update { ero, .. }
+                        if *ero != reported_ero =>
+                    {
+                        Err(format!(
+                            "PCRpt reports ERO {:?}, requested {:?}",
+                            reported_ero, ero
+                        ))
+                    }
+                    _ => Ok(()),
+                };
+                Some(verdict)
+            }
+            PcepMessage::PcErr(pcerr) => {
+                if !pcerr.srp_objects.iter().any(|srp| srp.srp_id == srp_id) {
+                    return None;
+                }
+                let (error_type, error_value) = pcerr
+                    .error_objects
+                    .first()
+                    .map(|error| (error.error_type, error.error_value))
+                    .unwrap_or_default();
+                let verdict = match expect {
+                    Expectation::Report => Err(format!(
+                        "PCErr type {} value {} instead of a PCRpt",
+                        error_type, error_value
+                    )),
+                    Expectation::Error {
+                        error_type: expected_type,
+                        error_value: expected_value,
+                    } => {
+                        if expected_type.is_none_or(|t| t == error_type)
+                            && expected_value.is_none_or(|v| v == error_value)
+                        {
+                            Ok(())
+                        } else {
+                            Err(format!(
+                                "PCErr type {} value {}, expected {:?}/{:?}",
+                                error_type, error_value, expected_type, expected_value
+                            ))
+                        }
+                    }
+                };
+                Some(verdict)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::encoder::Encode;
+    use crate::sim::pcc::{LspConfig, PccSimulator};
+
+    const SCRIPT: &str = "
+timeout: 5
+steps:
+  - action: wait_sync
+  - action: update
+    srp_id: 1
+    lsp: lsp-a
+    ero: [10.0.0.6, 10.0.0.4]
+  - action: initiate
+    srp_id: 2
+    name: lsp-x
+    source: 10.0.0.1
+    destination: 10.0.0.9
+    ero: [10.0.0.2, 10.0.0.9]
+  - action: delete
+    srp_id: 3
+    lsp: lsp-a
+    expect:
+      outcome: error
+      error_type: 19
+";
+
+    // Carry messages through their wire encoding, as over TCP
+    fn wire(message: &PcepMessage) -> PcepMessage {
+        let bytes = message.to_bytes();
+        let (_remaining, message) =
+            PcepMessage::parse_message(&bytes).expect("[!!] Error while parsing pcep message");
+        message
+    }
+
+    #[test]
+    fn test_pce_script_parsing() {
+        let script = Script::from_yaml(SCRIPT).expect("[!!] Invalid YAML script");
+        assert_eq!(script.timeout, 5);
+        assert_eq!(script.steps.len(), 4);
+        assert_eq!(
+            script.steps[3].expectation(),
+            Some((
+                3,
+                Expectation::Error {
+                    error_type: Some(19),
+                    error_value: None
+                }
+            ))
+        );
+        let json = r#"{"steps": [{"action": "sleep", "seconds": 1}]}"#;
+        let script = Script::from_json(json).expect("[!!] Invalid JSON script");
+        assert_eq!(script.timeout, 10);
+        assert_eq!(script.steps, vec![Step::Sleep { seconds: 1 }]);
+        assert!(Script::from_json(r#"{"steps": [{"action": "reboot"}]}"#).is_err());
+    }
+
+    #[test]
+    fn test_pce_script_against_pcc_simulator() {
+        let inventory = LspConfig::parse_inventory("lsp-a 10.0.0.1 10.0.0.4 10.0.0.2,10.0.0.4")
+            .expect("[!!] Invalid inventory");
+        let mut pcc = PccSimulator::new(inventory, 10);
+        let mut pce = PceSimulator::new();
+        for message in pcc.synchronization() {
+            pce.process(Duration::from_secs(0), &wire(&message));
+        }
+        assert!(pce.is_synchronized());
+        assert_eq!(pce.lspdb().len(), 1);
+
+        let script = Script::from_yaml(SCRIPT).expect("[!!] Invalid YAML script");
+        for (index, step) in script.steps.iter().enumerate().skip(1) {
+            let timestamp = Duration::from_secs(index as u64);
+            let request = pce
+                .request(timestamp, step)
+                .expect("[!!] Step refers to an unknown LSP")
+                .expect("[!!] Step sends a request");
+            let replies = pcc.handle(&wire(&request));
+            assert_eq!(replies.len(), 1);
+            let reply = wire(&replies[0]);
+            assert_eq!(pce.check(step, &reply), Some(Ok(())), "step {}", step);
+            pce.process(timestamp, &reply);
+        }
+        assert_eq!(pce.lspdb().len(), 2);
+        let lsp_x = pce
+            .lspdb()
+            .get_by_name("lsp-x")
+            .expect("[!!] lsp-x not reported");
+        assert!(lsp_x.pce_initiated);
+    }
+}
@@ -0,0 +1,288 @@
+use crate::encoder::Encode;
+use crate::messages::header::CommonHeader;
+use crate::messages::keepalive::KeepAlive;
+use crate::messages::message::PcepMessage;
+use crate::messages::open::Open;
+use crate::messages::pcclose::PcClose;
+use crate::messages::pcerr::PcErr;
+use crate::messages::types::MessageType;
+use crate::objects::close::CloseObject;
+use crate::objects::error::ErrorObject;
+use crate::objects::open::OpenObject;
+use crate::session::fsm::SessionFsm;
+use crate::session::types::{
+    NegotiatedParameters, SessionAction, SessionConfig, SessionEvent, SessionState, Timer,
+};
+
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::{Duration, Instant};
+
+// Blocking PCEP framing over a TCP stream
+#[derive(Debug)]
+pub struct PcepStream {
+    stream: TcpStream,
+    buf: Vec<u8>,
+}
+
+impl PcepStream {
+    pub fn new(stream: TcpStream) -> Self {
+        PcepStream {
+            stream,
+            buf: vec![],
+        }
+    }
+
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.peer_addr()
+    }
+
+    pub fn send(&mut self, message: &PcepMessage) -> io::Result<()> {
+        self.stream.write_all(&message.to_bytes())
+    }
+
+    // A fully buffered message that does not parse is reported as
+    // InvalidData rather than waited upon
+    fn decode_buffered(&mut self) -> io::Result<Option<PcepMessage>> {
+        let (consumed, message) = match PcepMessage::parse_message(&self.buf) {
+            Ok((remaining, message)) => (self.buf.len() - remaining.len(), message),
+            Err(nom::Err::Incomplete(_needed)) => return Ok(None),
+            Err(e) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("undecodable PCEP message: {:?}", e.map_input(|i| i.len())),
+                ))
+            }
+        };
+        self.buf.drain(..consumed);
+        Ok(Some(message))
+    }
+
+    // Next complete message, or None if timeout elapses first
+    pub fn recv(&mut self, timeout: Duration) -> io::Result<Option<PcepMessage>> {
+        loop {
+            if let Some(message) = self.decode_buffered()? {
+                return Ok(Some(message));
+            }
+            self.stream
+                .set_read_timeout(Some(timeout.max(Duration::from_millis(1))))?;
+            let mut chunk = [0u8; 4096];
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => self.buf.extend_from_slice(&chunk[..n]),
+                Err(e)
+                    if e.kind() == io::ErrorKind::WouldBlock
+                        || e.kind() == io::ErrorKind::TimedOut =>
+                {
+                    return Ok(None)
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    pub fn shutdown(&self) {
+        let _ = self.stream.shutdown(std::net::Shutdown::Both);
+    }
+}
+
+// A PCEP session over an established TCP connection, driven by
+// SessionFsm. Keepalives, timers and the Open handshake are handled
+// here, everything else is handed to the caller.
+#[derive(Debug)]
+pub struct PcepSession {
+    stream: PcepStream,
+    fsm: SessionFsm,
+    open_object: OpenObject,
+    peer_open: Option<OpenObject>,
+    timers: Vec<(Timer, Instant)>,
+    closed: bool,
+}
+
+impl PcepSession {
+    // Run the Open/Keepalive exchange until the session is up
+    pub fn establish(
+        stream: TcpStream,
+        config: SessionConfig,
+        open_object: OpenObject,
+    ) -> io::Result<Self> {
+        let mut session = PcepSession {
+            stream: PcepStream::new(stream),
+            fsm: SessionFsm::new(config),
+            open_object,
+            peer_open: None,
+            timers: vec![],
+            closed: false,
+        };
+        let actions = session.fsm.handle(SessionEvent::ConnectionEstablished);
+        session.apply(actions)?;
+        while session.fsm.state() != SessionState::Up {
+            match session.poll(Duration::from_secs(1))? {
+                None | Some(PcepMessage::PcErr(_)) | Some(PcepMessage::PcClose(_)) => {}
+                // Anything else than Open/Keepalive/PCErr resets the session
+                Some(_) => {
+                    let actions = session.fsm.handle(SessionEvent::MessageReceived);
+                    session.apply(actions)?;
+                }
+            }
+            if session.closed {
+                return Err(io::Error::new(
+                    io::ErrorKind::ConnectionRefused,
+                    "PCEP session negotiation failed",
+                ));
+            }
+        }
+        Ok(session)
+    }
+
+    pub fn state(&self) -> SessionState {
+        self.fsm.state()
+    }
+
+    pub fn negotiated(&self) -> &NegotiatedParameters {
+        self.fsm.negotiated()
+    }
+
+    // Open object received from the peer during the handshake
+    pub fn peer_open(&self) -> Option<&OpenObject> {
+        self.peer_open.as_ref()
+    }
+
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.peer_addr()
+    }
+
+    pub fn send(&mut self, message: &PcepMessage) -> io::Result<()> {
+        self.ensure_open()?;
+        self.stream.send(message)?;
+        let actions = self.fsm.handle(SessionEvent::MessageSent);
+        self.apply(actions)
+    }
+
+    // Send a PCClose and tear the session down
+    pub fn close(&mut self) -> io::Result<()> {
+        let actions = self.fsm.handle(SessionEvent::Stop);
+        self.apply(actions)
+    }
+
+    // Wait up to timeout for the next message. Session management
+    // messages are consumed and yield None, PCErr and PCClose are
+    // passed on after the state machine has seen them.
+    pub fn poll(&mut self, timeout: Duration) -> io::Result<Option<PcepMessage>> {
+        self.ensure_open()?;
+        self.fire_timers()?;
+        self.ensure_open()?;
+        let now = Instant::now();
+        let wait = self
+            .timers
+            .iter()
+            .map(|(_timer, deadline)| deadline.saturating_duration_since(now))
+            .fold(timeout, Duration::min);
+        let message = match self.stream.recv(wait) {
+            Ok(Some(message)) => message,
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                let actions = self.fsm.handle(SessionEvent::ConnectionClosed);
+                self.apply(actions)?;
+                return Err(e);
+            }
+        };
+        let actions = match message {
+            PcepMessage::Open(ref open) => {
+                self.peer_open = Some(open.open_object().clone());
+                self.fsm.handle(SessionEvent::Open(open))
+            }
+            PcepMessage::Keepalive(ref keepalive) => {
+                self.fsm.handle(SessionEvent::Keepalive(keepalive))
+            }
+            PcepMessage::PcErr(ref pcerr) => self.fsm.handle(SessionEvent::PcErr(pcerr)),
+            PcepMessage::PcClose(ref close) => self.fsm.handle(SessionEvent::Close(close)),
+            _ if self.fsm.state() == SessionState::Up => {
+                self.fsm.handle(SessionEvent::MessageReceived)
+            }
+            // Left to the caller while the handshake is in progress
+            _ => vec![],
+        };
+        self.apply(actions)?;
+        match message {
+            PcepMessage::Open(_) | PcepMessage::Keepalive(_) => Ok(None),
+            message => Ok(Some(message)),
+        }
+    }
+
+    fn ensure_open(&self) -> io::Result<()> {
+        if self.closed {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "PCEP session is down",
+            ));
+        }
+        Ok(())
+    }
+
+    fn fire_timers(&mut self) -> io::Result<()> {
+        let now = Instant::now();
+        while let Some(index) = self
+            .timers
+            .iter()
+            .position(|(_timer, deadline)| *deadline <= now)
+        {
+            let (timer, _deadline) = self.timers.remove(index);
+            let actions = self.fsm.handle(SessionEvent::TimerExpired(timer));
+            self.apply(actions)?;
+        }
+        Ok(())
+    }
+
+    fn local_open(&self) -> PcepMessage {
+        let mut open_object = self.open_object.clone();
+        open_object.keepalive = self.fsm.negotiated().local_keepalive;
+        open_object.deadtimer = self.fsm.negotiated().local_deadtimer;
+        PcepMessage::Open(Open::new(CommonHeader::new(MessageType::Open), open_object))
+    }
+
+    fn apply(&mut self, actions: Vec<SessionAction>) -> io::Result<()> {
+        for action in actions {
+            match action {
+                SessionAction::SendOpen => {
+                    let open = self.local_open();
+                    self.stream.send(&open)?;
+                }
+                SessionAction::SendKeepalive => {
+                    self.stream
+                        .send(&PcepMessage::Keepalive(KeepAlive::new()))?;
+                }
+                SessionAction::SendPcErr {
+                    error_type,
+                    error_value,
+                } => {
+                    let mut pcerr = PcErr::new(vec![ErrorObject::new(error_type, error_value)]);
+                    if (error_type, error_value) == (1, 4) {
+                        pcerr.open_object = Some(self.open_object.clone());
+                    }
+                    self.stream.send(&PcepMessage::PcErr(pcerr))?;
+                }
+                SessionAction::SendClose(reason) => {
+                    let close = PcClose::new(CloseObject::new(reason));
+                    self.stream.send(&PcepMessage::PcClose(close))?;
+                }
+                SessionAction::StartTimer(timer, seconds) => {
+                    self.timers.retain(|(t, _deadline)| *t != timer);
+                    self.timers
+                        .push((timer, Instant::now() + Duration::from_secs(seconds as u64)));
+                }
+                SessionAction::StopTimer(timer) => {
+                    self.timers.retain(|(t, _deadline)| *t != timer);
+                }
+                SessionAction::Disconnect => {
+                    self.stream.shutdown();
+                    self.closed = true;
+                }
+                // Already connected, nothing to (re)connect
+                SessionAction::Connect => {}
+                SessionAction::SessionUp | SessionAction::SessionDown => {}
+            }
+        }
+        Ok(())
+    }
+}
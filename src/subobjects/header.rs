@@ -6,9 +6,11 @@ use nom::number;
 use nom::sequence::tuple;
 use nom::IResult;
 
+use super::sr::NaiType;
 use super::types::SubObjectTypes;
+use crate::encoder::Encode;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct SubObject {
     pub flag_l: bool,
     pub subobject_type: SubObjectTypes,
@@ -16,6 +18,45 @@ pub struct SubObject {
 }
 
 impl SubObject {
+    pub fn new(flag_l: bool, subobject_type: SubObjectTypes) -> Self {
+        let mut subobject = SubObject {
+            flag_l,
+            subobject_type,
+            subobject_len: 0,
+        };
+        subobject.subobject_len = subobject.to_bytes().len() as u8;
+        subobject
+    }
+
+    // One line form of the hop: the address, or the SID (the label for
+    // an MPLS SID) followed by the NAI
+    pub fn summary(&self) -> String {
+        match self.subobject_type {
+            SubObjectTypes::Ipv4Prefix(ref prefix) if prefix.pref_len == 32 => {
+                format!("{}", prefix.ipv4_addr)
+            }
+            SubObjectTypes::Ipv4Prefix(ref prefix) => {
+                format!("{}/{}", prefix.ipv4_addr, prefix.pref_len)
+            }
+            SubObjectTypes::Ipv6Prefix => String::from("ipv6"),
+            SubObjectTypes::As => String::from("as"),
+            SubObjectTypes::Sr(ref sr_subobject) => {
+                let sid = match (sr_subobject.flag_s, sr_subobject.flag_m) {
+                    (true, _) => String::new(),
+                    (false, true) => format!("{}", sr_subobject.sid >> 12),
+                    (false, false) => format!("{}", sr_subobject.sid),
+                };
+                match sr_subobject.nai_type {
+                    NaiType::Ipv4Adj(ref nai) => {
+                        format!("{}({}->{})", sid, nai.local_ipv4, nai.remote_ipv4)
+                    }
+                    NaiType::Absent => sid,
+                }
+            }
+            SubObjectTypes::Unknown(subobject_type) => format!("?{}", subobject_type),
+        }
+    }
+
     fn parse_l_subobj(input: &[u8]) -> IResult<&[u8], (u8, u8)> {
         bits::bits::<_, _, Error<_>, _, _>(tuple((
             bits::streaming::take(1u8),
@@ -54,3 +95,25 @@ impl std::fmt::Display for SubObject {
         )
     }
 }
+
+impl Encode for SubObject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut body = vec![];
+        let subobject_type = match self.subobject_type {
+            SubObjectTypes::Ipv4Prefix(ref ipv4_pref_subobject) => {
+                ipv4_pref_subobject.encode(&mut body);
+                1
+            }
+            SubObjectTypes::Ipv6Prefix => 2,
+            SubObjectTypes::As => 32,
+            SubObjectTypes::Sr(ref sr_subobject) => {
+                sr_subobject.encode(&mut body);
+                36
+            }
+            SubObjectTypes::Unknown(x) => x,
+        };
+        buf.push((self.flag_l as u8) << 7 | (subobject_type & 0b111_1111));
+        buf.push((body.len() + 2) as u8);
+        buf.extend_from_slice(&body);
+    }
+}
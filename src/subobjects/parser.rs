@@ -1,5 +1,8 @@
 use nom::bytes;
-use nom::IResult;
+use nom::error::{Error, ErrorKind};
+use nom::{Err, IResult};
+
+use crate::error;
 
 use super::header::SubObject;
 use super::prefix::Ipv4PrefixSubobject;
@@ -11,54 +14,52 @@ pub struct Parser;
 impl Parser {
     fn parse_subobject(input: &[u8]) -> IResult<&[u8], SubObject> {
         let (remaining, mut subobject) = SubObject::parse_common_subobject(input)?;
+        if subobject.subobject_len < 2 {
+            return Err(Err::Failure(Error::new(input, ErrorKind::LengthValue)));
+        }
         let subobject_body_len = subobject.subobject_len - 2;
         let (remaining, subobject_body) =
             bytes::streaming::take(subobject_body_len as usize)(remaining)?;
         let subobject_type = match subobject.subobject_type {
             SubObjectTypes::Ipv4Prefix(_) => {
-                let (remaining, ipv4_pref_subobject) =
-                    Ipv4PrefixSubobject::parse_ipv4_pref_subobject(subobject_body)?;
-                assert!(
-                    remaining.is_empty(),
-                    "[!!] Ipv4 prefix object not parsed fully"
-                );
+                let (body_left, ipv4_pref_subobject) = error::complete(
+                    subobject_body,
+                    Ipv4PrefixSubobject::parse_ipv4_pref_subobject,
+                )?;
+                if !body_left.is_empty() {
+                    // Ipv4 prefix subobject not parsed fully
+                    return Err(Err::Failure(Error::new(body_left, ErrorKind::LengthValue)));
+                }
                 SubObjectTypes::Ipv4Prefix(ipv4_pref_subobject)
             }
-            SubObjectTypes::Ipv6Prefix => {
-                unimplemented!()
-            }
-            SubObjectTypes::As => {
-                unimplemented!()
-            }
             SubObjectTypes::Sr(_) => {
-                let (remaining, sr_subobject) = SrSubobject::parse_sr_subobject(subobject_body)?;
-                assert!(
-                    remaining.is_empty(),
-                    "[!!] Sr subobject is not parsed fully.."
-                );
+                let (body_left, sr_subobject) =
+                    error::complete(subobject_body, SrSubobject::parse_sr_subobject)?;
+                if !body_left.is_empty() {
+                    // Sr subobject not parsed fully
+                    return Err(Err::Failure(Error::new(body_left, ErrorKind::LengthValue)));
+                }
                 SubObjectTypes::Sr(sr_subobject)
             }
-            SubObjectTypes::Unknown(_x) => {
-                unimplemented!()
-            }
+            // No decoder for IPv6 prefix, AS and unknown subobjects yet
+            _ => return Err(Err::Failure(Error::new(input, ErrorKind::Switch))),
         };
         subobject.subobject_type = subobject_type;
         Ok((remaining, subobject))
     }
 
+    // Subobjects fill the whole ERO body, which is complete
     pub fn parse_subobjects(input: &[u8]) -> IResult<&[u8], Vec<SubObject>> {
-        let mut left = input;
-        let mut subobjects = vec![];
-        while left.first().is_some() {
-            match Self::parse_subobject(left) {
-                Ok((remaining, subobject)) => {
-                    subobjects.push(subobject);
-                    left = remaining;
-                }
-                Err(e) => return Err(e),
+        error::complete(input, |input| {
+            let mut left = input;
+            let mut subobjects = vec![];
+            while !left.is_empty() {
+                let (remaining, subobject) = Self::parse_subobject(left)?;
+                subobjects.push(subobject);
+                left = remaining;
             }
-        }
-        Ok((left, subobjects))
+            Ok((left, subobjects))
+        })
     }
 }
 
@@ -147,4 +148,37 @@ pub mod tests {
         assert!(remaining.is_empty());
         assert_eq!(expected_subobjects, subobjects);
     }
+
+    #[test]
+    fn test_subobject_parser_truncated() {
+        // Ipv4 prefix subobject of 8 bytes, ERO body ends after 6
+        let input: &[u8] = &[0x01, 0x08, 0xc0, 0xa8, 0x96, 0x2d];
+        assert!(matches!(
+            Parser::parse_subobjects(input),
+            Err(nom::Err::Failure(e)) if e.code == nom::error::ErrorKind::Complete
+        ));
+    }
+
+    #[test]
+    fn test_subobject_parser_malformed() {
+        let inputs: &[&[u8]] = &[
+            // Length below the subobject header
+            &[0x01, 0x01, 0xc0, 0xa8],
+            // Ipv4 prefix subobject longer than its fields
+            &[0x01, 0x0a, 0xc0, 0xa8, 0x96, 0x2d, 0x20, 0x00, 0x00, 0x00],
+            // AS subobject, not decoded
+            &[0x20, 0x04, 0xfd, 0xe8],
+            // Sr subobject with an unknown NAI type
+            &[
+                0x24, 0x10, 0x70, 0x01, 0x05, 0xdc, 0x30, 0x00, 0x0a, 0x68, 0x69, 0x02, 0x0a, 0x68,
+                0x69, 0x01,
+            ],
+        ];
+        for input in inputs {
+            assert!(matches!(
+                Parser::parse_subobjects(input),
+                Err(nom::Err::Failure(_))
+            ));
+        }
+    }
 }
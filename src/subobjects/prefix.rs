@@ -5,8 +5,9 @@ use nom::combinator::map_res;
 use nom::number;
 use nom::IResult;
 
+use crate::encoder::Encode;
 use std::net::Ipv4Addr;
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Ipv4PrefixSubobject {
     pub ipv4_addr: Ipv4Addr,
     pub pref_len: u8,
@@ -24,6 +25,14 @@ impl Default for Ipv4PrefixSubobject {
 }
 
 impl Ipv4PrefixSubobject {
+    pub fn new(ipv4_addr: Ipv4Addr, pref_len: u8) -> Self {
+        Ipv4PrefixSubobject {
+            ipv4_addr,
+            pref_len,
+            reserved: 0,
+        }
+    }
+
     pub fn parse_ipv4_pref_subobject(input: &[u8]) -> IResult<&[u8], Self> {
         let (remaining, ipv4_addr_octet): (&[u8], [u8; 4]) =
             map_res(bytes::streaming::take(4usize), |f: &[u8]| f.try_into())(input)?;
@@ -53,3 +62,11 @@ impl std::fmt::Display for Ipv4PrefixSubobject {
         )
     }
 }
+
+impl Encode for Ipv4PrefixSubobject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.ipv4_addr.octets());
+        buf.push(self.pref_len);
+        buf.push(self.reserved);
+    }
+}
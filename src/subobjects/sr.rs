@@ -1,16 +1,17 @@
+use crate::encoder::Encode;
 use colored::Colorize;
 use indoc::writedoc;
 use nom::bits;
 use nom::bytes;
 use nom::combinator::map_res;
-use nom::error::Error;
+use nom::error::{Error, ErrorKind};
 use nom::number;
 use nom::sequence::tuple;
-use nom::IResult;
+use nom::{Err, IResult};
 use std::net::Ipv4Addr;
 
 #[non_exhaustive]
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub enum NaiType {
     #[default]
     Absent,
@@ -31,7 +32,7 @@ impl TryFrom<u8> for NaiType {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Ipv4AdjNAI {
     pub local_ipv4: Ipv4Addr,
     pub remote_ipv4: Ipv4Addr,
@@ -60,7 +61,7 @@ impl Ipv4AdjNAI {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct SrSubobject {
     pub nai_type: NaiType,
     pub flag_f: bool,
@@ -79,19 +80,30 @@ impl SrSubobject {
         )))(input)
     }
     pub fn parse_sr_subobject(subobject_body: &[u8]) -> IResult<&[u8], Self> {
-        let (subobject_body, nt_res_flags) = Self::parse_nt_res_flags(subobject_body)?;
-        let (subobject_body, sid) = number::streaming::be_u32(subobject_body)?;
+        let (remaining, nt_res_flags) = Self::parse_nt_res_flags(subobject_body)?;
+        // Only absent and IPv4 adjacency NAIs are decoded
+        let nai_type = NaiType::try_from(nt_res_flags.0)
+            .map_err(|_e| Err::Failure(Error::new(subobject_body, ErrorKind::Switch)))?;
+        let flag_f = nt_res_flags.2 & 0b0000_1000 == 0b0000_1000;
+        let flag_s = nt_res_flags.2 & 0b0000_0100 == 0b0000_0100;
+        // S flag : the SID is absent, F flag : the NAI is absent
+        let (subobject_body, sid) = if flag_s {
+            (remaining, 0)
+        } else {
+            number::streaming::be_u32(remaining)?
+        };
         let mut sr_subobject = SrSubobject {
-            nai_type: nt_res_flags
-                .0
-                .try_into()
-                .expect("[!!] Error: Unknown NaiType"),
-            flag_f: nt_res_flags.2 & 0b0000_1000 == 0b0000_1000,
-            flag_s: nt_res_flags.2 & 0b0000_0100 == 0b0000_0100,
+            nai_type,
+            flag_f,
+            flag_s,
             flag_c: nt_res_flags.2 & 0b0000_0010 == 0b0000_0010,
             flag_m: nt_res_flags.2 & 0b0000_0001 == 0b0000_0001,
             sid,
         };
+        if flag_f {
+            sr_subobject.nai_type = NaiType::Absent;
+            return Ok((subobject_body, sr_subobject));
+        }
         match sr_subobject.nai_type {
             NaiType::Absent => Ok((subobject_body, sr_subobject)),
             NaiType::Ipv4Adj(_) => {
@@ -157,3 +169,28 @@ impl std::fmt::Display for SrSubobject {
         )
     }
 }
+
+impl Encode for SrSubobject {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let nai_type: u16 = match self.nai_type {
+            NaiType::Absent => 0,
+            NaiType::Ipv4Adj(_) => 3,
+        };
+        let nt_res_flags: u16 = nai_type << 12
+            | (self.flag_f as u16) << 3
+            | (self.flag_s as u16) << 2
+            | (self.flag_c as u16) << 1
+            | self.flag_m as u16;
+        buf.extend_from_slice(&nt_res_flags.to_be_bytes());
+        if !self.flag_s {
+            buf.extend_from_slice(&self.sid.to_be_bytes());
+        }
+        if self.flag_f {
+            return;
+        }
+        if let NaiType::Ipv4Adj(ref ipv4_adj_nai) = self.nai_type {
+            buf.extend_from_slice(&ipv4_adj_nai.local_ipv4.octets());
+            buf.extend_from_slice(&ipv4_adj_nai.remote_ipv4.octets());
+        }
+    }
+}
@@ -2,7 +2,7 @@ use super::prefix::Ipv4PrefixSubobject;
 use super::sr::SrSubobject;
 
 #[non_exhaustive]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum SubObjectTypes {
     Ipv4Prefix(Ipv4PrefixSubobject),
     Ipv6Prefix,
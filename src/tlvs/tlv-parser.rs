@@ -1,9 +1,14 @@
+use nom::bytes;
 use nom::number;
-use nom::{Err, IResult};
+use nom::IResult;
 
 use crate::common::Utils;
+use crate::error;
+use crate::registry;
 use crate::tlvs::tlv_set::{
-    Ipv4LSPIndetifiersTLV, SrPCECapabilityTLV, StatefulPCECapabilityTLV, UnknownTLV,
+    Ipv4LSPIndetifiersTLV, LspDbVersionTLV, LspErrorCodeTLV, MultipathBackupTLV, MultipathCapTLV,
+    MultipathWeightTLV, NoPathVectorTLV, OfListTLV, SrPCECapabilityTLV, StatefulPCECapabilityTLV,
+    UnknownTLV,
 };
 use crate::tlvs::types::Tlv;
 
@@ -12,62 +17,118 @@ use super::tlv_set::SymbolicPathNameTLV;
 pub struct Parser;
 
 impl Parser {
-    fn handle_padding(len: usize, input: &[u8]) -> IResult<&[u8], &[u8]> {
-        match Utils::consume_padding(len, input) {
-            Ok((remaining, padding)) => Ok((remaining, padding)),
-            Err(Err::Incomplete(_x)) => Ok((input, &[])),
-            Err(e) => Err(e),
-        }
-    }
+    // The declared tlv_len frames the TLV: its parser only sees the
+    // length field and value, so a length that does not match the TLV
+    // format cannot shift the TLVs that follow
     pub fn parse_tlv(input: &[u8]) -> IResult<&[u8], Tlv> {
         let (remaining, tlv_type) = number::streaming::be_u16(input)?;
-        match tlv_type.into() {
-            Tlv::StatefulPCECapability(_) => {
-                // parse StatefulPCETLV
-                let (remaining, tlv) = StatefulPCECapabilityTLV::parse_tlv(remaining)?;
-                let (remaining, _padding) = Self::handle_padding(tlv.tlv_len as usize, remaining)?;
-                Ok((remaining, Tlv::StatefulPCECapability(tlv)))
-            }
-            Tlv::SrPCECapability(_) => {
-                // parse SRPCECapabilityTLV
-                let (remaining, tlv) = SrPCECapabilityTLV::parse_tlv(remaining)?;
-                let (remaining, _padding) = Self::handle_padding(tlv.tlv_len as usize, remaining)?;
-                Ok((remaining, Tlv::SrPCECapability(tlv)))
-            }
-            Tlv::SymbolicPathName(_) => {
-                // parse SymbolicPathNameTLV
-                let (remaining, tlv) = SymbolicPathNameTLV::parse_tlv(remaining)?;
-                let (remaining, _padding) = Self::handle_padding(tlv.tlv_len as usize, remaining)?;
-                Ok((remaining, Tlv::SymbolicPathName(tlv)))
-            }
-            Tlv::Ipv4LSPIndetifiers(_) => {
-                let (remaining, tlv) = Ipv4LSPIndetifiersTLV::parse_tlv(remaining)?;
-                let (remaining, _padding) = Self::handle_padding(tlv.tlv_len as usize, remaining)?;
-                Ok((remaining, Tlv::Ipv4LSPIndetifiers(tlv)))
-            }
-            Tlv::Unknown(_) => {
-                //Parse UnknownTlv
-                let (remaining, mut tlv) = UnknownTLV::parse_tlv(remaining)?;
-                tlv.tlv_type = tlv_type;
-                let (remaining, _padding) = Self::handle_padding(tlv.tlv_len as usize, remaining)?;
-                Ok((remaining, Tlv::Unknown(tlv)))
-            }
-        }
+        let (_value, tlv_len) = number::streaming::be_u16(remaining)?;
+        let (after_value, len_value) = bytes::streaming::take(tlv_len as usize + 2)(remaining)?;
+        let (remaining, _padding) = Utils::consume_padding(tlv_len as usize, after_value)?;
+        let (_len_value, tlv) = error::complete(len_value, |len_value| {
+            let tlv = match tlv_type.into() {
+                Tlv::StatefulPCECapability(_) => {
+                    let (len_value, tlv) = StatefulPCECapabilityTLV::parse_tlv(len_value)?;
+                    (len_value, Tlv::StatefulPCECapability(tlv))
+                }
+                Tlv::SrPCECapability(_) => {
+                    let (len_value, tlv) = SrPCECapabilityTLV::parse_tlv(len_value)?;
+                    (len_value, Tlv::SrPCECapability(tlv))
+                }
+                Tlv::SymbolicPathName(_) => {
+                    let (len_value, tlv) = SymbolicPathNameTLV::parse_tlv(len_value)?;
+                    (len_value, Tlv::SymbolicPathName(tlv))
+                }
+                Tlv::Ipv4LSPIndetifiers(_) => {
+                    let (len_value, tlv) = Ipv4LSPIndetifiersTLV::parse_tlv(len_value)?;
+                    (len_value, Tlv::Ipv4LSPIndetifiers(tlv))
+                }
+                Tlv::OfList(_) => {
+                    let (len_value, tlv) = OfListTLV::parse_tlv(len_value)?;
+                    (len_value, Tlv::OfList(tlv))
+                }
+                Tlv::NoPathVector(_) => {
+                    let (len_value, tlv) = NoPathVectorTLV::parse_tlv(len_value)?;
+                    (len_value, Tlv::NoPathVector(tlv))
+                }
+                Tlv::LspErrorCode(_) => {
+                    let (len_value, tlv) = LspErrorCodeTLV::parse_tlv(len_value)?;
+                    (len_value, Tlv::LspErrorCode(tlv))
+                }
+                Tlv::LspDbVersion(_) => {
+                    let (len_value, tlv) = LspDbVersionTLV::parse_tlv(len_value)?;
+                    (len_value, Tlv::LspDbVersion(tlv))
+                }
+                Tlv::MultipathCap(_) => {
+                    let (len_value, tlv) = MultipathCapTLV::parse_tlv(len_value)?;
+                    (len_value, Tlv::MultipathCap(tlv))
+                }
+                Tlv::MultipathWeight(_) => {
+                    let (len_value, tlv) = MultipathWeightTLV::parse_tlv(len_value)?;
+                    (len_value, Tlv::MultipathWeight(tlv))
+                }
+                Tlv::MultipathBackup(_) => {
+                    let (len_value, tlv) = MultipathBackupTLV::parse_tlv(len_value)?;
+                    (len_value, Tlv::MultipathBackup(tlv))
+                }
+                Tlv::Unknown(_) | Tlv::Custom(_) => {
+                    //Parse UnknownTlv, unless a decoder is registered for it
+                    let (len_value, mut tlv) = UnknownTLV::parse_tlv(len_value)?;
+                    tlv.tlv_type = tlv_type;
+                    match registry::decode_tlv(tlv_type, &tlv.tlv_data) {
+                        Some(custom) => (len_value, Tlv::Custom(custom)),
+                        None => (len_value, Tlv::Unknown(tlv)),
+                    }
+                }
+            };
+            Ok(tlv)
+        })?;
+        Ok((remaining, tlv))
     }
 
+    // TLVs fill the rest of an object body, which is complete: a TLV or
+    // its padding running past the end of it is malformed
     pub fn parse_tlvs(input: &[u8]) -> IResult<&[u8], Vec<Tlv>> {
-        let mut left = input;
-        let mut tlvs = vec![];
-        while left.first().is_some() {
-            match Self::parse_tlv(left) {
-                Ok((remaining, tlv)) => {
-                    tlvs.push(tlv);
-                    left = remaining;
-                }
-                Err(Err::Incomplete(_x)) => break,
-                Err(e) => return Err(e),
+        error::complete(input, |input| {
+            let mut left = input;
+            let mut tlvs = vec![];
+            while !left.is_empty() {
+                let (remaining, tlv) = Self::parse_tlv(left)?;
+                tlvs.push(tlv);
+                left = remaining;
             }
+            Ok((left, tlvs))
+        })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use nom::error::ErrorKind;
+    use nom::Err;
+
+    #[test]
+    fn test_tlv_parser_truncation() {
+        // SYMBOLIC-PATH-NAME "lsp-1" and its 3 bytes of padding
+        let input: &[u8] = &[
+            0x00, 0x11, 0x00, 0x05, 0x6c, 0x73, 0x70, 0x2d, 0x31, 0x00, 0x00, 0x00,
+        ];
+        let (remaining, tlvs) = Parser::parse_tlvs(input).expect("[!!] Error while parsing tlvs");
+        assert!(remaining.is_empty());
+        assert_eq!(tlvs.len(), 1);
+
+        // On its own a TLV asks for the missing bytes
+        assert_eq!(
+            Parser::parse_tlv(&input[..7]),
+            Err(Err::Incomplete(nom::Needed::new(2)))
+        );
+        // Within an object body, a cut value or missing padding is an error
+        for len in [2, 7, 9, 11] {
+            assert!(matches!(
+                Parser::parse_tlvs(&input[..len]),
+                Err(Err::Failure(e)) if e.code == ErrorKind::Complete
+            ));
         }
-        Ok((&[], tlvs))
     }
 }
@@ -6,7 +6,10 @@ use nom::number;
 use nom::IResult;
 use std::str;
 
-#[derive(Debug, PartialEq, Eq, Default)]
+use crate::encoder::{Encode, Encoder};
+use crate::objects::of::ObjectiveFunction;
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct StatefulPCECapabilityTLV {
     pub tlv_type: u16,
     pub tlv_len: u16,
@@ -36,6 +39,18 @@ impl StatefulPCECapabilityTLV {
     }
 }
 
+impl Encode for StatefulPCECapabilityTLV {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let flags: u32 = (self.flag_lsp_update_capability as u32)
+            | (self.flag_include_db_version as u32) << 1
+            | (self.flag_lsp_instantiate_capability as u32) << 2
+            | (self.flag_triggered_resync as u32) << 3
+            | (self.flag_delta_lsp_sync_capability as u32) << 4
+            | (self.flag_triggered_initial_sync as u32) << 5;
+        Encoder::encode_tlv(buf, 16, &flags.to_be_bytes());
+    }
+}
+
 impl std::fmt::Display for StatefulPCECapabilityTLV {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let title = "[[data]]".bold();
@@ -65,11 +80,15 @@ impl std::fmt::Display for StatefulPCECapabilityTLV {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct SrPCECapabilityTLV {
     pub tlv_type: u16,
     pub tlv_len: u16,
     pub reserved: u16,
+    // N: the PCC resolves NAIs to SIDs, so SR-ERO subobjects may omit
+    // the SID
+    pub flag_nai: bool,
+    // L: the PCC imposes no limit on the number of SIDs
     pub flag_limit: bool,
     pub max_sid_depth: u8,
 }
@@ -85,6 +104,7 @@ impl SrPCECapabilityTLV {
             tlv_type: 26,
             tlv_len,
             reserved,
+            flag_nai: flags & 0b10 == 0b10,
             flag_limit: flags & 0b1 == 0b1,
             max_sid_depth,
         };
@@ -92,6 +112,15 @@ impl SrPCECapabilityTLV {
     }
 }
 
+impl Encode for SrPCECapabilityTLV {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut value = self.reserved.to_be_bytes().to_vec();
+        value.push((self.flag_nai as u8) << 1 | self.flag_limit as u8);
+        value.push(self.max_sid_depth);
+        Encoder::encode_tlv(buf, 26, &value);
+    }
+}
+
 impl std::fmt::Display for SrPCECapabilityTLV {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let title = "[[data]]".bold();
@@ -102,6 +131,7 @@ impl std::fmt::Display for SrPCECapabilityTLV {
                      tlv_type        = {tlv_type}
                      tlv_length      = {tlv_length}
                      reserved        = {reserved}
+                     nai_flag        = {nai}
                      limit_flag      = {limit}
                      max_sid_depth   = {max_sid_depth}
             "#,
@@ -109,13 +139,14 @@ impl std::fmt::Display for SrPCECapabilityTLV {
             tlv_type = self.tlv_type,
             tlv_length = self.tlv_len,
             reserved = self.reserved,
+            nai = self.flag_nai,
             limit = self.flag_limit,
             max_sid_depth = self.max_sid_depth
         )
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct UnknownTLV {
     pub tlv_type: u16,
     pub tlv_len: u16,
@@ -136,6 +167,12 @@ impl UnknownTLV {
     }
 }
 
+impl Encode for UnknownTLV {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        Encoder::encode_tlv(buf, self.tlv_type, &self.tlv_data);
+    }
+}
+
 impl std::fmt::Display for UnknownTLV {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let title = "[[data]]".bold();
@@ -154,7 +191,7 @@ impl std::fmt::Display for UnknownTLV {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct SymbolicPathNameTLV {
     pub tlv_type: u16,
     pub tlv_len: u16,
@@ -178,6 +215,12 @@ impl SymbolicPathNameTLV {
     }
 }
 
+impl Encode for SymbolicPathNameTLV {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        Encoder::encode_tlv(buf, 17, self.symbolic_path_name.as_bytes());
+    }
+}
+
 impl std::fmt::Display for SymbolicPathNameTLV {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let title = "[[data]]".bold();
@@ -198,7 +241,7 @@ impl std::fmt::Display for SymbolicPathNameTLV {
 
 use std::net::Ipv4Addr;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Ipv4LSPIndetifiersTLV {
     pub tlv_type: u16,
     pub tlv_len: u16,
@@ -250,6 +293,17 @@ impl Ipv4LSPIndetifiersTLV {
     }
 }
 
+impl Encode for Ipv4LSPIndetifiersTLV {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut value = self.tunnel_sender_address.octets().to_vec();
+        value.extend_from_slice(&self.lsp_id.to_be_bytes());
+        value.extend_from_slice(&self.tunnel_id.to_be_bytes());
+        value.extend_from_slice(&self.extended_tunnel_id.to_be_bytes());
+        value.extend_from_slice(&self.tunnel_endpoint_address.octets());
+        Encoder::encode_tlv(buf, 18, &value);
+    }
+}
+
 impl std::fmt::Display for Ipv4LSPIndetifiersTLV {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let title = "[[data]]".bold();
@@ -275,3 +329,488 @@ impl std::fmt::Display for Ipv4LSPIndetifiersTLV {
         )
     }
 }
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct OfListTLV {
+    pub tlv_type: u16,
+    pub tlv_len: u16,
+    pub of_codes: Vec<ObjectiveFunction>,
+}
+
+impl OfListTLV {
+    pub fn parse_tlv(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, tlv_len) = number::streaming::be_u16(input)?;
+        let (remaining, mut tlv_data) = bytes::streaming::take(tlv_len as usize)(remaining)?;
+        let mut of_codes = vec![];
+        while !tlv_data.is_empty() {
+            let (left, of_code) = number::streaming::be_u16(tlv_data)?;
+            of_codes.push(of_code.into());
+            tlv_data = left;
+        }
+        let tlv = OfListTLV {
+            tlv_type: 4,
+            tlv_len,
+            of_codes,
+        };
+        Ok((remaining, tlv))
+    }
+}
+
+impl Encode for OfListTLV {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut value = vec![];
+        for of_code in self.of_codes.iter() {
+            value.extend_from_slice(&u16::from(*of_code).to_be_bytes());
+        }
+        Encoder::encode_tlv(buf, 4, &value);
+    }
+}
+
+impl std::fmt::Display for OfListTLV {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = "[[data]]".bold();
+        let of_codes_str = self
+            .of_codes
+            .iter()
+            .map(|of_code| format!("{}", of_code))
+            .collect::<Vec<String>>()
+            .join(", ");
+        writedoc!(
+            f,
+            r#"
+                {title}
+                     tlv_type: {tlv_type}
+                     tlv_len : {tlv_len}
+                     of_codes: [{of_codes}]
+            "#,
+            tlv_type = self.tlv_type,
+            tlv_len = self.tlv_len,
+            of_codes = of_codes_str
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct NoPathVectorTLV {
+    pub tlv_type: u16,
+    pub tlv_len: u16,
+    pub flags: u32,
+    pub flag_pce_unavailable: bool,
+    pub flag_unknown_destination: bool,
+    pub flag_unknown_source: bool,
+    pub flag_brpc_chain_unavailable: bool,
+    pub flag_pks_expansion_failure: bool,
+    pub flag_no_gco_migration_path: bool,
+    pub flag_no_gco_solution: bool,
+    pub flag_p2mp_reachability: bool,
+}
+
+impl NoPathVectorTLV {
+    pub fn parse_tlv(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, tlv_len) = number::streaming::be_u16(input)?;
+        let (remaining, flags) = number::streaming::be_u32(remaining)?;
+        let tlv = NoPathVectorTLV {
+            tlv_type: 1,
+            tlv_len,
+            flags,
+            flag_pce_unavailable: flags & 0b0000_0001 == 0b0000_0001,
+            flag_unknown_destination: flags & 0b0000_0010 == 0b0000_0010,
+            flag_unknown_source: flags & 0b0000_0100 == 0b0000_0100,
+            flag_brpc_chain_unavailable: flags & 0b0000_1000 == 0b0000_1000,
+            flag_pks_expansion_failure: flags & 0b0001_0000 == 0b0001_0000,
+            flag_no_gco_migration_path: flags & 0b0010_0000 == 0b0010_0000,
+            flag_no_gco_solution: flags & 0b0100_0000 == 0b0100_0000,
+            flag_p2mp_reachability: flags & 0b1000_0000 == 0b1000_0000,
+        };
+        Ok((remaining, tlv))
+    }
+}
+
+impl Encode for NoPathVectorTLV {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let flags: u32 = (self.flags & !0b1111_1111)
+            | (self.flag_pce_unavailable as u32)
+            | (self.flag_unknown_destination as u32) << 1
+            | (self.flag_unknown_source as u32) << 2
+            | (self.flag_brpc_chain_unavailable as u32) << 3
+            | (self.flag_pks_expansion_failure as u32) << 4
+            | (self.flag_no_gco_migration_path as u32) << 5
+            | (self.flag_no_gco_solution as u32) << 6
+            | (self.flag_p2mp_reachability as u32) << 7;
+        Encoder::encode_tlv(buf, 1, &flags.to_be_bytes());
+    }
+}
+
+impl std::fmt::Display for NoPathVectorTLV {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = "[[data]]".bold();
+        writedoc!(
+            f,
+            r#"
+               {title}
+                    tlv_type                    = {tlv_type}
+                    tlv_len                     = {tlv_len}
+                    flags                       = {flags}
+                    pce_unavailable             = {pce_unavailable}
+                    unknown_destination         = {unknown_destination}
+                    unknown_source              = {unknown_source}
+                    brpc_chain_unavailable      = {brpc_chain_unavailable}
+                    pks_expansion_failure       = {pks_expansion_failure}
+                    no_gco_migration_path       = {no_gco_migration_path}
+                    no_gco_solution             = {no_gco_solution}
+                    p2mp_reachability_problem   = {p2mp_reachability}
+            "#,
+            title = title,
+            tlv_type = self.tlv_type,
+            tlv_len = self.tlv_len,
+            flags = self.flags,
+            pce_unavailable = self.flag_pce_unavailable,
+            unknown_destination = self.flag_unknown_destination,
+            unknown_source = self.flag_unknown_source,
+            brpc_chain_unavailable = self.flag_brpc_chain_unavailable,
+            pks_expansion_failure = self.flag_pks_expansion_failure,
+            no_gco_migration_path = self.flag_no_gco_migration_path,
+            no_gco_solution = self.flag_no_gco_solution,
+            p2mp_reachability = self.flag_p2mp_reachability
+        )
+    }
+}
+
+// LSP Error Codes, as per RFC 8231 section 7.3.3
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum LspErrorCode {
+    #[default]
+    UnknownReason,
+    LimitReached,
+    TooManyPendingUpdates,
+    UnacceptableParameters,
+    InternalError,
+    AdministrativelyDown,
+    Preempted,
+    RsvpSignalingError,
+    Unassigned(u32),
+}
+
+impl From<u32> for LspErrorCode {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => Self::UnknownReason,
+            2 => Self::LimitReached,
+            3 => Self::TooManyPendingUpdates,
+            4 => Self::UnacceptableParameters,
+            5 => Self::InternalError,
+            6 => Self::AdministrativelyDown,
+            7 => Self::Preempted,
+            8 => Self::RsvpSignalingError,
+            _ => Self::Unassigned(value),
+        }
+    }
+}
+
+impl From<LspErrorCode> for u32 {
+    fn from(value: LspErrorCode) -> Self {
+        match value {
+            LspErrorCode::UnknownReason => 1,
+            LspErrorCode::LimitReached => 2,
+            LspErrorCode::TooManyPendingUpdates => 3,
+            LspErrorCode::UnacceptableParameters => 4,
+            LspErrorCode::InternalError => 5,
+            LspErrorCode::AdministrativelyDown => 6,
+            LspErrorCode::Preempted => 7,
+            LspErrorCode::RsvpSignalingError => 8,
+            LspErrorCode::Unassigned(x) => x,
+        }
+    }
+}
+
+impl std::fmt::Display for LspErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownReason => write!(f, "LspErrorCode::Unknown reason"),
+            Self::LimitReached => {
+                write!(f, "LspErrorCode::Limit reached for PCE-controlled LSPs")
+            }
+            Self::TooManyPendingUpdates => {
+                write!(f, "LspErrorCode::Too many pending LSP update requests")
+            }
+            Self::UnacceptableParameters => write!(f, "LspErrorCode::Unacceptable parameters"),
+            Self::InternalError => write!(f, "LspErrorCode::Internal error"),
+            Self::AdministrativelyDown => {
+                write!(f, "LspErrorCode::LSP administratively brought down")
+            }
+            Self::Preempted => write!(f, "LspErrorCode::LSP preempted"),
+            Self::RsvpSignalingError => write!(f, "LspErrorCode::RSVP signaling error"),
+            Self::Unassigned(x) => write!(f, "LspErrorCode::Unassigned({})", x),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct LspErrorCodeTLV {
+    pub tlv_type: u16,
+    pub tlv_len: u16,
+    pub error_code: LspErrorCode,
+}
+
+impl LspErrorCodeTLV {
+    pub fn parse_tlv(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, tlv_len) = number::streaming::be_u16(input)?;
+        let (remaining, error_code) = number::streaming::be_u32(remaining)?;
+        let tlv = LspErrorCodeTLV {
+            tlv_type: 20,
+            tlv_len,
+            error_code: error_code.into(),
+        };
+        Ok((remaining, tlv))
+    }
+}
+
+impl Encode for LspErrorCodeTLV {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        Encoder::encode_tlv(buf, 20, &u32::from(self.error_code).to_be_bytes());
+    }
+}
+
+impl std::fmt::Display for LspErrorCodeTLV {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = "[[data]]".bold();
+        writedoc!(
+            f,
+            r#"
+                {title}
+                     tlv_type: {tlv_type}
+                     tlv_len : {tlv_len}
+                     error_code: {error_code}
+            "#,
+            tlv_type = self.tlv_type,
+            tlv_len = self.tlv_len,
+            error_code = self.error_code
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct LspDbVersionTLV {
+    pub tlv_type: u16,
+    pub tlv_len: u16,
+    pub lsp_state_db_version: u64,
+}
+
+impl LspDbVersionTLV {
+    pub fn parse_tlv(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, tlv_len) = number::streaming::be_u16(input)?;
+        let (remaining, lsp_state_db_version) = number::streaming::be_u64(remaining)?;
+        let tlv = LspDbVersionTLV {
+            tlv_type: 23,
+            tlv_len,
+            lsp_state_db_version,
+        };
+        Ok((remaining, tlv))
+    }
+}
+
+impl Encode for LspDbVersionTLV {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        Encoder::encode_tlv(buf, 23, &self.lsp_state_db_version.to_be_bytes());
+    }
+}
+
+impl std::fmt::Display for LspDbVersionTLV {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = "[[data]]".bold();
+        writedoc!(
+            f,
+            r#"
+                {title}
+                     tlv_type: {tlv_type}
+                     tlv_len : {tlv_len}
+                     lsp_state_db_version: {lsp_state_db_version}
+            "#,
+            tlv_type = self.tlv_type,
+            tlv_len = self.tlv_len,
+            lsp_state_db_version = self.lsp_state_db_version
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct MultipathCapTLV {
+    pub tlv_type: u16,
+    pub tlv_len: u16,
+    pub number_of_multipaths: u16,
+    pub flags: u16,
+    pub flag_weight: bool,
+    pub flag_backup: bool,
+    pub flag_oppdir: bool,
+}
+
+impl MultipathCapTLV {
+    pub fn parse_tlv(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, tlv_len) = number::streaming::be_u16(input)?;
+        let (remaining, number_of_multipaths) = number::streaming::be_u16(remaining)?;
+        let (remaining, flags) = number::streaming::be_u16(remaining)?;
+        let tlv = MultipathCapTLV {
+            tlv_type: 60,
+            tlv_len,
+            number_of_multipaths,
+            flags,
+            flag_weight: flags & 0b001 == 0b001,
+            flag_backup: flags & 0b010 == 0b010,
+            flag_oppdir: flags & 0b100 == 0b100,
+        };
+        Ok((remaining, tlv))
+    }
+}
+
+impl Encode for MultipathCapTLV {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let flags: u16 = (self.flags & !0b111)
+            | (self.flag_weight as u16)
+            | (self.flag_backup as u16) << 1
+            | (self.flag_oppdir as u16) << 2;
+        let mut value = self.number_of_multipaths.to_be_bytes().to_vec();
+        value.extend_from_slice(&flags.to_be_bytes());
+        Encoder::encode_tlv(buf, 60, &value);
+    }
+}
+
+impl std::fmt::Display for MultipathCapTLV {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = "[[data]]".bold();
+        writedoc!(
+            f,
+            r#"
+                {title}
+                     tlv_type             = {tlv_type}
+                     tlv_len              = {tlv_len}
+                     number_of_multipaths = {number_of_multipaths}
+                     flags                = {flags}
+                     weight_capability    = {weight}
+                     backup_capability    = {backup}
+                     oppdir_capability    = {oppdir}
+            "#,
+            tlv_type = self.tlv_type,
+            tlv_len = self.tlv_len,
+            number_of_multipaths = self.number_of_multipaths,
+            flags = self.flags,
+            weight = self.flag_weight,
+            backup = self.flag_backup,
+            oppdir = self.flag_oppdir
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct MultipathWeightTLV {
+    pub tlv_type: u16,
+    pub tlv_len: u16,
+    pub weight: u32,
+}
+
+impl MultipathWeightTLV {
+    pub fn parse_tlv(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, tlv_len) = number::streaming::be_u16(input)?;
+        let (remaining, weight) = number::streaming::be_u32(remaining)?;
+        let tlv = MultipathWeightTLV {
+            tlv_type: 61,
+            tlv_len,
+            weight,
+        };
+        Ok((remaining, tlv))
+    }
+}
+
+impl Encode for MultipathWeightTLV {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        Encoder::encode_tlv(buf, 61, &self.weight.to_be_bytes());
+    }
+}
+
+impl std::fmt::Display for MultipathWeightTLV {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = "[[data]]".bold();
+        writedoc!(
+            f,
+            r#"
+                {title}
+                     tlv_type: {tlv_type}
+                     tlv_len : {tlv_len}
+                     weight  : {weight}
+            "#,
+            tlv_type = self.tlv_type,
+            tlv_len = self.tlv_len,
+            weight = self.weight
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct MultipathBackupTLV {
+    pub tlv_type: u16,
+    pub tlv_len: u16,
+    pub backup_path_count: u16,
+    pub flags: u16,
+    // B flag: this path is a pure backup path
+    pub flag_backup: bool,
+    pub backup_path_ids: Vec<u32>,
+}
+
+impl MultipathBackupTLV {
+    pub fn parse_tlv(input: &[u8]) -> IResult<&[u8], Self> {
+        let (remaining, tlv_len) = number::streaming::be_u16(input)?;
+        let (remaining, tlv_data) = bytes::streaming::take(tlv_len as usize)(remaining)?;
+        let (tlv_data, backup_path_count) = number::streaming::be_u16(tlv_data)?;
+        let (mut tlv_data, flags) = number::streaming::be_u16(tlv_data)?;
+        let mut backup_path_ids = vec![];
+        while !tlv_data.is_empty() {
+            let (left, backup_path_id) = number::streaming::be_u32(tlv_data)?;
+            backup_path_ids.push(backup_path_id);
+            tlv_data = left;
+        }
+        let tlv = MultipathBackupTLV {
+            tlv_type: 62,
+            tlv_len,
+            backup_path_count,
+            flags,
+            flag_backup: flags & 0b1 == 0b1,
+            backup_path_ids,
+        };
+        Ok((remaining, tlv))
+    }
+}
+
+impl Encode for MultipathBackupTLV {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let flags: u16 = (self.flags & !0b1) | self.flag_backup as u16;
+        let mut value = self.backup_path_count.to_be_bytes().to_vec();
+        value.extend_from_slice(&flags.to_be_bytes());
+        for backup_path_id in self.backup_path_ids.iter() {
+            value.extend_from_slice(&backup_path_id.to_be_bytes());
+        }
+        Encoder::encode_tlv(buf, 62, &value);
+    }
+}
+
+impl std::fmt::Display for MultipathBackupTLV {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = "[[data]]".bold();
+        writedoc!(
+            f,
+            r#"
+                {title}
+                     tlv_type          = {tlv_type}
+                     tlv_len           = {tlv_len}
+                     backup_path_count = {backup_path_count}
+                     flags             = {flags}
+                     flag_backup       = {flag_backup}
+                     backup_path_ids   = {backup_path_ids:?}
+            "#,
+            tlv_type = self.tlv_type,
+            tlv_len = self.tlv_len,
+            backup_path_count = self.backup_path_count,
+            flags = self.flags,
+            flag_backup = self.flag_backup,
+            backup_path_ids = self.backup_path_ids
+        )
+    }
+}
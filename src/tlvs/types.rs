@@ -1,27 +1,46 @@
+use crate::encoder::Encode;
+use crate::registry::CustomTlv;
 use crate::tlvs::tlv_set::{
-    Ipv4LSPIndetifiersTLV, SrPCECapabilityTLV, StatefulPCECapabilityTLV, SymbolicPathNameTLV,
-    UnknownTLV,
+    Ipv4LSPIndetifiersTLV, LspDbVersionTLV, LspErrorCodeTLV, MultipathBackupTLV, MultipathCapTLV,
+    MultipathWeightTLV, NoPathVectorTLV, OfListTLV, SrPCECapabilityTLV, StatefulPCECapabilityTLV,
+    SymbolicPathNameTLV, UnknownTLV,
 };
 use colored::Colorize;
 use indoc::writedoc;
 
 #[non_exhaustive]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Tlv {
     StatefulPCECapability(StatefulPCECapabilityTLV),
     SrPCECapability(SrPCECapabilityTLV),
     SymbolicPathName(SymbolicPathNameTLV),
     Ipv4LSPIndetifiers(Ipv4LSPIndetifiersTLV),
+    OfList(OfListTLV),
+    NoPathVector(NoPathVectorTLV),
+    LspErrorCode(LspErrorCodeTLV),
+    LspDbVersion(LspDbVersionTLV),
+    MultipathCap(MultipathCapTLV),
+    MultipathWeight(MultipathWeightTLV),
+    MultipathBackup(MultipathBackupTLV),
     Unknown(UnknownTLV),
+    // Decoded by a TlvDecoder registered for its type, see registry
+    Custom(Box<dyn CustomTlv>),
 }
 
 impl From<u16> for Tlv {
     fn from(value: u16) -> Self {
         match value {
+            1 => Self::NoPathVector(Default::default()),
+            4 => Self::OfList(Default::default()),
             16 => Self::StatefulPCECapability(Default::default()),
             17 => Self::SymbolicPathName(Default::default()),
             18 => Self::Ipv4LSPIndetifiers(Default::default()),
+            20 => Self::LspErrorCode(Default::default()),
+            23 => Self::LspDbVersion(Default::default()),
             26 => Self::SrPCECapability(Default::default()),
+            60 => Self::MultipathCap(Default::default()),
+            61 => Self::MultipathWeight(Default::default()),
+            62 => Self::MultipathBackup(Default::default()),
             _ => Self::Unknown(Default::default()),
         }
     }
@@ -86,6 +105,104 @@ impl std::fmt::Display for Tlv {
                     indent = 4
                 )
             }
+            Self::OfList(ofl) => {
+                let title = "==[OF-LIST TLV]==".green().bold();
+                writedoc!(
+                    f,
+                    r#"
+                    {:indent$}{title}
+                        {tlv}
+                    "#,
+                    "",
+                    title = title,
+                    tlv = ofl,
+                    indent = 4
+                )
+            }
+            Self::NoPathVector(npv) => {
+                let title = "==[NO-PATH-VECTOR TLV]==".green().bold();
+                writedoc!(
+                    f,
+                    r#"
+                    {:indent$}{title}
+                        {tlv}
+                    "#,
+                    "",
+                    title = title,
+                    tlv = npv,
+                    indent = 4
+                )
+            }
+            Self::LspErrorCode(lec) => {
+                let title = "==[LSP-ERROR-CODE TLV]==".green().bold();
+                writedoc!(
+                    f,
+                    r#"
+                    {:indent$}{title}
+                        {tlv}
+                    "#,
+                    "",
+                    title = title,
+                    tlv = lec,
+                    indent = 4
+                )
+            }
+            Self::LspDbVersion(ldv) => {
+                let title = "==[LSP-DB-VERSION TLV]==".green().bold();
+                writedoc!(
+                    f,
+                    r#"
+                    {:indent$}{title}
+                        {tlv}
+                    "#,
+                    "",
+                    title = title,
+                    tlv = ldv,
+                    indent = 4
+                )
+            }
+            Self::MultipathCap(mpc) => {
+                let title = "==[MULTIPATH-CAP TLV]==".green().bold();
+                writedoc!(
+                    f,
+                    r#"
+                    {:indent$}{title}
+                        {tlv}
+                    "#,
+                    "",
+                    title = title,
+                    tlv = mpc,
+                    indent = 4
+                )
+            }
+            Self::MultipathWeight(mpw) => {
+                let title = "==[MULTIPATH-WEIGHT TLV]==".green().bold();
+                writedoc!(
+                    f,
+                    r#"
+                    {:indent$}{title}
+                        {tlv}
+                    "#,
+                    "",
+                    title = title,
+                    tlv = mpw,
+                    indent = 4
+                )
+            }
+            Self::MultipathBackup(mpb) => {
+                let title = "==[MULTIPATH-BACKUP TLV]==".green().bold();
+                writedoc!(
+                    f,
+                    r#"
+                    {:indent$}{title}
+                        {tlv}
+                    "#,
+                    "",
+                    title = title,
+                    tlv = mpb,
+                    indent = 4
+                )
+            }
             Self::Unknown(x) => {
                 let title = "==[UNKNOWN TLV]==".green().bold();
                 writedoc!(
@@ -100,6 +217,64 @@ impl std::fmt::Display for Tlv {
                     indent = 4
                 )
             }
+            Self::Custom(x) => {
+                let title = "==[CUSTOM TLV]==".green().bold();
+                writedoc!(
+                    f,
+                    r#"
+                    {:indent$}{title}
+                        {tlv}
+                    "#,
+                    "",
+                    title = title,
+                    tlv = x,
+                    indent = 4
+                )
+            }
+        }
+    }
+}
+
+impl Tlv {
+    // Set tlv_len to the value length encode() writes, for TLVs built in
+    // memory. Custom TLVs keep no length field.
+    pub(crate) fn set_tlv_len(&mut self) {
+        let bytes = self.to_bytes();
+        let tlv_len = u16::from_be_bytes([bytes[2], bytes[3]]);
+        match self {
+            Self::StatefulPCECapability(tlv) => tlv.tlv_len = tlv_len,
+            Self::SrPCECapability(tlv) => tlv.tlv_len = tlv_len,
+            Self::SymbolicPathName(tlv) => tlv.tlv_len = tlv_len,
+            Self::Ipv4LSPIndetifiers(tlv) => tlv.tlv_len = tlv_len,
+            Self::OfList(tlv) => tlv.tlv_len = tlv_len,
+            Self::NoPathVector(tlv) => tlv.tlv_len = tlv_len,
+            Self::LspErrorCode(tlv) => tlv.tlv_len = tlv_len,
+            Self::LspDbVersion(tlv) => tlv.tlv_len = tlv_len,
+            Self::MultipathCap(tlv) => tlv.tlv_len = tlv_len,
+            Self::MultipathWeight(tlv) => tlv.tlv_len = tlv_len,
+            Self::MultipathBackup(tlv) => tlv.tlv_len = tlv_len,
+            Self::Unknown(tlv) => tlv.tlv_len = tlv_len,
+            Self::Custom(_tlv) => {}
+        }
+    }
+}
+
+impl Encode for Tlv {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        match self {
+            Self::StatefulPCECapability(tlv) => tlv.encode(buf),
+            Self::SrPCECapability(tlv) => tlv.encode(buf),
+            Self::SymbolicPathName(tlv) => tlv.encode(buf),
+            Self::Ipv4LSPIndetifiers(tlv) => tlv.encode(buf),
+            Self::OfList(tlv) => tlv.encode(buf),
+            Self::NoPathVector(tlv) => tlv.encode(buf),
+            Self::LspErrorCode(tlv) => tlv.encode(buf),
+            Self::LspDbVersion(tlv) => tlv.encode(buf),
+            Self::MultipathCap(tlv) => tlv.encode(buf),
+            Self::MultipathWeight(tlv) => tlv.encode(buf),
+            Self::MultipathBackup(tlv) => tlv.encode(buf),
+            Self::Unknown(tlv) => tlv.encode(buf),
+            Self::Custom(tlv) => tlv.encode(buf),
         }
     }
 }
@@ -0,0 +1,720 @@
+use crate::borrowed::ObjectView;
+use crate::error;
+use crate::messages::header::CommonHeader;
+use crate::messages::message::PcepMessage;
+use crate::objects::classes::ObjectClassType;
+use crate::objects::ero::EroObject;
+use crate::objects::srp::SrpObject;
+use crate::options::{Diagnostics, ParseOptions};
+use crate::subobjects::sr::SrSubobject;
+use crate::subobjects::types::SubObjectTypes;
+use crate::tlvs::tlv_set::SrPCECapabilityTLV;
+
+use nom::error::{Error, ErrorKind};
+use nom::{Err, IResult};
+
+// A length field that disagrees with the bytes it describes, offset is
+// counted from the start of the message
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LengthIssue {
+    pub offset: usize,
+    pub kind: LengthIssueKind,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LengthIssueKind {
+    // message_length below the 4 byte common header
+    MessageLength { length: u16 },
+    // Body bytes the message format has no place for
+    UnparsedBody { length: usize },
+    // object_length below 4, not a multiple of 4 or too short for the
+    // fixed fields of the object
+    ObjectLength { length: u16 },
+    // subobject_len below 4 or not a multiple of 4
+    SubObjectLength { length: u8 },
+    // tlv_len that does not fit the value format of the TLV type
+    TlvLength { tlv_type: u16, length: u16 },
+    // An item running past the end of its container, needed is the
+    // number of bytes beyond it
+    Overrun { needed: usize },
+}
+
+impl std::fmt::Display for LengthIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            LengthIssueKind::MessageLength { length } => write!(
+                f,
+                "offset {}: message_length {} is shorter than the common header",
+                self.offset, length
+            ),
+            LengthIssueKind::UnparsedBody { length } => write!(
+                f,
+                "offset {}: {} trailing bytes in the message body",
+                self.offset, length
+            ),
+            LengthIssueKind::ObjectLength { length } => write!(
+                f,
+                "offset {}: object_length {} is below 4 or not a multiple of 4",
+                self.offset, length
+            ),
+            LengthIssueKind::SubObjectLength { length } => write!(
+                f,
+                "offset {}: subobject_len {} is below 4 or not a multiple of 4",
+                self.offset, length
+            ),
+            LengthIssueKind::TlvLength { tlv_type, length } => write!(
+                f,
+                "offset {}: tlv_len {} does not match TLV type {}",
+                self.offset, length, tlv_type
+            ),
+            LengthIssueKind::Overrun { needed } => write!(
+                f,
+                "offset {}: length runs {} bytes past its container",
+                self.offset, needed
+            ),
+        }
+    }
+}
+
+// A rule of RFC 8231 or RFC 8281 that a decoded message breaks, request
+// is the index of the offending request or report in the message
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Violation {
+    pub request: usize,
+    pub kind: ViolationKind,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ViolationKind {
+    // SRP-ID-number 0x00000000 and 0xFFFFFFFF are reserved
+    ReservedSrpId { srp_id: u32 },
+    // PCInitiate instantiation with a PLSP-ID other than 0
+    NonZeroPlspId { plsp_id: u32 },
+    // PCInitiate instantiation without a SYMBOLIC-PATH-NAME TLV
+    MissingSymbolicPathName,
+    // PCInitiate deletion without the SRP R flag
+    MissingRemoveFlag { plsp_id: u32 },
+    // PCInitiate deletion or PCUpd with PLSP-ID 0
+    ZeroPlspId,
+    // PCUpd for an LSP without the D flag
+    NotDelegated { plsp_id: u32 },
+    // PCRpt, other than the end of synchronization marker, without an
+    // LSP-IDENTIFIERS TLV
+    MissingLspIdentifiers { plsp_id: u32 },
+    // More SR-ERO subobjects than the Maximum SID Depth of the PCC
+    SidDepthExceeded { sids: usize, max_sid_depth: u8 },
+    // SR-ERO subobject without SID, hop is its index in the ERO, while
+    // the PCC did not set the N flag
+    NaiOnlyHop { hop: usize },
+    // ERO with both SR-ERO and other subobjects
+    MixedSubobjects,
+}
+
+impl ViolationKind {
+    // Error-type and Error-value of the PCErr a compliant peer answers
+    // with. Reserved SRP-IDs have no value of their own, the SRP object
+    // carrying one is a malformed object.
+    pub fn pcerr(&self) -> (u8, u8) {
+        match self {
+            Self::ReservedSrpId { .. } => (10, 11),
+            // A missing R flag makes the deletion an instantiation
+            Self::NonZeroPlspId { .. } | Self::MissingRemoveFlag { .. } => (19, 8),
+            Self::MissingSymbolicPathName => (10, 8),
+            Self::ZeroPlspId => (19, 3),
+            Self::NotDelegated { .. } => (19, 1),
+            Self::MissingLspIdentifiers { .. } => (6, 11),
+            Self::SidDepthExceeded { .. } => (10, 3),
+            // Unsupported parameter
+            Self::NaiOnlyHop { .. } => (4, 4),
+            Self::MixedSubobjects => (10, 5),
+        }
+    }
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (error_type, error_value) = self.kind.pcerr();
+        write!(f, "request {}: ", self.request)?;
+        match self.kind {
+            ViolationKind::ReservedSrpId { srp_id } => write!(f, "reserved SRP-ID {:#x}", srp_id)?,
+            ViolationKind::NonZeroPlspId { plsp_id } => {
+                write!(f, "instantiation with PLSP-ID {}", plsp_id)?
+            }
+            ViolationKind::MissingSymbolicPathName => {
+                write!(f, "instantiation without SYMBOLIC-PATH-NAME")?
+            }
+            ViolationKind::MissingRemoveFlag { plsp_id } => {
+                write!(f, "deletion of PLSP-ID {} without the R flag", plsp_id)?
+            }
+            ViolationKind::ZeroPlspId => write!(f, "PLSP-ID 0")?,
+            ViolationKind::NotDelegated { plsp_id } => {
+                write!(f, "update of non delegated PLSP-ID {}", plsp_id)?
+            }
+            ViolationKind::MissingLspIdentifiers { plsp_id } => {
+                write!(f, "report of PLSP-ID {} without LSP-IDENTIFIERS", plsp_id)?
+            }
+            ViolationKind::SidDepthExceeded {
+                sids,
+                max_sid_depth,
+            } => write!(f, "{} SIDs for a MSD of {}", sids, max_sid_depth)?,
+            ViolationKind::NaiOnlyHop { hop } => {
+                write!(f, "hop {} has no SID and the PCC resolves no NAI", hop)?
+            }
+            ViolationKind::MixedSubobjects => {
+                write!(f, "ERO mixes SR-ERO subobjects with other subobject types")?
+            }
+        }
+        write!(f, " (PCErr {}/{})", error_type, error_value)
+    }
+}
+
+// Violation of srp_id at request, if it is a reserved value
+pub(crate) fn check_srp_id(request: usize, srp_id: u32) -> Option<Violation> {
+    SrpObject::is_reserved_srp_id(srp_id).then_some(Violation {
+        request,
+        kind: ViolationKind::ReservedSrpId { srp_id },
+    })
+}
+
+// RFC 8664 SR-ERO rules for an ERO sent to a PCC that advertised
+// capability in its Open
+pub fn check_sr_ero(
+    request: usize,
+    ero_object: &EroObject,
+    capability: &SrPCECapabilityTLV,
+) -> Vec<Violation> {
+    let mut violations = vec![];
+    let violation = |kind| Violation { request, kind };
+    let subobjects = ero_object.subobjects.as_deref().unwrap_or_default();
+    let sr_subobjects: Vec<(usize, &SrSubobject)> = subobjects
+        .iter()
+        .enumerate()
+        .filter_map(|(hop, subobject)| match subobject.subobject_type {
+            SubObjectTypes::Sr(ref sr_subobject) => Some((hop, sr_subobject)),
+            _ => None,
+        })
+        .collect();
+    if sr_subobjects.is_empty() {
+        return violations;
+    }
+    if sr_subobjects.len() < subobjects.len() {
+        violations.push(violation(ViolationKind::MixedSubobjects));
+    }
+    if !capability.flag_limit && sr_subobjects.len() > capability.max_sid_depth as usize {
+        violations.push(violation(ViolationKind::SidDepthExceeded {
+            sids: sr_subobjects.len(),
+            max_sid_depth: capability.max_sid_depth,
+        }));
+    }
+    if !capability.flag_nai {
+        for (hop, sr_subobject) in sr_subobjects {
+            if sr_subobject.flag_s {
+                violations.push(violation(ViolationKind::NaiOnlyHop { hop }));
+            }
+        }
+    }
+    violations
+}
+
+// Check the length fields of the first message in input against the
+// bytes actually there: the message, its objects, their TLVs and ERO
+// subobjects. Walks the raw bytes, so every issue is reported, not just
+// the first one a parser would trip over.
+pub fn check_lengths(input: &[u8]) -> Vec<LengthIssue> {
+    let mut issues = vec![];
+    let common_header = match CommonHeader::parse_common_header(input) {
+        Ok((_remaining, common_header)) => common_header,
+        Err(_) => {
+            if input.len() < 4 {
+                issues.push(LengthIssue {
+                    offset: input.len(),
+                    kind: LengthIssueKind::Overrun {
+                        needed: 4 - input.len(),
+                    },
+                });
+            }
+            return issues;
+        }
+    };
+    let message_length = common_header.message_length;
+    if message_length < 4 {
+        issues.push(LengthIssue {
+            offset: 2,
+            kind: LengthIssueKind::MessageLength {
+                length: message_length,
+            },
+        });
+        return issues;
+    }
+    if message_length as usize > input.len() {
+        issues.push(LengthIssue {
+            offset: 2,
+            kind: LengthIssueKind::Overrun {
+                needed: message_length as usize - input.len(),
+            },
+        });
+    }
+    let message = &input[..input.len().min(message_length as usize)];
+    check_objects(message, 4, &mut issues);
+
+    // Only a body that decodes can tell how much of it the format uses
+    if message.len() == message_length as usize {
+        let body = &message[4..];
+        let options = ParseOptions {
+            check_lengths: false,
+            ..ParseOptions::lenient()
+        };
+        let mut diagnostics = Diagnostics::new(message, options);
+        if let Ok((left, _message)) = error::complete(body, |body| {
+            PcepMessage::parse_body(common_header, body, &mut diagnostics)
+        }) {
+            if !left.is_empty() {
+                issues.push(LengthIssue {
+                    offset: message.len() - left.len(),
+                    kind: LengthIssueKind::UnparsedBody { length: left.len() },
+                });
+            }
+        }
+    }
+    issues
+}
+
+fn be_u16_at(input: &[u8], offset: usize) -> u16 {
+    u16::from_be_bytes([input[offset], input[offset + 1]])
+}
+
+fn check_objects(message: &[u8], mut offset: usize, issues: &mut Vec<LengthIssue>) {
+    while offset < message.len() {
+        let left = message.len() - offset;
+        if left < 4 {
+            issues.push(LengthIssue {
+                offset: message.len(),
+                kind: LengthIssueKind::Overrun { needed: 4 - left },
+            });
+            return;
+        }
+        let object_length = be_u16_at(message, offset + 2);
+        if object_length < 4 || !object_length.is_multiple_of(4) {
+            issues.push(LengthIssue {
+                offset: offset + 2,
+                kind: LengthIssueKind::ObjectLength {
+                    length: object_length,
+                },
+            });
+            // Nothing to step over to reach the next object
+            if object_length < 4 {
+                return;
+            }
+        }
+        if object_length as usize > left {
+            issues.push(LengthIssue {
+                offset: offset + 2,
+                kind: LengthIssueKind::Overrun {
+                    needed: object_length as usize - left,
+                },
+            });
+            return;
+        }
+        let object_class_type: ObjectClassType = (message[offset], message[offset + 1] >> 4).into();
+        let body_start = offset + 4;
+        let body_end = offset + object_length as usize;
+        match object_class_type {
+            ObjectClassType::Ero(_) => check_subobjects(message, body_start, body_end, issues),
+            object_class_type => {
+                if let Some(tlvs_offset) = ObjectView::tlvs_offset(object_class_type) {
+                    if body_start + tlvs_offset > body_end {
+                        issues.push(LengthIssue {
+                            offset: offset + 2,
+                            kind: LengthIssueKind::ObjectLength {
+                                length: object_length,
+                            },
+                        });
+                    } else {
+                        check_tlvs(message, body_start + tlvs_offset, body_end, issues);
+                    }
+                }
+            }
+        }
+        offset = body_end;
+    }
+}
+
+fn check_subobjects(message: &[u8], mut offset: usize, end: usize, issues: &mut Vec<LengthIssue>) {
+    while offset < end {
+        let left = end - offset;
+        if left < 2 {
+            issues.push(LengthIssue {
+                offset: end,
+                kind: LengthIssueKind::Overrun { needed: 2 - left },
+            });
+            return;
+        }
+        let subobject_len = message[offset + 1];
+        if subobject_len < 4 || !subobject_len.is_multiple_of(4) {
+            issues.push(LengthIssue {
+                offset: offset + 1,
+                kind: LengthIssueKind::SubObjectLength {
+                    length: subobject_len,
+                },
+            });
+            if subobject_len < 2 {
+                return;
+            }
+        }
+        if subobject_len as usize > left {
+            issues.push(LengthIssue {
+                offset: offset + 1,
+                kind: LengthIssueKind::Overrun {
+                    needed: subobject_len as usize - left,
+                },
+            });
+            return;
+        }
+        offset += subobject_len as usize;
+    }
+}
+
+// Whether tlv_len fits the value format of the TLV types with a decoder
+fn tlv_length_fits(tlv_type: u16, tlv_len: u16) -> bool {
+    match tlv_type {
+        // NO-PATH-VECTOR, STATEFUL-PCE-CAPABILITY, LSP-ERROR-CODE,
+        // SR-PCE-CAPABILITY, MULTIPATH-CAP, MULTIPATH-WEIGHT
+        1 | 16 | 20 | 26 | 60 | 61 => tlv_len == 4,
+        // OF-LIST, a list of 2 byte codes
+        4 => tlv_len.is_multiple_of(2),
+        // SYMBOLIC-PATH-NAME
+        17 => tlv_len >= 1,
+        // IPV4-LSP-IDENTIFIERS
+        18 => tlv_len == 16,
+        // LSP-DB-VERSION
+        23 => tlv_len == 8,
+        // MULTIPATH-BACKUP, 4 bytes then 4 byte path ids
+        62 => tlv_len >= 4 && (tlv_len - 4).is_multiple_of(4),
+        _ => true,
+    }
+}
+
+fn check_tlvs(message: &[u8], mut offset: usize, end: usize, issues: &mut Vec<LengthIssue>) {
+    while offset < end {
+        let left = end - offset;
+        if left < 4 {
+            issues.push(LengthIssue {
+                offset: end,
+                kind: LengthIssueKind::Overrun { needed: 4 - left },
+            });
+            return;
+        }
+        let tlv_type = be_u16_at(message, offset);
+        let tlv_len = be_u16_at(message, offset + 2);
+        if !tlv_length_fits(tlv_type, tlv_len) {
+            issues.push(LengthIssue {
+                offset: offset + 2,
+                kind: LengthIssueKind::TlvLength {
+                    tlv_type,
+                    length: tlv_len,
+                },
+            });
+        }
+        // Value padded to a 4 byte boundary
+        let padded_len = 4 + (tlv_len as usize).div_ceil(4) * 4;
+        if padded_len > left {
+            issues.push(LengthIssue {
+                offset: offset + 2,
+                kind: LengthIssueKind::Overrun {
+                    needed: padded_len - left,
+                },
+            });
+            return;
+        }
+        offset += padded_len;
+    }
+}
+
+// parse_message that also rejects a message whose length fields do not
+// match its bytes, the error points at the first offending length field
+pub fn parse_message_strict(input: &[u8]) -> IResult<&[u8], PcepMessage> {
+    let (remaining, message) = PcepMessage::parse_message(input)?;
+    let consumed = &input[..input.len() - remaining.len()];
+    match check_lengths(consumed).first() {
+        Some(issue) => Err(Err::Failure(Error::new(
+            &input[issue.offset..],
+            ErrorKind::LengthValue,
+        ))),
+        None => Ok((remaining, message)),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::builder::{adj, ipv4_hop, sr_hop, LspBuilder};
+    use crate::common::OperationalStatus;
+    use crate::encoder::Encode;
+    use crate::messages::pcinitiate::PCInitiate;
+    use crate::messages::pcrpt::{PcRpt, StateReport};
+    use crate::messages::pcupdate::PcepUpdate;
+    use crate::objects::lsp::LspObject;
+    use crate::objects::open::OpenObject;
+    use crate::subobjects::header::SubObject;
+    use crate::tlvs::tlv_set::{Ipv4LSPIndetifiersTLV, UnknownTLV};
+    use crate::tlvs::types::Tlv;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn test_check_lengths_valid() {
+        let messages = vec![
+            PcepMessage::PcInitiate(
+                PCInitiate::builder()
+                    .lsp(LspBuilder::new().name("lsp-a"))
+                    .endpoints_ipv4(Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(10, 0, 0, 2))
+                    .ero(vec![
+                        ipv4_hop(Ipv4Addr::new(10, 0, 1, 1)),
+                        ipv4_hop(Ipv4Addr::new(10, 0, 1, 2)),
+                    ])
+                    .bandwidth(1000.0)
+                    .build(),
+            ),
+            PcepMessage::PcUpd(
+                PcepUpdate::builder()
+                    .lsp(LspBuilder::new().plsp_id(1).delegate(true))
+                    .ero(vec![ipv4_hop(Ipv4Addr::new(10, 0, 1, 1))])
+                    .build(),
+            ),
+        ];
+        for message in messages {
+            let mut buf = vec![];
+            message.encode(&mut buf);
+            assert_eq!(check_lengths(&buf), vec![]);
+            assert!(parse_message_strict(&buf).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_check_lengths_issues() {
+        // Open carrying a STATEFUL-PCE-CAPABILITY TLV declared 8 bytes long
+        // and 4 trailing bytes after the object
+        let input: &[u8] = &[
+            0x20, 0x01, 0x00, 0x1c, 0x01, 0x10, 0x00, 0x14, 0x20, 0x1e, 0x78, 0x01, 0x00, 0x10,
+            0x00, 0x08, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0xde, 0xad, 0xbe, 0xef,
+        ];
+        assert_eq!(
+            check_lengths(input),
+            vec![
+                LengthIssue {
+                    offset: 14,
+                    kind: LengthIssueKind::TlvLength {
+                        tlv_type: 16,
+                        length: 8
+                    }
+                },
+                LengthIssue {
+                    offset: 26,
+                    kind: LengthIssueKind::ObjectLength { length: 0xbeef }
+                },
+                LengthIssue {
+                    offset: 26,
+                    kind: LengthIssueKind::Overrun { needed: 0xbeef - 4 }
+                },
+                LengthIssue {
+                    offset: 24,
+                    kind: LengthIssueKind::UnparsedBody { length: 4 }
+                },
+            ]
+        );
+        // The lenient parser accepts it, the strict one points at the TLV
+        assert!(PcepMessage::parse_message(input).is_ok());
+        match parse_message_strict(input) {
+            Err(Err::Failure(e)) => {
+                assert_eq!(input.len() - e.input.len(), 14);
+                assert_eq!(e.code, ErrorKind::LengthValue);
+            }
+            _ => panic!("[!!] Expected a strict length failure"),
+        }
+
+        // Keepalive with a message_length below the header, and an ERO
+        // with a 6 byte subobject
+        assert_eq!(
+            check_lengths(&[0x20, 0x02, 0x00, 0x02]),
+            vec![LengthIssue {
+                offset: 2,
+                kind: LengthIssueKind::MessageLength { length: 2 }
+            }]
+        );
+        let input: &[u8] = &[
+            0x20, 0x0b, 0x00, 0x10, 0x07, 0x10, 0x00, 0x0c, 0x01, 0x06, 0x0a, 0x00, 0x00, 0x01,
+            0x00, 0x00,
+        ];
+        assert_eq!(
+            check_lengths(input)[0],
+            LengthIssue {
+                offset: 9,
+                kind: LengthIssueKind::SubObjectLength { length: 6 }
+            }
+        );
+    }
+
+    #[test]
+    fn test_semantic_violations() {
+        let pc_initiate = PCInitiate::builder()
+            .lsp(LspBuilder::new().name("lsp-a").delegate(true))
+            .build();
+        assert_eq!(pc_initiate.validate(), vec![]);
+        let pc_initiate = PCInitiate::builder()
+            .srp_id(0)
+            .lsp(LspBuilder::new().plsp_id(5))
+            .build();
+        let violations = pc_initiate.validate();
+        let kinds: Vec<ViolationKind> = violations.iter().map(|v| v.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ViolationKind::ReservedSrpId { srp_id: 0 },
+                ViolationKind::NonZeroPlspId { plsp_id: 5 },
+                ViolationKind::MissingSymbolicPathName,
+            ]
+        );
+        let pcerrs: Vec<(u8, u8)> = kinds.iter().map(|kind| kind.pcerr()).collect();
+        assert_eq!(pcerrs, vec![(10, 11), (19, 8), (10, 8)]);
+        let deletion = PCInitiate::builder().remove().build();
+        assert_eq!(
+            deletion.validate(),
+            vec![Violation {
+                request: 0,
+                kind: ViolationKind::ZeroPlspId
+            }]
+        );
+
+        let pcep_update = PcepUpdate::builder()
+            .srp_id(0xFFFF_FFFF)
+            .lsp(LspBuilder::new().plsp_id(0))
+            .build();
+        let pcerrs: Vec<(u8, u8)> = pcep_update
+            .validate()
+            .iter()
+            .map(|v| v.kind.pcerr())
+            .collect();
+        assert_eq!(pcerrs, vec![(10, 11), (19, 3), (19, 1)]);
+        assert_eq!(
+            format!("{}", pcep_update.validate()[2]),
+            "request 0: update of non delegated PLSP-ID 0 (PCErr 19/1)"
+        );
+
+        // A report with LSP-IDENTIFIERS follows the rules, one without
+        // does not
+        let lsp_identifiers = Tlv::Ipv4LSPIndetifiers(Ipv4LSPIndetifiersTLV {
+            tlv_type: 18,
+            tlv_len: 16,
+            tunnel_sender_address: Ipv4Addr::new(10, 0, 0, 1),
+            lsp_id: 1,
+            tunnel_id: 5,
+            extended_tunnel_id: 0,
+            tunnel_endpoint_address: Ipv4Addr::new(10, 0, 0, 2),
+        });
+        let pc_rpt = PcRpt::new(vec![StateReport::new(
+            None,
+            LspBuilder::new()
+                .plsp_id(5)
+                .name("lsp-a")
+                .tlv(lsp_identifiers)
+                .build(),
+            None,
+        )]);
+        assert_eq!(pc_rpt.validate(), vec![]);
+        let pc_rpt = PcRpt::new(vec![StateReport::new(
+            None,
+            LspObject::new(5, OperationalStatus::Up, vec![]),
+            None,
+        )]);
+        assert_eq!(
+            pc_rpt.validate()[0].kind,
+            ViolationKind::MissingLspIdentifiers { plsp_id: 5 }
+        );
+
+        // IPV6-LSP-IDENTIFIERS is not decoded but still counts
+        let ipv6_lsp_identifiers = Tlv::Unknown(UnknownTLV {
+            tlv_type: 19,
+            tlv_len: 52,
+            tlv_data: vec![0; 52],
+        });
+        let pc_rpt = PcRpt::new(vec![StateReport::new(
+            None,
+            LspObject::new(5, OperationalStatus::Up, vec![ipv6_lsp_identifiers]),
+            None,
+        )]);
+        assert_eq!(pc_rpt.validate(), vec![]);
+    }
+
+    #[test]
+    fn test_sr_ero_violations() {
+        // Open of a PCC with a MSD of 2
+        let input: &[u8] = &[
+            0x01, 0x10, 0x00, 0x10, 0x20, 0x1e, 0x78, 0x01, 0x00, 0x1a, 0x00, 0x04, 0x00, 0x00,
+            0x00, 0x02,
+        ];
+        let (_remaining, open_object) =
+            OpenObject::parse_open_object(input).expect("[!!] Error while parsing open object");
+        let capability = open_object
+            .sr_capability()
+            .expect("[!!] Expected an SR capability");
+        let (a, b, c) = (
+            Ipv4Addr::new(10, 0, 0, 1),
+            Ipv4Addr::new(10, 0, 0, 2),
+            Ipv4Addr::new(10, 0, 0, 3),
+        );
+        let pcep_update = PcepUpdate::builder()
+            .lsp(LspBuilder::new().plsp_id(1).delegate(true))
+            .ero(vec![sr_hop(24001, adj(a, b)), sr_hop(24002, adj(b, c))])
+            .build();
+        assert_eq!(pcep_update.validate_sr(capability), vec![]);
+
+        let nai_only = SubObject::new(
+            false,
+            SubObjectTypes::Sr(SrSubobject {
+                nai_type: adj(b, c),
+                flag_s: true,
+                ..Default::default()
+            }),
+        );
+        let pc_initiate = PCInitiate::builder()
+            .lsp(LspBuilder::new().name("lsp-a"))
+            .ero(vec![
+                sr_hop(24001, adj(a, b)),
+                nai_only,
+                sr_hop(24003, adj(c, a)),
+                ipv4_hop(a),
+            ])
+            .build();
+        let violations = pc_initiate.validate_sr(capability);
+        assert_eq!(
+            violations
+                .iter()
+                .map(|v| (v.kind, v.kind.pcerr()))
+                .collect::<Vec<_>>(),
+            vec![
+                (ViolationKind::MixedSubobjects, (10, 5)),
+                (
+                    ViolationKind::SidDepthExceeded {
+                        sids: 3,
+                        max_sid_depth: 2
+                    },
+                    (10, 3)
+                ),
+                (ViolationKind::NaiOnlyHop { hop: 1 }, (4, 4)),
+            ]
+        );
+
+        // No MSD limit and NAI resolution advertised
+        let capability = SrPCECapabilityTLV {
+            flag_nai: true,
+            flag_limit: true,
+            ..capability.clone()
+        };
+        assert_eq!(
+            pc_initiate.validate_sr(&capability),
+            vec![Violation {
+                request: 0,
+                kind: ViolationKind::MixedSubobjects
+            }]
+        );
+    }
+}
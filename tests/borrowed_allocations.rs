@@ -0,0 +1,82 @@
+// The borrowed views decode a message without allocating. Lives in its
+// own test binary, the counting allocator replaces the global one.
+use pcep_parser::borrowed::MessageView;
+use pcep_parser::builder::{ipv4_hop, LspBuilder};
+use pcep_parser::common::{IntendedAttrList, IntendedPathList, OperationalStatus};
+use pcep_parser::encoder::Encode;
+use pcep_parser::messages::message::PcepMessage;
+use pcep_parser::messages::pcrpt::{PcRpt, StateReport};
+use pcep_parser::messages::pcupdate::Path;
+use pcep_parser::objects::ero::EroObject;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::net::Ipv4Addr;
+
+// Counts the allocations of the current thread, tests run in parallel
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn report_bytes() -> Vec<u8> {
+    let lsp_object = LspBuilder::new()
+        .plsp_id(1)
+        .name("lsp-a")
+        .operational_status(OperationalStatus::Up)
+        .delegate(true)
+        .sync(true)
+        .build();
+    let ero_object = EroObject::new(vec![
+        ipv4_hop(Ipv4Addr::new(10, 0, 1, 1)),
+        ipv4_hop(Ipv4Addr::new(10, 0, 1, 2)),
+    ]);
+    let message = PcepMessage::PcRpt(PcRpt::new(vec![StateReport::new(
+        None,
+        lsp_object,
+        Some(Path::new(
+            IntendedPathList {
+                paths: vec![(None, ero_object)],
+            },
+            IntendedAttrList::new(None, None, vec![]),
+        )),
+    )]));
+    message.to_bytes()
+}
+
+#[test]
+fn test_borrowed_parsing_does_not_allocate() {
+    let input = report_bytes();
+    let before = ALLOCATIONS.with(|count| count.get());
+    let mut seen = 0;
+    let (_remaining, view) =
+        MessageView::parse(&input).expect("[!!] Error while parsing pcep message");
+    for object in view.objects().flatten() {
+        seen += object.body().len();
+        seen += object.tlvs().flatten().count();
+        seen += object.subobjects().flatten().count();
+    }
+    let after = ALLOCATIONS.with(|count| count.get());
+    assert!(seen > 0);
+    assert_eq!(after, before);
+
+    // The counter itself does see allocations
+    let owned = PcepMessage::parse_message(&input);
+    assert!(owned.is_ok());
+    assert!(ALLOCATIONS.with(|count| count.get()) > after);
+}